@@ -0,0 +1,134 @@
+// Pre-industrial CO2 (ppm), which the planet's base greenhouse factor is taken to hold, and
+// the strengthening of that factor for each doubling above it: about 3 °C of warming at
+// Earth's present emissivity.
+pub const REFERENCE_CO2_PPM: f32 = 280.0;
+const GREENHOUSE_PER_CO2_DOUBLING: f32 = 0.025;
+// Least CO2 the greenhouse term is evaluated at, so a drawn-down atmosphere stays finite.
+const MIN_CO2_PPM: f32 = 1.0;
+// Basalt degasses about 0.002 Gt of carbon per km³, at 2.13 Gt of carbon per ppm.
+pub const DEFAULT_OUTGASSING_PPM_PER_KM3: f32 = 1e-3;
+// Silicate weathering draws CO2 down in proportion to how much there is, with this e-folding
+// time at the reference temperature. Ridges and arcs outgas steadily at the rate that
+// balances it at the reference CO2, so an undisturbed planet holds there.
+pub const DEFAULT_WEATHERING_TIMESCALE_YEARS: f32 = 300_000.0;
+pub const DEFAULT_BACKGROUND_OUTGASSING_PPM_PER_YEAR: f32 = REFERENCE_CO2_PPM / DEFAULT_WEATHERING_TIMESCALE_YEARS;
+// Weathering speeds up e-fold for every 13.7 °C of warming (Walker, Hays and Kasting), and
+// runoff grows by this share per degree as a warmer climate rains more.
+const WEATHERING_REFERENCE_TEMPERATURE_C: f32 = 15.0;
+const WEATHERING_TEMPERATURE_E_FOLD_C: f32 = 13.7;
+const RUNOFF_PER_C: f32 = 0.04;
+
+// The carbon-silicate thermostat: volcanism adds CO2, weathering of silicate rock takes it
+// away faster the warmer and wetter the surface, and CO2 strengthens the greenhouse.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Atmosphere {
+    pub co2_ppm: f32,
+    pub outgassing_ppm_per_km3: f32,
+    pub background_outgassing_ppm_per_year: f32,
+    pub weathering_timescale_years: f32,
+}
+
+impl Atmosphere {
+    pub fn new() -> Self {
+        Self {
+            co2_ppm: REFERENCE_CO2_PPM,
+            outgassing_ppm_per_km3: DEFAULT_OUTGASSING_PPM_PER_KM3,
+            background_outgassing_ppm_per_year: DEFAULT_BACKGROUND_OUTGASSING_PPM_PER_YEAR,
+            weathering_timescale_years: DEFAULT_WEATHERING_TIMESCALE_YEARS,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    // CO2 released by magma reaching the surface. Returns the rise in ppm.
+    pub fn outgas(&mut self, magma_km3: f32) -> f32 {
+        let added_ppm = magma_km3.max(0.0) * self.outgassing_ppm_per_km3;
+        self.co2_ppm += added_ppm;
+        added_ppm
+    }
+
+    // Background outgassing against silicate weathering at the given mean surface
+    // temperature. The drawdown is integrated exactly over the step, so a long step relaxes
+    // CO2 toward the balance point instead of overshooting it. Returns the CO2 drawn down.
+    pub fn weather(&mut self, years: f32, surface_temperature_c: f32) -> f32 {
+        let rate_per_year = self.weathering_rate(surface_temperature_c) / self.weathering_timescale_years;
+        if rate_per_year <= 0.0 {
+            self.co2_ppm += self.background_outgassing_ppm_per_year * years;
+            return 0.0;
+        }
+        let balance_ppm = self.background_outgassing_ppm_per_year / rate_per_year;
+        let previous_ppm = self.co2_ppm;
+        self.co2_ppm = balance_ppm + (previous_ppm - balance_ppm) * (-rate_per_year * years).exp();
+        (previous_ppm + self.background_outgassing_ppm_per_year * years - self.co2_ppm).max(0.0)
+    }
+
+    // Weathering relative to the reference climate: Arrhenius-like kinetics times runoff.
+    pub fn weathering_rate(&self, surface_temperature_c: f32) -> f32 {
+        let warming_c = surface_temperature_c - WEATHERING_REFERENCE_TEMPERATURE_C;
+        let kinetics = (warming_c / WEATHERING_TEMPERATURE_E_FOLD_C).exp();
+        let runoff = (1.0 + RUNOFF_PER_C * warming_c).max(0.0);
+        kinetics * runoff
+    }
+
+    // The planet's base greenhouse factor, held at the reference CO2, strengthened by a fixed
+    // step for each doubling above it and weakened for each halving below.
+    pub fn greenhouse_factor(&self, base_greenhouse_factor: f32) -> f32 {
+        let doublings = (self.co2_ppm.max(MIN_CO2_PPM) / REFERENCE_CO2_PPM).log2();
+        (base_greenhouse_factor + GREENHOUSE_PER_CO2_DOUBLING * doublings).clamp(0.0, 1.0)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn describe(&self) {
+        println!("{}", self.summary());
+    }
+
+    pub fn summary(&self) -> String {
+        format!("Atmosphere:\n  CO2: {:.0} ppm", self.co2_ppm)
+    }
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::crust::{Crust, CrustType, DEFAULT_SOLAR_CONSTANT_W_PER_M2};
+    use crate::earth::limits::Limits;
+    use crate::earth::planet::EARTH_GREENHOUSE_FACTOR;
+
+    // Weathers against the crust's temperature and warms the crust by the resulting
+    // greenhouse, 100 kyr at a time.
+    fn run_climate(atmosphere: &mut Atmosphere, crust: &mut Crust, steps: usize) {
+        for _ in 0..steps {
+            atmosphere.weather(100_000.0, crust.surface_temperature_c);
+            let greenhouse_factor = atmosphere.greenhouse_factor(EARTH_GREENHOUSE_FACTOR);
+            crust.update_surface_temperature(100_000.0, DEFAULT_SOLAR_CONSTANT_W_PER_M2, greenhouse_factor, &Limits::default());
+        }
+    }
+
+    #[test]
+    fn weathering_draws_a_co2_spike_back_down() {
+        let mut atmosphere = Atmosphere::new();
+        let mut crust = Crust::new(CrustType::Continental);
+        run_climate(&mut atmosphere, &mut crust, 100);
+        let (settled_ppm, settled_c) = (atmosphere.co2_ppm, crust.surface_temperature_c);
+
+        atmosphere.outgas(3.0 * settled_ppm / DEFAULT_OUTGASSING_PPM_PER_KM3);
+        assert!((atmosphere.co2_ppm - 4.0 * settled_ppm).abs() < 1e-2 * settled_ppm);
+        run_climate(&mut atmosphere, &mut crust, 3);
+        let peak_c = crust.surface_temperature_c;
+        assert!(peak_c > settled_c + 1.0, "a quadrupling only warmed {} to {}", settled_c, peak_c);
+        assert!(atmosphere.co2_ppm < 4.0 * settled_ppm);
+
+        run_climate(&mut atmosphere, &mut crust, 100);
+        assert!((atmosphere.co2_ppm - settled_ppm).abs() < 0.01 * settled_ppm);
+        assert!((crust.surface_temperature_c - settled_c).abs() < 0.1);
+    }
+}
@@ -1,10 +1,144 @@
+use crate::earth::limits::Limits;
+use crate::earth::thermal::RadiogenicHeat;
+use crate::earth::units::{km_to_m, mm_per_year_to_km_per_year, myr_to_years, years_to_myr};
+use crate::earth::validation::{ValidationError, Validator};
+use log::{debug, info};
+use std::fmt;
+use std::str::FromStr;
+
+pub const DEFAULT_MANTLE_DENSITY: f32 = 3300.0;
+
+// Energy balance: present-day solar constant (W/m²), Stefan-Boltzmann constant, and the
+// albedo a fully ice-covered surface tends toward.
+pub const DEFAULT_SOLAR_CONSTANT_W_PER_M2: f32 = 1361.0;
+const STEFAN_BOLTZMANN: f32 = 5.67e-8;
+const ICE_ALBEDO: f32 = 0.6;
+// e-folding times for the surface to settle to equilibrium and for ice cover to spread or retreat.
+const SURFACE_RELAXATION_YEARS: f32 = 10.0;
+const ICE_ALBEDO_RESPONSE_YEARS: f32 = 100.0;
+// Keeps the effective emissivity positive when the greenhouse factor reaches 1.
+const MIN_EFFECTIVE_EMISSIVITY: f32 = 0.05;
+// Only eruptions from VEI 4 up loft enough ash and sulfate into the stratosphere to matter.
+// The albedo boost doubles with each VEI above that, and the surface cools at once by
+// roughly the equilibrium sensitivity to albedo.
+const STRATOSPHERIC_VEI: u8 = 4;
+const ASH_ALBEDO_AT_STRATOSPHERIC_VEI: f32 = 0.0025;
+const ASH_COOLING_C_PER_ALBEDO: f32 = 100.0;
+// Footprint over which volcanic supply spreads, which is also the area each new volcanic
+// province covers, and the size of the single province a crust starts out as.
+const VOLCANIC_FOOTPRINT_KM2: f32 = 1_000_000.0;
+const INITIAL_PROVINCE_AREA_KM2: f32 = 10_000_000.0;
+// New volcanic crust joins the youngest province while that is still younger than this.
+const PROVINCE_MERGE_AGE_MYR: f32 = 1.0;
+// Groundwater lost per Myr by continental interiors cut off from oceanic moisture.
+const SUPERCONTINENT_GROUNDWATER_LOSS_PCT_PER_MYR: f32 = 0.5;
+// Climate scaling of the baseline erosion rate: full plant cover holds back this share of
+// it, freeze-thaw adds up to FREEZE_THAW_BOOST around 0 °C (falling off over the given
+// width), and warmer, wetter climates add RAINFALL_BOOST_PER_C for each degree above freezing.
+const VEGETATION_PROTECTION: f32 = 0.8;
+const FREEZE_THAW_BOOST: f32 = 1.0;
+const FREEZE_THAW_WIDTH_C: f32 = 10.0;
+const RAINFALL_BOOST_PER_C: f32 = 0.02;
+// Present-day radiogenic heat production (W/kg): granite is rich in U, Th and K, basalt poor.
+const CONTINENTAL_RADIOGENIC_HEAT_W_PER_KG: f64 = 3.7e-10;
+const OCEANIC_RADIOGENIC_HEAT_W_PER_KG: f64 = 3.5e-11;
+// Surface heat flow (W/m²) is the heat conducted up from the mantle plus what the crust makes
+// itself. Old continental crust has lost much of its heat-producing upper layer to erosion
+// and to melts that carried U, Th and K away, so its radiogenic share falls off with age on
+// this e-folding time: Archean cratons run near 0.04 W/m² against 0.06 for young crust.
+const CONTINENTAL_BASAL_HEAT_FLUX_W_PER_M2: f32 = 0.03;
+const OCEANIC_BASAL_HEAT_FLUX_W_PER_M2: f32 = 0.08;
+const RADIOGENIC_DEPLETION_MYR: f32 = 2_500.0;
+// Post-glacial rebound relaxes with an e-folding time proportional to the viscosity of the
+// mantle beneath: about 4,000 years over a 1e19 Pa·s asthenosphere, as under Scandinavia.
+const REBOUND_RELAXATION_YEARS: f32 = 4_000.0;
+const REBOUND_REFERENCE_VISCOSITY: f32 = 1e19;
+// Granitic continental and basaltic oceanic crust densities (kg/m³).
+const CONTINENTAL_CRUST_DENSITY: f32 = 2700.0;
+const OCEANIC_CRUST_DENSITY: f32 = 2900.0;
+// Continental crust stretched thinner than the hyperextension thickness is increasingly
+// intruded by mantle melt, growing denser until at the breakup thickness it gives way to
+// new ocean floor, as along the Iberia margin.
+const HYPEREXTENSION_THICKNESS_KM: f32 = 20.0;
+const BREAKUP_THICKNESS_KM: f32 = 10.0;
+// Athy's law: sediment porosity falls off exponentially with burial depth from its value at
+// deposition, over the compaction length. A pile's mean porosity relaxes toward that profile
+// over the compaction time, and whatever lies deeper than the lithification depth has
+// turned to rock and joins the crust.
+const SURFACE_SEDIMENT_POROSITY: f32 = 0.6;
+const COMPACTION_LENGTH_KM: f32 = 2.0;
+const COMPACTION_TIMESCALE_MYR: f32 = 10.0;
+const LITHIFICATION_DEPTH_KM: f32 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CrustType {
+    Continental,
+    Oceanic,
+}
+
+impl CrustType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrustType::Continental => "continental",
+            CrustType::Oceanic => "oceanic",
+        }
+    }
+}
+
+impl fmt::Display for CrustType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CrustType {
+    type Err = CrustError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "continental" => Ok(CrustType::Continental),
+            "oceanic" => Ok(CrustType::Oceanic),
+            _ => Err(CrustError { input: s.to_string() }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrustError {
+    pub input: String,
+}
+
+impl fmt::Display for CrustError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid crust type '{}', expected 'continental' or 'oceanic'", self.input)
+    }
+}
+
+impl std::error::Error for CrustError {}
+
+// A patch of crust formed at one time, such as an ancient craton or a young volcanic arc.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrustProvince {
+    pub age_myr: f32,
+    pub area_km2: f32,
+    pub composition: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Crust {
     pub thickness_km: f32,
-    pub type_name: String,
-    pub age_myr: f32,
+    pub kind: CrustType,
+    pub provinces: Vec<CrustProvince>,
     pub composition: String,
     pub average_density: f32,
     pub heat_flux_out: f32,
+    // Share of heat_flux_out made by decay in the crust itself, in W/m².
+    pub radiogenic_heat_flux: f32,
+    pub radiogenic_heat_w_per_kg: RadiogenicHeat,
     pub tectonic_activity_factor: f32,
     pub volcanic_activity_factor: f32,
     pub erosion_rate_mm_per_yr: f32,
@@ -13,135 +147,695 @@ pub struct Crust {
     // New fields for future simulation depth
     pub mineral_distribution: String,
     pub sediment_thickness_km: f32,
+    // Mean pore fraction of the sediment pile, from 0 to 1.
+    pub sediment_porosity: f32,
+    // Material eroded off this crust and not yet carried anywhere, as thickness over it.
+    pub sediment_load_km: f32,
     pub surface_temperature_c: f32,
-    pub surface_albedo: f32,
-    pub groundwater_content_pct: f32,
+    pub surface: CrustSurface,
     pub isostasy_adjustment_km: f32,
+    // Where the isostasy adjustment is heading; the column creeps toward it at a pace set by
+    // the viscosity of the mantle underneath.
+    pub target_isostasy_km: f32,
+    pub underlying_viscosity: f32,
+}
+
+// What covers the crust: how reflective it is, how much water and plant cover it holds.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrustSurface {
+    pub albedo: f32,
+    // Albedo of the bare surface, which the current albedo relaxes back to when ice-free.
+    pub base_albedo: f32,
+    pub groundwater_content_pct: f32,
     pub vegetation_coverage_pct: f32,
-    pub surface_roughness_factor: f32,
+    pub roughness_factor: f32,
 }
 
 impl Crust {
-    pub fn new(type_name: &str) -> Self {
-        match type_name {
-            "continental" => Self {
+    pub fn new(kind: CrustType) -> Self {
+        let mut crust = match kind {
+            CrustType::Continental => Self {
                 thickness_km: 35.0,
-                type_name: "continental".to_string(),
-                age_myr: 1000.0,
+                kind,
+                provinces: vec![CrustProvince {
+                    age_myr: 1000.0,
+                    area_km2: INITIAL_PROVINCE_AREA_KM2,
+                    composition: "granite-dominated".to_string(),
+                }],
                 composition: "granite-dominated".to_string(),
-                average_density: 2700.0,
-                heat_flux_out: 0.06,
+                average_density: CONTINENTAL_CRUST_DENSITY,
+                heat_flux_out: CONTINENTAL_BASAL_HEAT_FLUX_W_PER_M2,
+                radiogenic_heat_flux: 0.0,
+                radiogenic_heat_w_per_kg: RadiogenicHeat::primordial(CONTINENTAL_RADIOGENIC_HEAT_W_PER_KG),
                 tectonic_activity_factor: 0.5,
                 volcanic_activity_factor: 0.2,
                 erosion_rate_mm_per_yr: 0.1,
                 is_active_margin: false,
                 mineral_distribution: "silicates, minor iron".to_string(),
                 sediment_thickness_km: 2.0,
+                sediment_porosity: compacted_porosity(2.0),
+                sediment_load_km: 0.0,
                 surface_temperature_c: 15.0,
-                surface_albedo: 0.3,
-                groundwater_content_pct: 5.0,
+                surface: CrustSurface {
+                    albedo: 0.3,
+                    base_albedo: 0.3,
+                    groundwater_content_pct: 5.0,
+                    vegetation_coverage_pct: 50.0,
+                    roughness_factor: 0.5,
+                },
                 isostasy_adjustment_km: 0.0,
-                vegetation_coverage_pct: 50.0,
-                surface_roughness_factor: 0.5,
+                target_isostasy_km: 0.0,
+                underlying_viscosity: REBOUND_REFERENCE_VISCOSITY,
             },
-            "oceanic" => Self {
+            CrustType::Oceanic => Self {
                 thickness_km: 7.0,
-                type_name: "oceanic".to_string(),
-                age_myr: 50.0,
+                kind,
+                provinces: vec![CrustProvince {
+                    age_myr: 50.0,
+                    area_km2: INITIAL_PROVINCE_AREA_KM2,
+                    composition: "basalt-dominated".to_string(),
+                }],
                 composition: "basalt-dominated".to_string(),
-                average_density: 2900.0,
-                heat_flux_out: 0.08,
+                average_density: OCEANIC_CRUST_DENSITY,
+                heat_flux_out: OCEANIC_BASAL_HEAT_FLUX_W_PER_M2,
+                radiogenic_heat_flux: 0.0,
+                radiogenic_heat_w_per_kg: RadiogenicHeat::primordial(OCEANIC_RADIOGENIC_HEAT_W_PER_KG),
                 tectonic_activity_factor: 0.7,
                 volcanic_activity_factor: 0.6,
                 erosion_rate_mm_per_yr: 0.5,
                 is_active_margin: true,
                 mineral_distribution: "basalts, sulfides".to_string(),
                 sediment_thickness_km: 0.5,
+                sediment_porosity: compacted_porosity(0.5),
+                sediment_load_km: 0.0,
                 surface_temperature_c: 4.0,
-                surface_albedo: 0.1,
-                groundwater_content_pct: 1.0,
+                surface: CrustSurface {
+                    albedo: 0.1,
+                    base_albedo: 0.1,
+                    groundwater_content_pct: 1.0,
+                    vegetation_coverage_pct: 0.0,
+                    roughness_factor: 0.8,
+                },
                 isostasy_adjustment_km: 0.0,
-                vegetation_coverage_pct: 0.0,
-                surface_roughness_factor: 0.8,
+                target_isostasy_km: 0.0,
+                underlying_viscosity: REBOUND_REFERENCE_VISCOSITY,
             },
-            _ => panic!("Invalid crust type! Use 'continental' or 'oceanic'."),
+        };
+        crust.update_heat_flux();
+        crust
+    }
+
+    // Heat the crust's own isotopes make through its whole thickness, less what old crust has
+    // lost, on top of the flux from the mantle beneath.
+    pub fn update_heat_flux(&mut self) {
+        let kg_per_m2 = self.average_density as f64 * km_to_m(self.thickness_km as f64);
+        let retained = (-self.age_myr() / RADIOGENIC_DEPLETION_MYR).exp();
+        self.radiogenic_heat_flux = (self.radiogenic_heat_w_per_kg.total() * kg_per_m2) as f32 * retained;
+        let basal = match self.kind {
+            CrustType::Continental => CONTINENTAL_BASAL_HEAT_FLUX_W_PER_M2,
+            CrustType::Oceanic => OCEANIC_BASAL_HEAT_FLUX_W_PER_M2,
+        };
+        self.heat_flux_out = basal + self.radiogenic_heat_flux;
+    }
+
+    // Fluent setters for the surface, clamping each value into its valid range. The albedo
+    // also becomes the bare-surface albedo the climate relaxes back to.
+    pub fn with_albedo(mut self, albedo: f32) -> Self {
+        self.surface.albedo = albedo.clamp(0.0, 1.0);
+        self.surface.base_albedo = self.surface.albedo;
+        self
+    }
+
+    pub fn with_vegetation(mut self, coverage_pct: f32) -> Self {
+        self.surface.vegetation_coverage_pct = coverage_pct.clamp(0.0, 100.0);
+        self
+    }
+
+    pub fn with_groundwater(mut self, content_pct: f32) -> Self {
+        self.surface.groundwater_content_pct = content_pct.clamp(0.0, 100.0);
+        self
+    }
+
+    // Area-weighted mean age of the provinces.
+    pub fn age_myr(&self) -> f32 {
+        let area_km2: f32 = self.provinces.iter().map(|province| province.area_km2).sum();
+        if area_km2 <= 0.0 {
+            return 0.0;
+        }
+        self.provinces.iter().map(|province| province.age_myr * province.area_km2).sum::<f32>() / area_km2
+    }
+
+    pub fn age_provinces(&mut self, years: f32) {
+        for province in &mut self.provinces {
+            province.age_myr += years_to_myr(years);
         }
     }
 
+    // Fresh lava forms a new zero-age province, or tops up the youngest one if that is
+    // itself barely formed, leaving older provinces as they were.
     pub fn grow_by_volcanism(&mut self, magma_supply_km3: f32) -> f32 {
-        let added_thickness = magma_supply_km3 / 1_000_000.0;
+        let added_thickness = magma_supply_km3 / VOLCANIC_FOOTPRINT_KM2;
         self.thickness_km += added_thickness;
-        self.age_myr = 0.0;
+
+        let youngest = self.provinces.iter_mut().min_by(|a, b| a.age_myr.total_cmp(&b.age_myr));
+        match youngest {
+            Some(province) if province.age_myr < PROVINCE_MERGE_AGE_MYR => {
+                let area_km2 = province.area_km2 + VOLCANIC_FOOTPRINT_KM2;
+                province.age_myr *= province.area_km2 / area_km2;
+                province.area_km2 = area_km2;
+            }
+            _ => self.provinces.push(CrustProvince {
+                age_myr: 0.0,
+                area_km2: VOLCANIC_FOOTPRINT_KM2,
+                composition: "fresh volcanic basalt".to_string(),
+            }),
+        }
         added_thickness
     }
 
-    pub fn erode(&mut self, years: f32) -> f32 {
-        let erosion_km = (self.erosion_rate_mm_per_yr * years) / 1_000_000.0;
+    // The baseline rate scaled by climate: roots hold soil in place, while frost cycles near
+    // freezing and heavier rain in warm climates break it down faster.
+    pub fn effective_erosion_rate_mm_per_yr(&self) -> f32 {
+        let vegetation = 1.0 - VEGETATION_PROTECTION * (self.surface.vegetation_coverage_pct / 100.0).clamp(0.0, 1.0);
+        let freeze_thaw = FREEZE_THAW_BOOST * (-(self.surface_temperature_c / FREEZE_THAW_WIDTH_C).powi(2)).exp();
+        let rainfall = RAINFALL_BOOST_PER_C * self.surface_temperature_c.max(0.0);
+        self.erosion_rate_mm_per_yr * vegetation * (1.0 + freeze_thaw + rainfall)
+    }
+
+    // Strips material down to the thickness floor and holds it as sediment load until it is
+    // routed somewhere. Returns the thickness actually removed.
+    pub fn erode(&mut self, years: f32, limits: &Limits) -> f32 {
+        let erodible_km = (self.thickness_km - limits.min_crust_thickness_km).max(0.0);
+        let erosion_km = (mm_per_year_to_km_per_year(self.effective_erosion_rate_mm_per_yr()) * years).min(erodible_km);
         self.thickness_km -= erosion_km;
-        if self.thickness_km < 5.0 {
-            self.thickness_km = 5.0;
-        }
+        self.sediment_load_km += erosion_km;
         erosion_km
     }
 
-    pub fn update_tectonics(&mut self, plate_motion_cm_per_year: f32) -> f32 {
+    // Carries a fraction of the sediment load onto the target, which deposits it over an
+    // equal footprint, so the thickness leaving is the thickness arriving.
+    pub fn route_sediment_to(&mut self, target: &mut Crust, fraction: f32) -> f32 {
+        let routed_km = self.sediment_load_km * fraction.clamp(0.0, 1.0);
+        self.sediment_load_km -= routed_km;
+        target.deposit_sediment(routed_km);
+        target.simulate_erosion_feedback();
+        routed_km
+    }
+
+    // Dries the ground out, as deep in a supercontinent far from any coast.
+    pub fn aridify(&mut self, years: f32) {
+        let loss_pct = SUPERCONTINENT_GROUNDWATER_LOSS_PCT_PER_MYR * years_to_myr(years);
+        self.surface.groundwater_content_pct = (self.surface.groundwater_content_pct - loss_pct).max(0.0);
+    }
+
+    // Height the crust floats to above its isostatic root.
+    pub fn elevation_km(&self, mantle_density: f32) -> f32 {
+        self.thickness_km - self.root_depth_km(mantle_density)
+    }
+
+    pub fn update_tectonics(&mut self, plate_motion_cm_per_year: f32, limits: &Limits) -> f32 {
         let deformation = plate_motion_cm_per_year / 100.0 * self.tectonic_activity_factor;
         self.thickness_km += deformation;
-        if self.thickness_km > 70.0 {
-            self.thickness_km = 70.0;
+        if self.thickness_km > limits.max_crust_thickness_km {
+            self.thickness_km = limits.max_crust_thickness_km;
         }
         deformation
     }
 
+    // Recycles the oldest province into fresh melt; the rest of the mosaic is untouched.
     pub fn rejuvenate_subduction(&mut self) {
-        self.age_myr = 0.0;
+        if let Some(oldest) = self.provinces.iter_mut().max_by(|a, b| a.age_myr.total_cmp(&b.age_myr)) {
+            oldest.age_myr = 0.0;
+            oldest.composition = "rejuvenated basaltic melt".to_string();
+        }
         self.thickness_km = 7.0;
         self.composition = "rejuvenated basaltic melt".to_string();
-        println!("♻️ Crust rejuvenated via subduction or melting reset.");
+        info!("♻️ Crust rejuvenated via subduction or melting reset.");
     }
 
-    pub fn adjust_isostasy(&mut self, load_change_km: f32) {
-        self.isostasy_adjustment_km += load_change_km * 0.1;
-        println!("⚖️ Isostasy adjusted by {:.3} km due to load change.", load_change_km * 0.1);
+    // Spreads the same crust over more area, thinning it by the ratio of old area to new.
+    pub fn stretch(&mut self, area_ratio: f32) {
+        self.thickness_km *= area_ratio;
     }
 
-    pub fn update_surface_temperature(&mut self, external_forcing: f32) {
-        self.surface_temperature_c += external_forcing;
-        println!("🌡️ Surface temperature adjusted by {:.2}°C.", external_forcing);
+    // Densifies hyperextended continental crust as it thins, and turns it into oceanic crust
+    // once it passes the breakup thickness. Returns whether it broke up just now.
+    pub fn update_rift_transition(&mut self) -> bool {
+        if self.kind != CrustType::Continental || self.thickness_km >= HYPEREXTENSION_THICKNESS_KM {
+            return false;
+        }
+        if self.thickness_km > BREAKUP_THICKNESS_KM {
+            let progress = (HYPEREXTENSION_THICKNESS_KM - self.thickness_km) / (HYPEREXTENSION_THICKNESS_KM - BREAKUP_THICKNESS_KM);
+            let density = CONTINENTAL_CRUST_DENSITY + (OCEANIC_CRUST_DENSITY - CONTINENTAL_CRUST_DENSITY) * progress;
+            self.average_density = self.average_density.max(density);
+            return false;
+        }
+
+        let ocean_floor = Crust::new(CrustType::Oceanic);
+        self.kind = CrustType::Oceanic;
+        self.composition = ocean_floor.composition;
+        self.mineral_distribution = ocean_floor.mineral_distribution;
+        self.average_density = ocean_floor.average_density;
+        self.radiogenic_heat_w_per_kg = ocean_floor.radiogenic_heat_w_per_kg;
+        self.provinces.push(CrustProvince {
+            age_myr: 0.0,
+            area_km2: VOLCANIC_FOOTPRINT_KM2,
+            composition: "new ocean floor".to_string(),
+        });
+        self.update_heat_flux();
+        info!("🌊 Hyperextended continental crust broke up into new ocean floor.");
+        true
+    }
+
+    // Airy compensation: a load of crustal density sinks until the mantle it displaces
+    // balances it, so the column deflects by load * rho_crust / rho_mantle. Negative loads
+    // (melting ice, eroded sediment) rebound by the same ratio. The load changes at once but
+    // the mantle has to flow out of the way, so only the target moves here; relax_isostasy
+    // carries the column there over time. Returns the root depth the column is heading for.
+    pub fn adjust_isostasy(&mut self, load_km: f32, mantle_density: f32) -> f32 {
+        let deflection_km = load_km * self.average_density / mantle_density;
+        self.target_isostasy_km += deflection_km;
+        debug!("⚖️ Isostatic target shifted by {:.3} km due to load change.", deflection_km);
+        self.thickness_km * self.average_density / mantle_density + self.target_isostasy_km
+    }
+
+    // Viscous relaxation toward the isostatic target: the gap closes exponentially, fast at
+    // first and ever slower, like the uplift still lifting Scandinavia.
+    pub fn relax_isostasy(&mut self, years: f32) {
+        let relaxation_years = REBOUND_RELAXATION_YEARS * self.underlying_viscosity / REBOUND_REFERENCE_VISCOSITY;
+        let gap_km = self.target_isostasy_km - self.isostasy_adjustment_km;
+        self.isostasy_adjustment_km += gap_km * relaxation(years, relaxation_years);
     }
 
+    pub fn root_depth_km(&self, mantle_density: f32) -> f32 {
+        self.thickness_km * self.average_density / mantle_density + self.isostasy_adjustment_km
+    }
+
+    // Zero-dimensional energy balance: absorbed sunlight S/4 * (1 - albedo) is radiated at
+    // an effective emissivity of 1 - greenhouse_factor, so a stronger greenhouse warms the
+    // surface. Ice cover responds to the current temperature first, pushing the albedo toward
+    // ICE_ALBEDO below freezing, and the temperature then relaxes toward the equilibrium for
+    // that albedo, so a cold enough start can run away into a snowball.
+    pub fn update_surface_temperature(&mut self, years: f32, solar_constant_w_per_m2: f32, greenhouse_factor: f32, limits: &Limits) {
+        let target_albedo = if self.surface_temperature_c < 0.0 { ICE_ALBEDO } else { self.surface.base_albedo };
+        let albedo = self.surface.albedo + (target_albedo - self.surface.albedo) * relaxation(years, ICE_ALBEDO_RESPONSE_YEARS);
+        self.surface.albedo = limits.clamp_albedo(albedo);
+
+        let previous_c = self.surface_temperature_c;
+        let equilibrium_c = self.equilibrium_temperature_c(solar_constant_w_per_m2, greenhouse_factor);
+        self.surface_temperature_c += (equilibrium_c - self.surface_temperature_c) * relaxation(years, SURFACE_RELAXATION_YEARS);
+
+        debug!(
+            "🌡️ Surface temperature adjusted by {:.2}°C toward {:.1}°C, albedo {:.2}.",
+            self.surface_temperature_c - previous_c,
+            equilibrium_c,
+            self.surface.albedo
+        );
+    }
+
+    pub fn equilibrium_temperature_c(&self, solar_constant_w_per_m2: f32, greenhouse_factor: f32) -> f32 {
+        let absorbed_w_per_m2 = solar_constant_w_per_m2 / 4.0 * (1.0 - self.surface.albedo);
+        let emissivity = (1.0 - greenhouse_factor).max(MIN_EFFECTIVE_EMISSIVITY);
+        (absorbed_w_per_m2 / (emissivity * STEFAN_BOLTZMANN)).powf(0.25) - 273.15
+    }
+
+    // Volcanic winter: ash and aerosols brighten the surface and cool it straight away. Both
+    // wear off through `update_surface_temperature`, which relaxes them back. Returns the
+    // immediate cooling.
+    pub fn inject_ash(&mut self, vei: u8) -> f32 {
+        if vei < STRATOSPHERIC_VEI {
+            return 0.0;
+        }
+        let albedo_increase = ASH_ALBEDO_AT_STRATOSPHERIC_VEI * 2f32.powi((vei - STRATOSPHERIC_VEI) as i32);
+        self.surface.albedo = (self.surface.albedo + albedo_increase).min(1.0);
+        let cooling_c = albedo_increase * ASH_COOLING_C_PER_ALBEDO;
+        self.surface_temperature_c -= cooling_c;
+        info!("🌋 VEI {} eruption cooled the surface by {:.2}°C.", vei, cooling_c);
+        cooling_c
+    }
+
+    // Fresh sediment arrives at surface porosity and mixes into the pile's mean by thickness.
     pub fn deposit_sediment(&mut self, thickness_km: f32) {
-        self.sediment_thickness_km += thickness_km;
-        println!("🏔️ Sediment deposition: added {:.3} km.", thickness_km);
+        let total_km = self.sediment_thickness_km + thickness_km;
+        if total_km > 0.0 {
+            self.sediment_porosity = (self.sediment_porosity * self.sediment_thickness_km + SURFACE_SEDIMENT_POROSITY * thickness_km) / total_km;
+        }
+        self.sediment_thickness_km = total_km;
+        debug!("🏔️ Sediment deposition: added {:.3} km.", thickness_km);
+    }
+
+    // Squeezes pore water out of the pile toward the porosity its burial calls for, keeping
+    // the grains, so the pile thins as it compacts. Sediment buried past the lithification
+    // depth is added to the crust as rock. Returns the thickness lithified.
+    pub fn compact_sediment(&mut self, years: f32) -> f32 {
+        if self.sediment_thickness_km <= 0.0 {
+            return 0.0;
+        }
+        let target = compacted_porosity(self.sediment_thickness_km);
+        let porosity = self.sediment_porosity + (target - self.sediment_porosity) * relaxation(years, myr_to_years(COMPACTION_TIMESCALE_MYR));
+        self.sediment_thickness_km *= (1.0 - self.sediment_porosity) / (1.0 - porosity);
+        self.sediment_porosity = porosity;
+
+        let lithified_km = (self.sediment_thickness_km - LITHIFICATION_DEPTH_KM).max(0.0);
+        if lithified_km > 0.0 {
+            self.sediment_thickness_km -= lithified_km;
+            self.thickness_km += lithified_km;
+            debug!("🪨 Sediment lithified: {:.3} km of buried pile turned to rock.", lithified_km);
+        }
+        lithified_km
     }
 
     pub fn simulate_erosion_feedback(&mut self) {
         if self.sediment_thickness_km > 5.0 {
             self.erosion_rate_mm_per_yr *= 1.2;
-            println!("⚠️ Erosion rate increased due to high sediment cover.");
+            info!("⚠️ Erosion rate increased due to high sediment cover.");
         }
     }
 
     pub fn update_vegetation(&mut self, change_pct: f32) {
-        self.vegetation_coverage_pct += change_pct;
-        if self.vegetation_coverage_pct > 100.0 {
-            self.vegetation_coverage_pct = 100.0;
+        self.surface.vegetation_coverage_pct = (self.surface.vegetation_coverage_pct + change_pct).clamp(0.0, 100.0);
+        debug!("🌱 Vegetation coverage adjusted by {:.1}%.", change_pct);
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        Validator::new()
+            .positive("thickness_km", self.thickness_km)
+            .positive("average_density", self.average_density)
+            .above_absolute_zero("surface_temperature_c", self.surface_temperature_c)
+            .non_negative("radiogenic_heat_flux", self.radiogenic_heat_flux)
+            .non_negative("sediment_thickness_km", self.sediment_thickness_km)
+            .within("sediment_porosity", self.sediment_porosity, 0.0, 1.0)
+            .non_negative("sediment_load_km", self.sediment_load_km)
+            .positive("underlying_viscosity", self.underlying_viscosity)
+            .within("surface.albedo", self.surface.albedo, 0.0, 1.0)
+            .within("surface.groundwater_content_pct", self.surface.groundwater_content_pct, 0.0, 100.0)
+            .within("surface.vegetation_coverage_pct", self.surface.vegetation_coverage_pct, 0.0, 100.0)
+            .finish()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn describe(&self) {
+        println!("{}", self.summary());
+    }
+
+    pub fn summary(&self) -> String {
+        let mut lines = vec!["Crust description:".to_string()];
+        lines.push(format!("  Type: {}, Age: {:.1} Myr over {} province(s)", self.kind, self.age_myr(), self.provinces.len()));
+        lines.push(format!("  Thickness: {:.2} km, Composition: {}", self.thickness_km, self.composition));
+        lines.push(format!("  Density: {:.0} kg/m³, Heat flux: {:.3} W/m² ({:.3} radiogenic)", self.average_density, self.heat_flux_out, self.radiogenic_heat_flux));
+        lines.push(format!("  Tectonic factor: {:.2}, Volcanic factor: {:.2}, Erosion rate: {:.2} mm/yr ({:.2} baseline)", self.tectonic_activity_factor, self.volcanic_activity_factor, self.effective_erosion_rate_mm_per_yr(), self.erosion_rate_mm_per_yr));
+        lines.push(format!(
+            "  Mineral dist: {}, Sediment: {:.2} km at {:.0}% porosity, Awaiting transport: {:.3} km",
+            self.mineral_distribution,
+            self.sediment_thickness_km,
+            self.sediment_porosity * 100.0,
+            self.sediment_load_km
+        ));
+        lines.push(format!("  Surface temp: {:.1}°C, Albedo: {:.2}, Groundwater: {:.1}%", self.surface_temperature_c, self.surface.albedo, self.surface.groundwater_content_pct));
+        lines.push(format!("  Isostasy adj: {:.3} km (target {:.3} km), Vegetation: {:.1}%, Roughness: {:.2}", self.isostasy_adjustment_km, self.target_isostasy_km, self.surface.vegetation_coverage_pct, self.surface.roughness_factor));
+        lines.push(format!("  Active margin: {}", self.is_active_margin));
+        lines.join("\n")
+    }
+}
+
+impl FromStr for Crust {
+    type Err = CrustError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Crust::new)
+    }
+}
+
+// Fraction of the remaining gap closed over `years` by a process with the given e-folding time.
+fn relaxation(years: f32, e_folding_years: f32) -> f32 {
+    1.0 - (-years / e_folding_years).exp()
+}
+
+// Mean porosity of a pile of this thickness once fully compacted: Athy's profile averaged
+// from the surface down to its base.
+fn compacted_porosity(thickness_km: f32) -> f32 {
+    if thickness_km <= 0.0 {
+        return SURFACE_SEDIMENT_POROSITY;
+    }
+    let depth = thickness_km / COMPACTION_LENGTH_KM;
+    SURFACE_SEDIMENT_POROSITY * (1.0 - (-depth).exp()) / depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!((actual - expected).abs() <= tolerance, "{} is not within {} of {}", actual, tolerance, expected);
+    }
+
+    #[test]
+    fn an_ice_load_settles_to_the_airy_equilibrium() {
+        // 35 km of 2700 kg/m³ granite under 2 km of load over a 3300 kg/m³ mantle: the load
+        // sinks by 2 * 2700 / 3300 = 1.636 km, below a root of 35 * 2700 / 3300 = 28.636 km.
+        let mut crust = Crust::new(CrustType::Continental);
+        crust.adjust_isostasy(2.0, DEFAULT_MANTLE_DENSITY);
+        assert_close(crust.target_isostasy_km, 1.636_364, 1e-5);
+
+        crust.relax_isostasy(1e6);
+        assert_close(crust.root_depth_km(DEFAULT_MANTLE_DENSITY), 28.636_364 + 1.636_364, 1e-4);
+
+        // Melting the ice off rebounds the column by the same ratio, back to the bare root.
+        crust.adjust_isostasy(-2.0, DEFAULT_MANTLE_DENSITY);
+        crust.relax_isostasy(1e6);
+        assert_close(crust.root_depth_km(DEFAULT_MANTLE_DENSITY), 28.636_364, 1e-4);
+    }
+
+    // Starts a temperate continent at -10 °C and lets ice and temperature play out.
+    fn after_cold_snap(greenhouse_factor: f32) -> Crust {
+        let mut crust = Crust::new(CrustType::Continental);
+        crust.surface_temperature_c = -10.0;
+        for _ in 0..1_000 {
+            crust.update_surface_temperature(10.0, DEFAULT_SOLAR_CONSTANT_W_PER_M2, greenhouse_factor, &Limits::default());
         }
-        if self.vegetation_coverage_pct < 0.0 {
-            self.vegetation_coverage_pct = 0.0;
+        crust
+    }
+
+    #[test]
+    fn a_cold_snap_runs_away_into_a_snowball_under_a_weak_greenhouse() {
+        let crust = after_cold_snap(0.2);
+        assert!(crust.surface_temperature_c < -30.0, "{} °C", crust.surface_temperature_c);
+        assert_close(crust.surface.albedo, ICE_ALBEDO, 0.01);
+    }
+
+    #[test]
+    fn a_strong_greenhouse_recovers_from_a_cold_snap() {
+        let crust = after_cold_snap(0.6);
+        assert!(crust.surface_temperature_c > 0.0, "{} °C", crust.surface_temperature_c);
+        assert_close(crust.surface.albedo, crust.surface.base_albedo, 0.01);
+    }
+
+    #[test]
+    fn sediment_eroded_off_a_highland_all_lands_in_the_basin() {
+        let limits = Limits::default();
+        let mut highland = Crust::new(CrustType::Continental);
+        let mut basin = Crust::new(CrustType::Oceanic);
+        let (highland_before, basin_sediment_before) = (highland.thickness_km, basin.sediment_thickness_km);
+
+        let mut eroded_km = 0.0;
+        for _ in 0..10 {
+            eroded_km += highland.erode(1_000_000.0, &limits);
+            highland.route_sediment_to(&mut basin, 0.5);
         }
-        println!("🌱 Vegetation coverage adjusted by {:.1}%.", change_pct);
+        highland.route_sediment_to(&mut basin, 1.0);
+
+        assert!(eroded_km > 0.0);
+        assert_close(highland_before - highland.thickness_km, eroded_km, 1e-4);
+        assert_eq!(highland.sediment_load_km, 0.0);
+        assert_close(basin.sediment_thickness_km - basin_sediment_before, eroded_km, 1e-4);
     }
 
-    pub fn describe(&self) {
-        println!("Crust description:");
-        println!("  Type: {}, Age: {:.1} Myr", self.type_name, self.age_myr);
-        println!("  Thickness: {:.2} km, Composition: {}", self.thickness_km, self.composition);
-        println!("  Density: {:.0} kg/m³, Heat flux: {:.3} MW/m²", self.average_density, self.heat_flux_out);
-        println!("  Tectonic factor: {:.2}, Volcanic factor: {:.2}, Erosion rate: {:.2} mm/yr", self.tectonic_activity_factor, self.volcanic_activity_factor, self.erosion_rate_mm_per_yr);
-        println!("  Mineral dist: {}, Sediment: {:.2} km", self.mineral_distribution, self.sediment_thickness_km);
-        println!("  Surface temp: {:.1}°C, Albedo: {:.2}, Groundwater: {:.1}%", self.surface_temperature_c, self.surface_albedo, self.groundwater_content_pct);
-        println!("  Isostasy adj: {:.3} km, Vegetation: {:.1}%, Roughness: {:.2}", self.isostasy_adjustment_km, self.vegetation_coverage_pct, self.surface_roughness_factor);
-        println!("  Active margin: {}", self.is_active_margin);
+    #[test]
+    fn volcanism_adds_a_young_province_beside_the_old_craton() {
+        let mut crust = Crust::new(CrustType::Continental);
+        crust.age_provinces(2.5e9);
+        let craton = crust.provinces.clone();
+        assert!(craton.iter().all(|province| province.age_myr >= 2_500.0));
+
+        crust.grow_by_volcanism(1_000_000.0);
+        assert_eq!(crust.provinces.len(), craton.len() + 1);
+        assert_eq!(crust.provinces[..craton.len()], craton[..]);
+        assert_eq!(crust.provinces.last().map(|province| province.age_myr), Some(0.0));
+        assert!(crust.age_myr() < craton[0].age_myr);
+
+        // Subduction resets the oldest province, which here is the craton.
+        crust.rejuvenate_subduction();
+        assert_eq!(crust.provinces[0].age_myr, 0.0);
+    }
+
+    fn crust_with_climate(vegetation_pct: f32, temperature_c: f32) -> Crust {
+        let mut crust = Crust::new(CrustType::Continental);
+        crust.surface.vegetation_coverage_pct = vegetation_pct;
+        crust.surface_temperature_c = temperature_c;
+        crust
+    }
+
+    #[test]
+    fn a_vegetated_warm_crust_erodes_slower_than_a_barren_cold_one() {
+        let mut vegetated = crust_with_climate(100.0, 25.0);
+        let mut barren = crust_with_climate(0.0, 0.0);
+        assert_eq!(vegetated.erosion_rate_mm_per_yr, barren.erosion_rate_mm_per_yr);
+        assert!(vegetated.effective_erosion_rate_mm_per_yr() < barren.effective_erosion_rate_mm_per_yr());
+
+        let limits = Limits::default();
+        assert!(vegetated.erode(1_000_000.0, &limits) < barren.erode(1_000_000.0, &limits));
+    }
+
+    #[test]
+    fn full_vegetation_cover_holds_back_most_erosion() {
+        let bare = crust_with_climate(0.0, 25.0);
+        let covered = crust_with_climate(100.0, 25.0);
+        assert_close(covered.effective_erosion_rate_mm_per_yr(), bare.effective_erosion_rate_mm_per_yr() * (1.0 - VEGETATION_PROTECTION), 1e-6);
+    }
+
+    #[test]
+    fn with_no_vegetation_climate_only_speeds_erosion_up() {
+        for temperature_c in [-40.0, 0.0, 15.0, 35.0] {
+            let crust = crust_with_climate(0.0, temperature_c);
+            assert!(crust.effective_erosion_rate_mm_per_yr() >= crust.erosion_rate_mm_per_yr, "{} °C", temperature_c);
+        }
+        // Frost shatters rock fastest around freezing.
+        let freezing = crust_with_climate(0.0, 0.0).effective_erosion_rate_mm_per_yr();
+        assert!(freezing > crust_with_climate(0.0, -40.0).effective_erosion_rate_mm_per_yr());
+    }
+
+    #[test]
+    fn surface_builders_clamp_out_of_range_inputs() {
+        let icy = Crust::new(CrustType::Continental).with_albedo(0.85).with_vegetation(0.0).with_groundwater(40.0);
+        assert_eq!((icy.surface.albedo, icy.surface.base_albedo), (0.85, 0.85));
+        assert_eq!((icy.surface.vegetation_coverage_pct, icy.surface.groundwater_content_pct), (0.0, 40.0));
+
+        let high = Crust::new(CrustType::Oceanic).with_albedo(1.5).with_vegetation(250.0).with_groundwater(101.0);
+        assert_eq!((high.surface.albedo, high.surface.vegetation_coverage_pct, high.surface.groundwater_content_pct), (1.0, 100.0, 100.0));
+
+        let low = Crust::new(CrustType::Oceanic).with_albedo(-0.2).with_vegetation(-5.0).with_groundwater(-1.0);
+        assert_eq!((low.surface.albedo, low.surface.vegetation_coverage_pct, low.surface.groundwater_content_pct), (0.0, 0.0, 0.0));
+        assert!(low.validate().is_ok());
+    }
+
+    #[test]
+    fn vegetation_changes_stay_within_full_and_no_cover() {
+        let mut crust = Crust::new(CrustType::Continental).with_vegetation(90.0);
+        crust.update_vegetation(25.0);
+        assert_eq!(crust.surface.vegetation_coverage_pct, 100.0);
+        crust.update_vegetation(-150.0);
+        assert_eq!(crust.surface.vegetation_coverage_pct, 0.0);
+        crust.update_vegetation(30.0);
+        assert_eq!(crust.surface.vegetation_coverage_pct, 30.0);
+    }
+
+    #[test]
+    fn rebound_approaches_the_target_without_jumping() {
+        let mut crust = Crust::new(CrustType::Continental);
+        crust.adjust_isostasy(2.0, DEFAULT_MANTLE_DENSITY);
+        crust.relax_isostasy(1e7);
+        let loaded_km = crust.root_depth_km(DEFAULT_MANTLE_DENSITY);
+
+        // Melting the ice sheet moves the target at once but leaves the root where it was.
+        let target_km = crust.adjust_isostasy(-2.0, DEFAULT_MANTLE_DENSITY);
+        assert_close(target_km, 28.636_364, 1e-4);
+        assert_eq!(crust.root_depth_km(DEFAULT_MANTLE_DENSITY), loaded_km);
+
+        let mut gaps = Vec::new();
+        for _ in 0..20 {
+            crust.relax_isostasy(1_000.0);
+            gaps.push(crust.root_depth_km(DEFAULT_MANTLE_DENSITY) - target_km);
+        }
+        assert!(gaps.iter().all(|&gap| gap > 0.0), "the root overshot the target");
+        assert!(gaps.windows(2).all(|pair| pair[1] < pair[0]));
+        // Each step closes less of the distance than the one before.
+        let closed: Vec<f32> = gaps.windows(2).map(|pair| pair[0] - pair[1]).collect();
+        assert!(closed.windows(2).all(|pair| pair[1] < pair[0]));
+    }
+
+    #[test]
+    fn update_tectonics_caps_thickness_at_the_configured_limit() {
+        let thicken = |limits: &Limits| {
+            let mut crust = Crust::new(CrustType::Continental);
+            crust.tectonic_activity_factor = 1.0;
+            for _ in 0..200 {
+                crust.update_tectonics(500.0, limits);
+            }
+            crust.thickness_km
+        };
+
+        assert_eq!(thicken(&Limits::default()), crate::earth::limits::DEFAULT_MAX_CRUST_THICKNESS_KM);
+        let thick = Limits { max_crust_thickness_km: 120.0, ..Limits::default() };
+        assert!(thick.validate().is_ok());
+        assert_eq!(thicken(&thick), 120.0);
+    }
+
+    #[test]
+    fn continental_crust_stretched_thin_breaks_up_into_ocean_floor() {
+        let mut crust = Crust::new(CrustType::Continental);
+        let mut density = crust.average_density;
+        let mut breakups = 0;
+        while crust.thickness_km > BREAKUP_THICKNESS_KM / 2.0 {
+            crust.stretch(0.9);
+            let broke_up = crust.update_rift_transition();
+            if crust.kind == CrustType::Continental {
+                assert!(!broke_up);
+                assert!(crust.average_density >= density, "hyperextended crust got lighter");
+                density = crust.average_density;
+            } else if broke_up {
+                breakups += 1;
+                assert!(crust.thickness_km <= BREAKUP_THICKNESS_KM);
+            }
+        }
+
+        assert_eq!(breakups, 1);
+        assert_eq!(crust.kind, CrustType::Oceanic);
+        assert_eq!(crust.average_density, Crust::new(CrustType::Oceanic).average_density);
+        assert!(crust.provinces.iter().any(|province| province.composition == "new ocean floor"));
+    }
+
+    #[test]
+    fn a_thick_sediment_pile_compacts_and_lithifies() {
+        let mut crust = Crust::new(CrustType::Continental);
+        crust.sediment_thickness_km = 0.0;
+        crust.deposit_sediment(LITHIFICATION_DEPTH_KM * 2.0);
+        let (porosity_before, thickness_before_km, pile_before_km) = (crust.sediment_porosity, crust.thickness_km, crust.sediment_thickness_km);
+        assert_eq!(porosity_before, SURFACE_SEDIMENT_POROSITY);
+
+        let first_km = crust.compact_sediment(myr_to_years(COMPACTION_TIMESCALE_MYR));
+        assert!(crust.sediment_porosity < porosity_before);
+        let lithified_km = first_km + (0..20).map(|_| crust.compact_sediment(myr_to_years(COMPACTION_TIMESCALE_MYR))).sum::<f32>();
+
+        assert!(crust.sediment_porosity < porosity_before);
+        assert!(crust.sediment_thickness_km < pile_before_km);
+        assert!(crust.sediment_thickness_km <= LITHIFICATION_DEPTH_KM);
+        assert!(lithified_km > 0.0);
+        assert_close(crust.thickness_km, thickness_before_km + lithified_km, 1e-4);
+    }
+
+    #[test]
+    fn an_old_craton_makes_less_radiogenic_heat_than_young_crust() {
+        let mut young = Crust::new(CrustType::Continental);
+        young.update_heat_flux();
+        let mut old = young.clone();
+        old.age_provinces(myr_to_years(2_500.0));
+        old.update_heat_flux();
+
+        assert!(old.age_myr() > young.age_myr());
+        assert!(old.radiogenic_heat_flux > 0.0);
+        assert!(old.radiogenic_heat_flux < young.radiogenic_heat_flux, "{} vs {} W/m²", old.radiogenic_heat_flux, young.radiogenic_heat_flux);
+        assert!(old.heat_flux_out < young.heat_flux_out);
+        assert_close(young.heat_flux_out - young.radiogenic_heat_flux, old.heat_flux_out - old.radiogenic_heat_flux, 1e-6);
+    }
+
+    #[test]
+    fn parsing_an_unknown_crust_type_is_an_error_not_a_panic() {
+        assert_eq!("oceanic".parse::<Crust>().unwrap().kind, CrustType::Oceanic);
+        assert_eq!(Crust::from_str("continental").unwrap().kind, CrustType::Continental);
+
+        let Err(err) = Crust::from_str("mantle") else { panic!("'mantle' parsed as a crust type") };
+        assert_eq!(err, CrustError { input: "mantle".to_string() });
+        assert_eq!(err.to_string(), "invalid crust type 'mantle', expected 'continental' or 'oceanic'");
     }
 }
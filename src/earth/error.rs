@@ -0,0 +1,137 @@
+use std::fmt;
+
+use crate::earth::mantle::StaleFluxError;
+use crate::earth::scenario::ScenarioError;
+use crate::earth::simulation::CheckpointError;
+use crate::earth::validation::{Constraint, ValidationError};
+
+// One error type for the whole crate, for callers who would rather handle a single type
+// than each module's own. Module errors convert into it, so `?` works across them.
+#[derive(Debug)]
+pub enum WorldSimError {
+    // Parts of a composition that don't add up to 100%.
+    InvalidComposition { field: &'static str, total_pct: f32 },
+    OutOfRange(ValidationError),
+    Serialization(String),
+    Io(std::io::Error),
+    MissingPlate { id: u32 },
+    // A step ran its layers out of order and would have reused last step's core heat flux.
+    StaleHeatFlux(StaleFluxError),
+}
+
+impl fmt::Display for WorldSimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorldSimError::InvalidComposition { field, total_pct } => {
+                write!(f, "invalid composition: {} sums to {}%, not 100%", field, total_pct)
+            }
+            WorldSimError::OutOfRange(err) => write!(f, "parameter out of range: {}", err),
+            WorldSimError::Serialization(message) => write!(f, "serialization failed: {}", message),
+            WorldSimError::Io(err) => write!(f, "i/o failed: {}", err),
+            WorldSimError::MissingPlate { id } => write!(f, "no plate with id {}", id),
+            WorldSimError::StaleHeatFlux(err) => write!(f, "stale heat flux: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for WorldSimError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WorldSimError::OutOfRange(err) => Some(err),
+            WorldSimError::Io(err) => Some(err),
+            WorldSimError::StaleHeatFlux(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ValidationError> for WorldSimError {
+    fn from(err: ValidationError) -> Self {
+        match err.constraint {
+            Constraint::SumsToHundred => WorldSimError::InvalidComposition { field: err.field, total_pct: err.value },
+            _ => WorldSimError::OutOfRange(err),
+        }
+    }
+}
+
+// A failed `validate` reports every violation; this keeps the first. Call `validate`
+// directly for the full list.
+impl From<Vec<ValidationError>> for WorldSimError {
+    fn from(errors: Vec<ValidationError>) -> Self {
+        errors.into_iter().next().expect("validation only fails with at least one violation").into()
+    }
+}
+
+impl From<StaleFluxError> for WorldSimError {
+    fn from(err: StaleFluxError) -> Self {
+        WorldSimError::StaleHeatFlux(err)
+    }
+}
+
+impl From<ScenarioError> for WorldSimError {
+    fn from(err: ScenarioError) -> Self {
+        match err {
+            ScenarioError::Io(err) => WorldSimError::Io(err),
+            err => WorldSimError::Serialization(err.to_string()),
+        }
+    }
+}
+
+impl From<CheckpointError> for WorldSimError {
+    fn from(err: CheckpointError) -> Self {
+        match err {
+            CheckpointError::Io(err) => WorldSimError::Io(err),
+            CheckpointError::Parse(message) => WorldSimError::Serialization(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::mantle::PlateBuilder;
+    #[cfg(feature = "std")]
+    use crate::earth::scenario::Scenario;
+    use crate::earth::simulation::Simulation;
+    use crate::earth::Earth;
+
+    #[test]
+    fn a_composition_off_100_is_an_invalid_composition() {
+        let mut earth = Earth::new_seeded(1);
+        earth.outer_core.iron_pct += 30.0;
+        let err = WorldSimError::from(earth.validate().unwrap_err());
+        assert!(matches!(err, WorldSimError::InvalidComposition { field: "iron_pct + nickel_pct + light_elements_pct", total_pct } if total_pct > 100.0));
+    }
+
+    #[test]
+    fn a_zero_step_is_out_of_range() {
+        let err = Simulation::new(Earth::new_seeded(1)).run(1_000_000.0, 0.0, None).unwrap_err();
+        assert!(matches!(&err, WorldSimError::OutOfRange(violation) if violation.field == "step_years"));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn an_unsupported_scenario_format_is_a_serialization_error() {
+        let err = WorldSimError::from(Scenario::load("scenario.yaml").unwrap_err());
+        assert!(matches!(&err, WorldSimError::Serialization(message) if message.contains("scenario.yaml")));
+    }
+
+    #[cfg(all(feature = "std", feature = "serde"))]
+    #[test]
+    fn a_missing_scenario_file_is_an_io_error() {
+        let path = std::env::temp_dir().join("world_sim_no_such_scenario.json");
+        let err = WorldSimError::from(Scenario::load(&path).unwrap_err());
+        assert!(matches!(&err, WorldSimError::Io(io) if io.kind() == std::io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn linking_an_unknown_plate_is_a_missing_plate() {
+        let mut earth = Earth::new_seeded(1);
+        let id = earth.mantle.add_plate(PlateBuilder::new().build());
+        let err = earth.mantle.connect_plates(id, id + 99).unwrap_err();
+        assert!(matches!(err, WorldSimError::MissingPlate { id: missing } if missing == id + 99));
+        assert!(earth.mantle.plate(id).unwrap().neighbor_ids.is_empty());
+        assert_eq!(err.to_string(), format!("no plate with id {}", id + 99));
+    }
+}
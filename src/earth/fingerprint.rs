@@ -0,0 +1,113 @@
+use std::fmt;
+
+// 64-bit FNV-1a, spelled out here so fingerprints stay the same across Rust releases.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+// Floats are rounded to this many significant digits before hashing, so last-bit noise from
+// a reordered sum doesn't change the fingerprint.
+pub const FINGERPRINT_SIGNIFICANT_DIGITS: usize = 6;
+
+// Hashes the complete debug rendering of a state: every field, nested struct and list
+// element in declaration order, with each float quantized. Equal states render equally, so
+// they always fingerprint the same.
+//
+// The `Debug` output is therefore part of the fingerprint format. Adding, renaming or
+// reordering a field anywhere in the state, or hand-writing a `Debug` impl for one of its
+// types, changes every fingerprint, and any recorded ones have to be regenerated.
+pub fn fingerprint(state: &impl fmt::Debug) -> u64 {
+    let text = format!("{:?}", state);
+    let bytes = text.as_bytes();
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let follows_identifier = i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_');
+        let starts_number = bytes[i].is_ascii_digit()
+            || (bytes[i] == b'-' && bytes.get(i + 1).is_some_and(|next| next.is_ascii_digit()));
+        if !starts_number || follows_identifier {
+            hash = feed(hash, &bytes[i..i + 1]);
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len()
+            && (bytes[i].is_ascii_digit()
+                || bytes[i] == b'.'
+                || bytes[i] == b'e'
+                || ((bytes[i] == b'-' || bytes[i] == b'+') && bytes[i - 1] == b'e'))
+        {
+            i += 1;
+        }
+        hash = feed(hash, quantize(&text[start..i]).as_bytes());
+    }
+    hash
+}
+
+// Integers pass through unchanged; floats are rewritten in a fixed scientific form.
+fn quantize(number: &str) -> String {
+    if !number.contains(['.', 'e']) {
+        return number.to_string();
+    }
+    match number.parse::<f64>() {
+        Ok(0.0) => "0".to_string(),
+        Ok(value) => format!("{:.*e}", FINGERPRINT_SIGNIFICANT_DIGITS - 1, value),
+        Err(_) => number.to_string(),
+    }
+}
+
+fn feed(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "parallel")]
+    use crate::earth::mantle::PlateBuilder;
+    use crate::earth::Earth;
+
+    #[test]
+    fn a_clone_fingerprints_the_same_and_a_step_changes_it() {
+        let mut earth = Earth::new_seeded(21);
+        let fork = earth.clone();
+        assert_eq!(fork.state_fingerprint(), earth.state_fingerprint());
+        assert_eq!(fingerprint(&fork), earth.state_fingerprint());
+
+        earth.step(1_000_000.0).unwrap();
+        assert_ne!(earth.state_fingerprint(), fork.state_fingerprint());
+    }
+
+    #[test]
+    fn floats_are_quantized_and_integers_kept() {
+        assert_eq!(quantize("42"), "42");
+        assert_eq!(quantize("0.0"), "0");
+        assert_eq!(quantize("-0.0"), "0");
+        assert_eq!(quantize("1.0000000001"), quantize("1.0"));
+        assert_ne!(quantize("1.0001"), quantize("1.0"));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn the_thread_count_does_not_change_the_fingerprint() {
+        let run_on = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            pool.install(|| {
+                let mut earth = Earth::new_seeded(34);
+                for lon_deg in [-150.0, -90.0, -30.0, 30.0, 90.0, 150.0] {
+                    earth.mantle.add_plate(PlateBuilder::new().center(0.0, lon_deg).velocity(4.0).build());
+                }
+                for _ in 0..20 {
+                    earth.step(1_000_000.0).unwrap();
+                }
+                earth.state_fingerprint()
+            })
+        };
+        assert_eq!(run_on(1), run_on(8));
+    }
+}
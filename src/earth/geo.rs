@@ -0,0 +1,155 @@
+use crate::earth::units::{CM_PER_KM, YEARS_PER_MYR};
+
+pub const EARTH_RADIUS_KM: f32 = 6_371.0;
+
+// Haversine distance between two points on a spherical Earth.
+pub fn great_circle_km(lat1_deg: f32, lon1_deg: f32, lat2_deg: f32, lon2_deg: f32) -> f32 {
+    let (lat1, lat2) = (lat1_deg.to_radians(), lat2_deg.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (lon2_deg - lon1_deg).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+}
+
+// Initial direction of the great circle from the first point toward the second, in the same
+// convention as plate motion and mantle flow directions: 0° east, 90° north.
+pub fn bearing_deg(lat1_deg: f32, lon1_deg: f32, lat2_deg: f32, lon2_deg: f32) -> f32 {
+    let (lat1, lat2) = (lat1_deg.to_radians(), lat2_deg.to_radians());
+    let d_lon = (lon2_deg - lon1_deg).to_radians();
+    let east = d_lon.sin() * lat2.cos();
+    let north = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+    north.atan2(east).to_degrees().rem_euclid(360.0)
+}
+
+// Wraps a longitude into [-180, 180).
+pub fn normalize_lon(lon_deg: f32) -> f32 {
+    (lon_deg + 180.0).rem_euclid(360.0) - 180.0
+}
+
+// Point reached by travelling the given distance along a great circle that sets off in the
+// given direction (0° east, 90° north). Returns (lat, lon) with longitude normalized.
+pub fn destination(lat_deg: f32, lon_deg: f32, direction_deg: f32, distance_km: f32) -> (f32, f32) {
+    let lat1 = lat_deg.to_radians();
+    let compass = (90.0 - direction_deg).to_radians();
+    let angular = distance_km / EARTH_RADIUS_KM;
+    let lat2 = (lat1.sin() * angular.cos() + lat1.cos() * angular.sin() * compass.cos()).clamp(-1.0, 1.0).asin();
+    let d_lon = (compass.sin() * angular.sin() * lat1.cos()).atan2(angular.cos() - lat1.sin() * lat2.sin());
+    (lat2.to_degrees(), normalize_lon(lon_deg + d_lon.to_degrees()))
+}
+
+// Point half way along the great circle between two points.
+pub fn midpoint(lat1_deg: f32, lon1_deg: f32, lat2_deg: f32, lon2_deg: f32) -> (f32, f32) {
+    let half_km = great_circle_km(lat1_deg, lon1_deg, lat2_deg, lon2_deg) / 2.0;
+    destination(lat1_deg, lon1_deg, bearing_deg(lat1_deg, lon1_deg, lat2_deg, lon2_deg), half_km)
+}
+
+// Rotation of a rigid plate about an axis through the planet's centre. Every point on the
+// plate turns at the same angular rate, so its speed grows with angular distance from the
+// pole: nothing at the pole itself, the most 90° away.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EulerPole {
+    pub lat_deg: f32,
+    pub lon_deg: f32,
+    // Anticlockwise seen from above the pole.
+    pub rate_deg_per_myr: f32,
+}
+
+impl EulerPole {
+    // The pole that moves a point at the given speed and direction: 90° away from it, square
+    // to the motion.
+    pub fn from_motion(lat_deg: f32, lon_deg: f32, speed_cm_per_year: f32, direction_deg: f32) -> Self {
+        let (east, north) = local_axes(lat_deg, lon_deg);
+        let (sin, cos) = direction_deg.to_radians().sin_cos();
+        let heading = [0, 1, 2].map(|i| east[i] * cos + north[i] * sin);
+        let (lat_deg, lon_deg) = to_lat_lon(cross(unit_vector(lat_deg, lon_deg), heading));
+        let rate_rad_per_year = speed_cm_per_year / (EARTH_RADIUS_KM * CM_PER_KM);
+        Self { lat_deg, lon_deg, rate_deg_per_myr: rate_rad_per_year.to_degrees() * YEARS_PER_MYR }
+    }
+
+    // Speed (cm/yr) and direction (0° east, 90° north) of the surface at a point.
+    pub fn velocity_at(&self, lat_deg: f32, lon_deg: f32) -> (f32, f32) {
+        let rate_rad_per_year = self.rate_deg_per_myr.to_radians() / YEARS_PER_MYR;
+        let omega = unit_vector(self.lat_deg, self.lon_deg).map(|x| x * rate_rad_per_year);
+        let position = unit_vector(lat_deg, lon_deg).map(|x| x * EARTH_RADIUS_KM * CM_PER_KM);
+        let velocity = cross(omega, position);
+        let (east, north) = local_axes(lat_deg, lon_deg);
+        let (v_east, v_north) = (dot(velocity, east), dot(velocity, north));
+        (v_east.hypot(v_north), v_north.atan2(v_east).to_degrees().rem_euclid(360.0))
+    }
+}
+
+fn unit_vector(lat_deg: f32, lon_deg: f32) -> [f32; 3] {
+    let (lat, lon) = (lat_deg.to_radians(), lon_deg.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+fn to_lat_lon(v: [f32; 3]) -> (f32, f32) {
+    (v[2].atan2(v[0].hypot(v[1])).to_degrees(), v[1].atan2(v[0]).to_degrees())
+}
+
+// Unit vectors pointing east and north along the surface at a point.
+fn local_axes(lat_deg: f32, lon_deg: f32) -> ([f32; 3], [f32; 3]) {
+    let (lat, lon) = (lat_deg.to_radians(), lon_deg.to_radians());
+    ([-lon.sin(), lon.cos(), 0.0], [-lat.sin() * lon.cos(), -lat.sin() * lon.sin(), lat.cos()])
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!((actual - expected).abs() <= tolerance, "{} is not within {} of {}", actual, tolerance, expected);
+    }
+
+    #[test]
+    fn distances_match_known_pairs() {
+        assert_close(great_circle_km(0.0, 0.0, 1.0, 0.0), 111.19, 0.01);
+        assert_close(great_circle_km(0.0, 0.0, 0.0, 1.0), 111.19, 0.01);
+        // London to Paris.
+        assert_close(great_circle_km(51.5074, -0.1278, 48.8566, 2.3522), 343.5, 1.0);
+        assert_close(great_circle_km(0.0, 0.0, 0.0, 180.0), std::f32::consts::PI * EARTH_RADIUS_KM, 0.5);
+        assert_close(great_circle_km(0.0, 179.0, 0.0, -179.0), 222.39, 0.01);
+    }
+
+    #[test]
+    fn longitudes_wrap_into_range() {
+        assert_eq!(normalize_lon(190.0), -170.0);
+        assert_eq!(normalize_lon(-190.0), 170.0);
+        assert_eq!(normalize_lon(540.0), -180.0);
+        assert_eq!(normalize_lon(180.0), -180.0);
+        assert_eq!(normalize_lon(45.0), 45.0);
+    }
+
+    #[test]
+    fn an_euler_pole_is_still_at_its_pole_and_fastest_90_degrees_away() {
+        let pole = EulerPole { lat_deg: 60.0, lon_deg: -40.0, rate_deg_per_myr: 0.5 };
+        assert_close(pole.velocity_at(60.0, -40.0).0, 0.0, 1e-3);
+
+        // 0.5°/Myr on the equator of the rotation is 6371 km * 0.5° per Myr, about 5.56 cm/yr.
+        let (fastest, _) = pole.velocity_at(-30.0, -40.0);
+        assert_close(fastest, 5.56, 0.01);
+        for distance_deg in [10.0f32, 30.0, 60.0, 80.0, 100.0, 150.0] {
+            let (lat_deg, lon_deg) = destination(60.0, -40.0, 270.0, distance_deg.to_radians() * EARTH_RADIUS_KM);
+            let (speed, _) = pole.velocity_at(lat_deg, lon_deg);
+            assert!(speed < fastest);
+            assert_close(speed, fastest * distance_deg.to_radians().sin(), 0.01);
+        }
+    }
+
+    #[test]
+    fn a_pole_from_motion_reproduces_that_motion() {
+        let pole = EulerPole::from_motion(20.0, 50.0, 4.0, 135.0);
+        let (speed, direction_deg) = pole.velocity_at(20.0, 50.0);
+        assert_close(speed, 4.0, 1e-3);
+        assert_close(direction_deg, 135.0, 0.01);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::earth::geo::normalize_lon;
+use crate::earth::mantle::Plate;
+
+pub const DEFAULT_GRID_STEP_DEG: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridCell {
+    // None only when there are no plates to own the cell.
+    pub plate_id: Option<u32>,
+    pub crust_thickness_km: f32,
+    pub crust_age_myr: f32,
+    pub surface_temperature_c: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridField {
+    SurfaceTemperature,
+    CrustThickness,
+    CrustAge,
+    PlateId,
+}
+
+// Maps a value scaled to [0, 1] onto a colour. Plate ids are categorical and always use a
+// fixed palette instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Colormap {
+    // Blue through white to red, for diverging fields such as temperature.
+    Thermal,
+    Grayscale,
+}
+
+impl Colormap {
+    pub fn color(&self, t: f32) -> [u8; 3] {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |x: f32| (x * 255.0).round() as u8;
+        match self {
+            Colormap::Thermal if t < 0.5 => {
+                let x = t * 2.0;
+                [channel(x), channel(x), 255]
+            }
+            Colormap::Thermal => {
+                let x = (1.0 - t) * 2.0;
+                [255, channel(x), channel(x)]
+            }
+            Colormap::Grayscale => [channel(t); 3],
+        }
+    }
+}
+
+const PLATE_PALETTE: [[u8; 3]; 8] = [
+    [228, 26, 28],
+    [55, 126, 184],
+    [77, 175, 74],
+    [152, 78, 163],
+    [255, 127, 0],
+    [255, 255, 51],
+    [166, 86, 40],
+    [247, 129, 191],
+];
+const UNOWNED_COLOR: [u8; 3] = [0, 0, 0];
+
+// Regular latitude/longitude raster, stored row by row from the south pole and from 180°W
+// eastward. Each cell is identified by its centre.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grid {
+    pub lat_step_deg: f32,
+    pub lon_step_deg: f32,
+    pub rows: usize,
+    pub cols: usize,
+    pub cells: Vec<GridCell>,
+}
+
+impl Grid {
+    pub fn new(lat_step_deg: f32, lon_step_deg: f32) -> Self {
+        let rows = (180.0 / lat_step_deg).ceil() as usize;
+        let cols = (360.0 / lon_step_deg).ceil() as usize;
+        Self {
+            lat_step_deg,
+            lon_step_deg,
+            rows,
+            cols,
+            cells: vec![GridCell { plate_id: None, crust_thickness_km: 0.0, crust_age_myr: 0.0, surface_temperature_c: 0.0 }; rows * cols],
+        }
+    }
+
+    // Each cell goes to the plate it lies deepest inside by Plate::footprint_depth, which
+    // measures distance from the plate's centre in units of the radius of a circular plate of
+    // the same area, so larger plates reach further. Plates carry their cells with them as their centres move.
+    pub fn rasterize(&mut self, plates: &[Plate]) {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let (lat_deg, lon_deg) = self.cell_center(row, col);
+                let owner = plates.iter().min_by(|a, b| a.footprint_depth(lat_deg, lon_deg).total_cmp(&b.footprint_depth(lat_deg, lon_deg)));
+
+                self.cells[row * self.cols + col] = match owner {
+                    Some(plate) => GridCell {
+                        plate_id: Some(plate.id),
+                        crust_thickness_km: plate.crust.thickness_km,
+                        crust_age_myr: plate.crust.age_myr(),
+                        surface_temperature_c: plate.crust.surface_temperature_c,
+                    },
+                    None => GridCell { plate_id: None, crust_thickness_km: 0.0, crust_age_myr: 0.0, surface_temperature_c: 0.0 },
+                };
+            }
+        }
+    }
+
+    pub fn cell_center(&self, row: usize, col: usize) -> (f32, f32) {
+        let lat_deg = (-90.0 + (row as f32 + 0.5) * self.lat_step_deg).min(90.0);
+        let lon_deg = normalize_lon(-180.0 + (col as f32 + 0.5) * self.lon_step_deg);
+        (lat_deg, lon_deg)
+    }
+
+    pub fn cell_at(&self, lat_deg: f32, lon_deg: f32) -> &GridCell {
+        let row = (((lat_deg.clamp(-90.0, 90.0) + 90.0) / self.lat_step_deg) as usize).min(self.rows - 1);
+        let col = (((normalize_lon(lon_deg) + 180.0) / self.lon_step_deg) as usize).min(self.cols - 1);
+        &self.cells[row * self.cols + col]
+    }
+
+    pub fn cell_count(&self, plate_id: u32) -> usize {
+        self.cells.iter().filter(|cell| cell.plate_id == Some(plate_id)).count()
+    }
+
+    pub fn value(cell: &GridCell, field: GridField) -> f32 {
+        match field {
+            GridField::SurfaceTemperature => cell.surface_temperature_c,
+            GridField::CrustThickness => cell.crust_thickness_km,
+            GridField::CrustAge => cell.crust_age_myr,
+            GridField::PlateId => cell.plate_id.map_or(0.0, |id| id as f32),
+        }
+    }
+
+    #[cfg(feature = "image")]
+    pub fn render_heatmap(&self, field: GridField) -> image::RgbImage {
+        self.render_heatmap_with(field, Colormap::Thermal)
+    }
+
+    // One pixel per cell, north at the top and 180°W at the left edge. Values are stretched
+    // between the field's minimum and maximum on this grid, so frames of an animation are
+    // each scaled on their own.
+    #[cfg(feature = "image")]
+    pub fn render_heatmap_with(&self, field: GridField, colormap: Colormap) -> image::RgbImage {
+        let values: Vec<f32> = self.cells.iter().map(|cell| Grid::value(cell, field)).collect();
+        let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let span = max - min;
+
+        image::RgbImage::from_fn(self.cols as u32, self.rows as u32, |x, y| {
+            let index = (self.rows - 1 - y as usize) * self.cols + x as usize;
+            let color = match (field, self.cells[index].plate_id) {
+                (_, None) => UNOWNED_COLOR,
+                (GridField::PlateId, Some(id)) => PLATE_PALETTE[id as usize % PLATE_PALETTE.len()],
+                _ if span > 0.0 => colormap.color((values[index] - min) / span),
+                _ => colormap.color(0.5),
+            };
+            image::Rgb(color)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "image")]
+    use super::*;
+    use crate::earth::crust::CrustType;
+    use crate::earth::mantle::{Mantle, PlateBuilder};
+    use crate::earth::planet::PlanetConfig;
+
+    #[test]
+    fn every_cell_belongs_to_one_of_two_plates() {
+        let mut mantle = Mantle::new_seeded(&PlanetConfig::earth(), 4);
+        let continent = mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).center(40.0, -100.0).area_km2(8e7).build());
+        let ocean = mantle.add_plate(PlateBuilder::new().center(-20.0, 60.0).area_km2(1.5e8).build());
+        let grid = mantle.rasterize();
+
+        assert_eq!(grid.cells.len(), grid.rows * grid.cols);
+        assert_eq!(grid.cell_count(continent) + grid.cell_count(ocean), grid.rows * grid.cols);
+        assert!(grid.cell_count(continent) > 0 && grid.cell_count(ocean) > 0);
+        assert_eq!(grid.cell_at(40.0, -100.0).plate_id, Some(continent));
+        assert_eq!(grid.cell_at(-20.0, 60.0).plate_id, Some(ocean));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn a_36_by_18_heatmap_shows_hot_cells_redder() {
+        let mut grid = Grid::new(10.0, 10.0);
+        for (i, cell) in grid.cells.iter_mut().enumerate() {
+            *cell = GridCell { plate_id: Some(1), crust_thickness_km: 7.0, crust_age_myr: 0.0, surface_temperature_c: (i % 40) as f32 };
+        }
+        // The southernmost row is the bottom row of the image.
+        grid.cells[0].surface_temperature_c = 60.0;
+        grid.cells[1].surface_temperature_c = -40.0;
+
+        let image = grid.render_heatmap(GridField::SurfaceTemperature);
+        assert_eq!(image.dimensions(), (36, 18));
+        let (hot, cold) = (image.get_pixel(0, 17), image.get_pixel(1, 17));
+        assert!(hot[0] > cold[0] && hot[2] < cold[2], "hot {:?}, cold {:?}", hot, cold);
+    }
+}
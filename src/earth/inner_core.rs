@@ -1,71 +1,244 @@
+use crate::earth::geo::normalize_lon;
+use crate::earth::planet::{PlanetConfig, GRAVITATIONAL_CONSTANT};
+use crate::earth::thermal::{flux_to_tj_per_year, heat_content_tj, shell_volume_m3, sphere_area_m2, CORE_HEAT_CAPACITY_J_PER_KG_K};
+use crate::earth::units::{km_to_m, mm_per_year_to_km_per_year, myr_to_years};
+use crate::earth::validation::{ValidationError, Validator};
+use std::f64::consts::PI;
+
+// e-folding time for the inner core to cool to the boundary temperature.
+const COOLING_TIMESCALE_MYR: f32 = 1_000.0;
+// Clausius-Clapeyron slope of the iron melting curve at core pressures: the boundary
+// freezes this much colder per GPa of pressure it loses as it moves outward.
+const MELTING_SLOPE_K_PER_GPA: f32 = 8.0;
+// Crystallization rate (mm/yr) per kelvin of contrast across the boundary; the default
+// gives Earth's present 1 mm/yr for its initial 1100 K contrast.
+const DEFAULT_COOLING_COUPLING_MM_PER_YEAR_PER_K: f32 = 1.0 / 1_100.0;
+const INITIAL_AGE_MYR: f32 = 1_000.0;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InnerCore {
+    // Mean of the two hemisphere radii.
     pub radius_km: f32,
+    pub radius_east_km: f32,
+    pub radius_west_km: f32,
     pub temperature_c: f32,
+    pub icb_temperature_c: f32,
+    pub icb_pressure_gpa: f32,
+    pub density: f32,
+    // Central pressure.
     pub pressure_gpa: f32,
+    // Overburden above the inner core: the outer core out to the core radius, then a
+    // mantle of uniform mean density out to the planet's surface.
+    pub core_radius_km: f32,
+    pub outer_core_density: f32,
+    pub planet_radius_km: f32,
+    pub mantle_density: f32,
     pub iron_pct: f32,
     pub nickel_pct: f32,
     pub other_elements_pct: f32,
-    pub heat_flux_mw_per_m2: f32,
+    pub heat_flux_w_per_m2: f32,
     pub crystallization_rate_mm_per_year: f32,
+    pub cooling_coupling_mm_per_year_per_k: f32,
+    pub secular_cooling_tj_per_year: f64,
     pub mass_kg: f64,
+    // Age when the simulation began; the current age adds the planet's clock to it.
+    pub initial_age_myr: f32,
     pub age_myr: f32,
     pub rotation_offset_deg_per_year: f32,
+    pub cumulative_rotation_deg: f32,
     pub magnetic_contribution_factor: f32,
     pub crystal_anisotropy_factor: f32,
     pub latent_heat_release_tj_per_year: f32,
+    // Potential energy freed as light elements expelled at the boundary rise through the
+    // outer core, from the growth rate and the density jump across the boundary.
+    pub gravitational_energy_release_tj_per_year: f32,
     pub asymmetric_growth_factor: f32,
+    pub cumulative_heat_released_tj: f64,
 }
 
 impl InnerCore {
-    pub fn new() -> Self {
-        Self {
-            radius_km: 1221.0,
-            temperature_c: 5400.0,
-            pressure_gpa: 330.0,
+    pub fn new(config: &PlanetConfig) -> Self {
+        let mut inner_core = Self {
+            radius_km: config.inner_core_radius_km,
+            radius_east_km: config.inner_core_radius_km,
+            radius_west_km: config.inner_core_radius_km,
+            temperature_c: config.inner_core_temperature_c,
+            icb_temperature_c: config.icb_temperature_c,
+            icb_pressure_gpa: 0.0,
+            density: config.inner_core_density,
+            pressure_gpa: 0.0,
+            core_radius_km: config.core_radius_km,
+            outer_core_density: config.outer_core_density,
+            planet_radius_km: config.radius_km,
+            mantle_density: config.mean_mantle_density(),
             iron_pct: 80.0,
             nickel_pct: 20.0,
             other_elements_pct: 0.0,
-            heat_flux_mw_per_m2: 0.05,
+            heat_flux_w_per_m2: 0.05,
             crystallization_rate_mm_per_year: 1.0,
-            mass_kg: 9.7e22,
-            age_myr: 1000.0,
+            cooling_coupling_mm_per_year_per_k: DEFAULT_COOLING_COUPLING_MM_PER_YEAR_PER_K,
+            secular_cooling_tj_per_year: 0.0,
+            mass_kg: 0.0,
+            initial_age_myr: INITIAL_AGE_MYR,
+            age_myr: INITIAL_AGE_MYR,
             rotation_offset_deg_per_year: 0.1,
+            cumulative_rotation_deg: 0.0,
             magnetic_contribution_factor: 0.9,
             crystal_anisotropy_factor: 0.5,
             latent_heat_release_tj_per_year: 50.0,
+            gravitational_energy_release_tj_per_year: 0.0,
             asymmetric_growth_factor: 0.1,
-        }
+            cumulative_heat_released_tj: 0.0,
+        };
+        inner_core.update_mass();
+        inner_core.icb_pressure_gpa = inner_core.pressure_at_radius(inner_core.radius_km);
+        inner_core.pressure_gpa = inner_core.pressure_at_radius(0.0);
+        inner_core.update_gravitational_energy();
+        inner_core
+    }
+
+    pub fn reset(&mut self, config: &PlanetConfig) {
+        *self = Self::new(config);
     }
 
-    pub fn update_crystallization(&mut self, years: f32) {
-        self.age_myr += years / 1_000_000.0;
+    // `absolute_time_myr` is the planet's clock at the end of this step.
+    pub fn update_crystallization(&mut self, years: f32, absolute_time_myr: f32) {
+        self.age_myr = self.initial_age_myr + absolute_time_myr;
+        // Reduce the step's rotation in f64 first; long steps turn through many full revolutions.
+        let step_rotation_deg = (self.rotation_offset_deg_per_year as f64 * years as f64) % 360.0;
+        self.cumulative_rotation_deg = (self.cumulative_rotation_deg + step_rotation_deg as f32).rem_euclid(360.0);
+
+        // Freezing is driven by the heat drawn out across the boundary, so the rate follows
+        // the temperature contrast and slows as the inner core approaches the ICB temperature.
+        let delta_t = self.icb_temperature_contrast_c();
+        self.crystallization_rate_mm_per_year = (self.cooling_coupling_mm_per_year_per_k * delta_t).max(0.0);
 
-        let growth_km = (self.crystallization_rate_mm_per_year * years) / 1_000_000.0;
-        self.radius_km += growth_km;
+        // The eastern hemisphere freezes faster than the western by the asymmetry factor,
+        // leaving the mean growth unchanged.
+        let growth_km = mm_per_year_to_km_per_year(self.crystallization_rate_mm_per_year) * years;
+        // Neither hemisphere can grow past the core-mantle boundary.
+        self.radius_east_km = (self.radius_east_km + growth_km * (1.0 + self.asymmetric_growth_factor)).min(self.core_radius_km);
+        self.radius_west_km = (self.radius_west_km + growth_km * (1.0 - self.asymmetric_growth_factor)).min(self.core_radius_km);
+        self.radius_km = (self.radius_east_km + self.radius_west_km) / 2.0;
+        // Frozen out to the core-mantle boundary, there is no liquid left to crystallize.
+        if self.radius_km >= self.core_radius_km {
+            self.crystallization_rate_mm_per_year = 0.0;
+        }
 
         self.update_mass();
+        self.update_pressure();
+        self.cool(delta_t, years);
         self.update_heat_flux();
 
         self.latent_heat_release_tj_per_year = self.crystallization_rate_mm_per_year * 50.0;
+        self.update_gravitational_energy();
+        self.cumulative_heat_released_tj += self.heat_output_tj_per_year() * years as f64;
+    }
+
+    // Conducted heat across the inner-core boundary, latent heat of crystallization, and the
+    // heat given up by the inner core's own cooling.
+    pub fn heat_output_tj_per_year(&self) -> f64 {
+        flux_to_tj_per_year(self.heat_flux_w_per_m2, sphere_area_m2(self.radius_km))
+            + self.latent_heat_release_tj_per_year as f64
+            + self.secular_cooling_tj_per_year
+    }
+
+    pub fn set_cooling_model(&mut self, coeff: f32) {
+        self.cooling_coupling_mm_per_year_per_k = coeff.max(0.0);
+    }
+
+    pub fn icb_temperature_contrast_c(&self) -> f32 {
+        self.temperature_c - self.icb_temperature_c
+    }
+
+    // Relaxes the temperature toward the boundary and records the heat that releases so it
+    // is passed on to the outer core with the rest of the output.
+    fn cool(&mut self, delta_t: f32, years: f32) {
+        let cooling_k = delta_t * (1.0 - (-years / myr_to_years(COOLING_TIMESCALE_MYR)).exp());
+        self.temperature_c -= cooling_k;
+        self.secular_cooling_tj_per_year = if years > 0.0 {
+            self.mass_kg * CORE_HEAT_CAPACITY_J_PER_KG_K / 1e12 * cooling_k as f64 / years as f64
+        } else {
+            0.0
+        };
+    }
+
+    // Freezing leaves the light elements behind in the liquid, so each kilogram frozen
+    // releases fluid lighter than the outer core by the boundary density jump. Lifting that
+    // buoyancy from the boundary to the core-mantle boundary against gravity at the boundary
+    // frees (delta rho / rho) * g * h per kilogram.
+    fn update_gravitational_energy(&mut self) {
+        let radius_m = km_to_m(self.radius_km as f64);
+        let growth_m_per_year = km_to_m(mm_per_year_to_km_per_year(self.crystallization_rate_mm_per_year) as f64);
+        let frozen_kg_per_year = sphere_area_m2(self.radius_km) * growth_m_per_year * self.density as f64;
+
+        let density_contrast = ((self.density - self.outer_core_density) / self.outer_core_density).max(0.0) as f64;
+        let icb_gravity = GRAVITATIONAL_CONSTANT * self.mass_kg / (radius_m * radius_m);
+        let rise_m = km_to_m((self.core_radius_km - self.radius_km).max(0.0) as f64);
+
+        self.gravitational_energy_release_tj_per_year = (frozen_kg_per_year * density_contrast * icb_gravity * rise_m / 1e12) as f32;
+    }
+
+    // Lithostatic pressure, integrating density times local gravity from the surface down
+    // through the uniform-density mantle, outer core and inner core.
+    pub fn pressure_at_radius(&self, r_km: f32) -> f32 {
+        let r_m = r_km.max(0.0) as f64 * 1_000.0;
+        let shells = [
+            (self.radius_km, self.density),
+            (self.core_radius_km, self.outer_core_density),
+            (self.planet_radius_km, self.mantle_density),
+        ];
+
+        let mut pressure_pa = 0.0;
+        let mut mass_below_kg = 0.0;
+        let mut inner_m: f64 = 0.0;
+        for (outer_km, density) in shells {
+            let outer_m = outer_km as f64 * 1_000.0;
+            let rho = density as f64;
+            let from_m = inner_m.max(r_m);
+            if outer_m > from_m {
+                // Within a uniform shell g(s) = G * (m + 4/3 pi rho s³) / s², where m is the
+                // mass below the shell less the same density filled in to the centre.
+                let point_mass_kg = mass_below_kg - (4.0 / 3.0) * PI * rho * inner_m.powi(3);
+                let point_term = if from_m > 0.0 { point_mass_kg * (1.0 / from_m - 1.0 / outer_m) } else { 0.0 };
+                let shell_term = (2.0 / 3.0) * PI * rho * (outer_m.powi(2) - from_m.powi(2));
+                pressure_pa += GRAVITATIONAL_CONSTANT * rho * (point_term + shell_term);
+            }
+            mass_below_kg += shell_volume_m3(inner_m as f32 / 1_000.0, outer_km) * rho;
+            inner_m = outer_m;
+        }
+        (pressure_pa / 1e9) as f32
+    }
+
+    // A growing inner core pushes its boundary out to lower pressure, where iron melts at a
+    // lower temperature, so the boundary temperature follows the melting curve.
+    fn update_pressure(&mut self) {
+        let icb_pressure_gpa = self.pressure_at_radius(self.radius_km);
+        self.icb_temperature_c += MELTING_SLOPE_K_PER_GPA * (icb_pressure_gpa - self.icb_pressure_gpa);
+        self.icb_pressure_gpa = icb_pressure_gpa;
+        self.pressure_gpa = self.pressure_at_radius(0.0);
+    }
+
+    pub fn heat_content_tj(&self) -> f64 {
+        heat_content_tj(self.mass_kg, CORE_HEAT_CAPACITY_J_PER_KG_K, self.temperature_c)
     }
 
     fn update_mass(&mut self) {
         let radius_m = self.radius_km * 1_000.0;
         let volume_m3 = (4.0 / 3.0) * std::f32::consts::PI * radius_m.powi(3);
-        let density = 12_800.0;
 
-        self.mass_kg = (volume_m3 as f64) * (density as f64);
+        self.mass_kg = (volume_m3 as f64) * (self.density as f64);
     }
 
     fn update_heat_flux(&mut self) {
         let radius_m = self.radius_km * 1_000.0;
         let area_m2 = 4.0 * std::f32::consts::PI * radius_m.powi(2);
 
-        let outer_core_temp_c = 4300.0;
-        let delta_t = self.temperature_c - outer_core_temp_c;
+        let delta_t = self.icb_temperature_contrast_c();
 
         let k = 1e6;
-        self.heat_flux_mw_per_m2 = (k * delta_t / area_m2) * 1e-6;
+        self.heat_flux_w_per_m2 = k * delta_t / area_m2;
     }
 
     pub fn update_anisotropy(&mut self, delta: f32) {
@@ -76,8 +249,10 @@ impl InnerCore {
         self.asymmetric_growth_factor = (self.asymmetric_growth_factor + delta).clamp(0.0, 1.0);
     }
 
-    pub fn cumulative_rotation(&self, years: f32) -> f32 {
-        self.rotation_offset_deg_per_year * years
+    // Super-rotation expressed as the mantle longitude the inner core's reference meridian has
+    // turned to, in [-180, 180); positive is eastward.
+    pub fn rotation_relative_to_mantle(&self) -> f32 {
+        normalize_lon(self.cumulative_rotation_deg)
     }
 
     pub fn adjust_magnetic_contribution(&mut self, delta: f32) {
@@ -88,20 +263,169 @@ impl InnerCore {
         self.magnetic_contribution_factor = new_value.clamp(0.0, 1.0);
     }
 
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        Validator::new()
+            .composition("iron_pct + nickel_pct + other_elements_pct", &[self.iron_pct, self.nickel_pct, self.other_elements_pct])
+            .positive("radius_km", self.radius_km)
+            .positive("mass_kg", self.mass_kg as f32)
+            .above_absolute_zero("temperature_c", self.temperature_c)
+            .finish()
+    }
+
+    #[cfg(feature = "std")]
     pub fn describe(&self) {
-        println!("Inner Core:");
-        println!("  Radius: {:.1} km", self.radius_km);
-        println!("  Temperature: {:.1} °C", self.temperature_c);
-        println!("  Pressure: {:.1} GPa", self.pressure_gpa);
-        println!("  Composition: {:.1}% iron, {:.1}% nickel, {:.1}% other", self.iron_pct, self.nickel_pct, self.other_elements_pct);
-        println!("  Heat flux: {:.3} MW/m²", self.heat_flux_mw_per_m2);
-        println!("  Crystallization rate: {:.2} mm/year", self.crystallization_rate_mm_per_year);
-        println!("  Mass: {:.2e} kg", self.mass_kg);
-        println!("  Age: {:.1} million years", self.age_myr);
-        println!("  Rotation offset: {:.3} deg/year", self.rotation_offset_deg_per_year);
-        println!("  Magnetic contribution: {:.2}", self.magnetic_contribution_factor);
-        println!("  Crystal anisotropy: {:.2}", self.crystal_anisotropy_factor);
-        println!("  Latent heat release: {:.1} TJ/year", self.latent_heat_release_tj_per_year);
-        println!("  Asymmetric growth factor: {:.2}", self.asymmetric_growth_factor);
+        println!("{}", self.summary());
+    }
+
+    pub fn summary(&self) -> String {
+        let mut lines = vec!["Inner Core:".to_string()];
+        lines.push(format!("  Radius: {:.1} km (east {:.1} km, west {:.1} km)", self.radius_km, self.radius_east_km, self.radius_west_km));
+        lines.push(format!("  Temperature: {:.1} °C", self.temperature_c));
+        lines.push(format!("  Pressure: {:.1} GPa at centre, {:.1} GPa at boundary", self.pressure_gpa, self.icb_pressure_gpa));
+        lines.push(format!("  Composition: {:.1}% iron, {:.1}% nickel, {:.1}% other", self.iron_pct, self.nickel_pct, self.other_elements_pct));
+        lines.push(format!("  Heat flux: {:.3} W/m²", self.heat_flux_w_per_m2));
+        lines.push(format!("  Crystallization rate: {:.2} mm/year", self.crystallization_rate_mm_per_year));
+        lines.push(format!("  Mass: {:.2e} kg", self.mass_kg));
+        lines.push(format!("  Age: {:.1} million years", self.age_myr));
+        lines.push(format!("  Rotation offset: {:.3} deg/year", self.rotation_offset_deg_per_year));
+        lines.push(format!("  Rotation relative to mantle: {:.1}°", self.rotation_relative_to_mantle()));
+        lines.push(format!("  Magnetic contribution: {:.2}", self.magnetic_contribution_factor));
+        lines.push(format!("  Crystal anisotropy: {:.2}", self.crystal_anisotropy_factor));
+        lines.push(format!("  Latent heat release: {:.1} TJ/year", self.latent_heat_release_tj_per_year));
+        lines.push(format!("  Gravitational energy release: {:.2e} TJ/year", self.gravitational_energy_release_tj_per_year));
+        lines.push(format!("  Asymmetric growth factor: {:.2}", self.asymmetric_growth_factor));
+        lines.push(format!("  Cumulative heat released: {:.2e} TJ", self.cumulative_heat_released_tj));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::validation::Constraint;
+
+    fn earth_core() -> InnerCore {
+        InnerCore::new(&PlanetConfig::earth())
+    }
+
+    #[test]
+    fn a_fresh_core_is_valid() {
+        assert_eq!(earth_core().validate(), Ok(()));
+    }
+
+    #[test]
+    fn a_composition_summing_to_150_is_flagged() {
+        let mut core = earth_core();
+        core.other_elements_pct = 50.0;
+        let errors = core.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "iron_pct + nickel_pct + other_elements_pct");
+        assert_eq!(errors[0].value, 150.0);
+        assert_eq!(errors[0].constraint, Constraint::SumsToHundred);
+    }
+
+    #[test]
+    fn super_rotation_accumulates_modulo_a_full_turn() {
+        let mut core = earth_core();
+        let years = 1_000_000.0;
+        core.update_crystallization(years, 1.0);
+
+        let expected_deg = (core.rotation_offset_deg_per_year as f64 * years as f64).rem_euclid(360.0) as f32;
+        assert!((core.cumulative_rotation_deg - expected_deg).abs() < 1e-3);
+        assert!((core.rotation_relative_to_mantle() - normalize_lon(expected_deg)).abs() < 1e-3);
+
+        // The same million years taken in ten steps ends up at the same angle.
+        let mut stepped = earth_core();
+        for step in 1..=10 {
+            stepped.update_crystallization(years / 10.0, step as f32 * 0.1);
+        }
+        assert!((stepped.cumulative_rotation_deg - expected_deg).abs() < 1e-2);
+    }
+
+    #[test]
+    fn crystallization_slows_as_the_core_cools() {
+        let mut core = earth_core();
+        let mut rates = Vec::new();
+        for step in 1..=100 {
+            core.update_crystallization(10_000_000.0, step as f32 * 10.0);
+            rates.push(core.crystallization_rate_mm_per_year);
+        }
+        assert!(rates.iter().any(|&rate| rate != 1.0));
+        assert!(rates.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(rates[99] < rates[0]);
+    }
+
+    #[test]
+    fn a_larger_inner_core_raises_the_central_pressure() {
+        let core = earth_core();
+        let mut grown = earth_core();
+        grown.radius_km += 200.0;
+        assert!(grown.pressure_at_radius(0.0) > core.pressure_at_radius(0.0));
+        assert!(core.pressure_at_radius(0.0) > core.pressure_at_radius(core.radius_km));
+
+        let mut growing = earth_core();
+        let central_before = growing.pressure_gpa;
+        for step in 1..=10 {
+            growing.update_crystallization(10_000_000.0, step as f32 * 10.0);
+        }
+        assert!(growing.radius_km > core.radius_km);
+        assert!(growing.pressure_gpa > central_before);
+    }
+
+    #[test]
+    fn the_summary_is_returned_rather_than_printed() {
+        let summary = earth_core().summary();
+        assert!(summary.starts_with("Inner Core:"));
+        assert!(summary.contains("Radius"));
+        assert!(summary.contains("°C") && !summary.contains("Â"));
+    }
+
+    #[test]
+    fn an_asymmetric_core_grows_its_hemispheres_apart() {
+        let mut core = earth_core();
+        core.adjust_asymmetry(0.5 - core.asymmetric_growth_factor);
+        assert_eq!(core.asymmetric_growth_factor, 0.5);
+        for step in 1..=10 {
+            core.update_crystallization(1_000_000.0, step as f32);
+        }
+        assert!(core.radius_east_km > core.radius_west_km);
+        assert_eq!(core.radius_km, (core.radius_east_km + core.radius_west_km) / 2.0);
+
+        let mut symmetric = earth_core();
+        symmetric.adjust_asymmetry(-1.0);
+        symmetric.update_crystallization(1_000_000.0, 1.0);
+        assert_eq!(symmetric.radius_east_km, symmetric.radius_west_km);
+    }
+
+    #[test]
+    fn gravitational_energy_scales_with_the_freezing_rate() {
+        let release_at = |coupling: f32| {
+            let mut core = earth_core();
+            core.set_cooling_model(coupling);
+            core.update_crystallization(1_000.0, 0.001);
+            (core.crystallization_rate_mm_per_year, core.gravitational_energy_release_tj_per_year)
+        };
+
+        let (slow_rate, slow) = release_at(0.000_1);
+        let (fast_rate, fast) = release_at(0.000_2);
+        assert!(slow > 0.0);
+        assert!((fast_rate / slow_rate - 2.0).abs() < 1e-3);
+        assert!((fast / slow - 2.0).abs() < 1e-2, "{} is not twice {}", fast, slow);
+
+        let (_, frozen) = release_at(0.0);
+        assert_eq!(frozen, 0.0);
+    }
+
+    #[test]
+    fn a_core_frozen_to_the_boundary_releases_no_latent_heat() {
+        let mut core = earth_core();
+        core.set_cooling_model(1.0);
+        core.update_crystallization(1_000.0, 0.001);
+        assert!(core.latent_heat_release_tj_per_year > 0.0);
+
+        core.update_crystallization(1e9, 1_000.0);
+        assert_eq!(core.radius_km, core.core_radius_km);
+        assert_eq!((core.crystallization_rate_mm_per_year, core.latent_heat_release_tj_per_year), (0.0, 0.0));
+        assert_eq!(core.gravitational_energy_release_tj_per_year, 0.0);
     }
 }
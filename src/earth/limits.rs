@@ -0,0 +1,55 @@
+use crate::earth::validation::{ValidationError, Validator};
+
+// Erosion strips crust no thinner than the floor, and deformation and collisions thicken it
+// no further than the cap, about the deepest continental roots under Tibet and the Andes.
+pub const DEFAULT_MIN_CRUST_THICKNESS_KM: f32 = 5.0;
+pub const DEFAULT_MAX_CRUST_THICKNESS_KM: f32 = 70.0;
+// Lateral flow the core can drive through the asthenosphere, and deep convection through
+// the lower mantle.
+pub const DEFAULT_MAX_ASTHENOSPHERE_FLOW_CM_PER_YEAR: f32 = 20.0;
+pub const DEFAULT_MAX_LOWER_MANTLE_FLOW_CM_PER_YEAR: f32 = 10.0;
+
+// The physical bounds the model holds its state within, in one place so they can be tuned
+// together. Omitted fields in a config file keep their defaults.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Limits {
+    pub min_crust_thickness_km: f32,
+    pub max_crust_thickness_km: f32,
+    pub max_asthenosphere_flow_cm_per_year: f32,
+    pub max_lower_mantle_flow_cm_per_year: f32,
+    pub min_albedo: f32,
+    pub max_albedo: f32,
+}
+
+impl Limits {
+    pub fn clamp_albedo(&self, albedo: f32) -> f32 {
+        albedo.clamp(self.min_albedo, self.max_albedo)
+    }
+
+    // Each cap must sit at or above its floor, or `clamp` would have nothing to hold to.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        Validator::new()
+            .non_negative("min_crust_thickness_km", self.min_crust_thickness_km)
+            .within("max_crust_thickness_km", self.max_crust_thickness_km, self.min_crust_thickness_km, f32::INFINITY)
+            .non_negative("max_asthenosphere_flow_cm_per_year", self.max_asthenosphere_flow_cm_per_year)
+            .non_negative("max_lower_mantle_flow_cm_per_year", self.max_lower_mantle_flow_cm_per_year)
+            .within("min_albedo", self.min_albedo, 0.0, 1.0)
+            .within("max_albedo", self.max_albedo, self.min_albedo, 1.0)
+            .finish()
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            min_crust_thickness_km: DEFAULT_MIN_CRUST_THICKNESS_KM,
+            max_crust_thickness_km: DEFAULT_MAX_CRUST_THICKNESS_KM,
+            max_asthenosphere_flow_cm_per_year: DEFAULT_MAX_ASTHENOSPHERE_FLOW_CM_PER_YEAR,
+            max_lower_mantle_flow_cm_per_year: DEFAULT_MAX_LOWER_MANTLE_FLOW_CM_PER_YEAR,
+            min_albedo: 0.0,
+            max_albedo: 1.0,
+        }
+    }
+}
@@ -1,75 +1,628 @@
-use crate::earth::crust::Crust;
+use crate::earth::crust::{Crust, CrustType};
+use crate::earth::error::WorldSimError;
+use crate::earth::geo::{bearing_deg, destination, great_circle_km, midpoint, EulerPole, EARTH_RADIUS_KM};
+use crate::earth::grid::{Grid, DEFAULT_GRID_STEP_DEG};
+use crate::earth::limits::Limits;
+use crate::earth::planet::PlanetConfig;
+use crate::earth::thermal::{arrhenius_viscosity, flux_to_tj_per_year, heat_content_tj, shell_volume_m3, sphere_area_m2, RadiogenicHeat, MANTLE_HEAT_CAPACITY_J_PER_KG_K, SECONDS_PER_YEAR};
+use crate::earth::units::{cm_per_year_to_km_per_year, years_to_myr};
+#[cfg(feature = "std")]
+use crate::earth::units::myr_to_years;
+use crate::earth::validation::{ValidationError, Validator};
+use crate::earth::volatiles::{VolatileBudget, OCEAN_MASS_WATER_GT};
+use log::debug;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
+// Mean time between plumes leaving a D'' layer at full generation potential.
+const PLUME_RECURRENCE_YEARS: f32 = 20_000_000.0;
+const PLUME_POTENTIAL_DECAY: f32 = 0.9;
+const PLUME_MAX_LATITUDE_DEG: f32 = 60.0;
+// Plumes rise mostly from the edges of the large low-shear-velocity provinces, falling off
+// over this distance either side of the rim; anywhere else keeps the background chance.
+const LLSVP_EDGE_WIDTH_KM: f32 = 1_000.0;
+const PLUME_BACKGROUND_WEIGHT: f32 = 0.1;
+// Earth's two provinces, under Africa and the central Pacific, as seen from the surface.
+const AFRICAN_LLSVP: Llsvp = Llsvp { lat_deg: -10.0, lon_deg: 15.0, radius_km: 4_500.0 };
+const PACIFIC_LLSVP: Llsvp = Llsvp { lat_deg: -10.0, lon_deg: -160.0, radius_km: 4_500.0 };
+// A plume rises at this speed times its buoyancy, which never drops below the floor so a
+// weak one still arrives eventually. Its head holds up to the maximum volume of melt, all
+// erupted at once as a large igneous province when it reaches the lithosphere.
+const PLUME_ASCENT_CM_PER_YEAR: f32 = 100.0;
+const MIN_PLUME_BUOYANCY: f32 = 0.1;
+const MAX_PLUME_HEAD_VOLUME_KM3: f32 = 5_000_000.0;
+// Magma a hot spot supplies to the plate above it, and how many times that a hot spot on a
+// spreading ridge supplies, where decompression melting adds to the plume's own melt. A
+// point counts as on the ridge when it lies within this band across a spreading plate's
+// footprint: far enough out to be near the edge, but not so far it is off the plate.
+const HOT_SPOT_MAGMA_KM3_PER_YEAR: f32 = 0.05;
+const RIDGE_HOT_SPOT_AMPLIFICATION: f32 = 4.0;
+const RIDGE_MARGIN_DEPTH: std::ops::RangeInclusive<f32> = 0.8..=1.2;
+// Arc a hot spot drifts per migration for each unit of mantle flow beneath it.
+const HOT_SPOT_DRIFT_DEG_PER_FLOW: f32 = 0.1;
+// A landmark raised within this distance of a named feature of the same kind is that feature
+// growing, so a collision running for many steps names one range rather than one per step.
+const FEATURE_MERGE_RADIUS_KM: f32 = 1_000.0;
+
+// Earth's mantle layer densities (kg/m³); other planets scale them by their mantle density.
+pub const LITHOSPHERE_DENSITY: f32 = 3300.0;
+pub const ASTHENOSPHERE_DENSITY: f32 = 3400.0;
+pub const TRANSITION_ZONE_DENSITY: f32 = 3900.0;
+pub const LOWER_MANTLE_DENSITY: f32 = 4900.0;
+pub const D_PRIME_PRIME_DENSITY: f32 = 5500.0;
+
+// Wilson cycle stage lengths and the age at which ocean floor becomes dense enough to sink.
+const RIFTING_DURATION_MYR: f32 = 10.0;
+const SUBDUCTION_ONSET_AGE_MYR: f32 = 100.0;
+const SUBDUCTION_DURATION_MYR: f32 = 50.0;
+const COLLISION_DURATION_MYR: f32 = 20.0;
+const SUTURING_DURATION_MYR: f32 = 30.0;
+
+pub const TRANSFORM_SLIP_THRESHOLD_MPA: f32 = 300.0;
+// 5 MPa of loading per 100 kyr on a locked transform boundary.
+pub const TRANSFORM_STRESS_RATE_MPA_PER_YEAR: f32 = 5e-5;
+// Only a small fraction of the accumulated shear stress drops in a single rupture.
+const SLIP_STRESS_DROP_FRACTION: f32 = 0.01;
+const SEISMOGENIC_WIDTH_KM: f32 = 20.0;
+const MAX_RUPTURE_LENGTH_KM: f32 = 1_500.0;
+
+// The lithosphere as a whole is loaded by plate convergence and fails once it reaches its
+// yield stress, dropping back to the background level it rests at between earthquakes.
+pub const LITHOSPHERE_YIELD_STRESS_MPA: f32 = 250.0;
+pub const LITHOSPHERE_BACKGROUND_STRESS_MPA: f32 = 50.0;
+// Stress built per year for each cm/yr of convergence.
+const CONVERGENT_STRESS_RATE_MPA_PER_CM: f32 = 1e-5;
+
+// Convergent boundaries: plate area handed from the sinking plate to the overriding one
+// per cm/yr of closing, extra thickening where two continents meet, and how quickly
+// oceanic lithosphere densifies as it ages (kg/m³ per Myr).
+const COLLISION_AREA_TRANSFER_KM2_PER_CM: f32 = 1_000.0;
+const OROGENY_BONUS_CM_PER_YEAR: f32 = 50.0;
+const OCEANIC_DENSIFICATION_PER_MYR: f32 = 1.0;
+// Back-arc basin area opened per step for each cm/yr of extension behind the arc.
+const BACK_ARC_AREA_KM2_PER_CM: f32 = 1_000.0;
+
+// Subducted slabs enter the lower mantle budget at the 410 km transition, sink through the
+// upper mantle at a fixed rate, and slow in the lower mantle in proportion to its viscosity.
+// At the 660 km discontinuity a slab only breaks through once its volume outweighs the
+// viscous resistance; smaller ones pond there and merge until the pile is heavy enough.
+const SLAB_ENTRY_DEPTH_KM: f32 = 410.0;
+const SLAB_ENTRY_TEMPERATURE_C: f32 = 600.0;
+pub const UPPER_LOWER_MANTLE_BOUNDARY_KM: f32 = 660.0;
+const SLAB_SINKING_RATE_CM_PER_YEAR: f32 = 5.0;
+const REFERENCE_LOWER_MANTLE_VISCOSITY: f32 = 2e22;
+const SLAB_PENETRATION_VOLUME_KM3: f32 = 1_000_000.0;
+const SLAB_THERMAL_EQUILIBRATION_YEARS: f32 = 100_000_000.0;
+// Plume generation potential gained per km³ of slab settling onto the D'' layer.
+const PLUME_POTENTIAL_PER_SLAB_KM3: f32 = 1e-8;
+// Transition-zone water storage a slab squeezes out per km³ on its way through to the base.
+const TRANSITION_ZONE_DEHYDRATION_PER_SLAB_KM3: f32 = 1e-8;
+// The ringwoodite breakdown at 660 km is endothermic, so its negative Clapeyron slope
+// (MPa/K) deflects the boundary against anything crossing it. At the layering slope the
+// deflection holds slabs and plumes back altogether and the mantle convects in two layers;
+// Earth's gentler slope only holds back part of the traffic.
+pub const EARTH_CLAPEYRON_SLOPE_MPA_PER_K: f32 = -2.0;
+pub const LAYERING_CLAPEYRON_SLOPE_MPA_PER_K: f32 = -4.0;
+
+// Arrhenius activation energies (J/mol) for creep in the olivine-dominated upper mantle and
+// the bridgmanite-dominated lower mantle and D''. Deep convection vigour scales with the
+// Rayleigh number, and so with inverse viscosity, to this power.
+const UPPER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL: f32 = 300_000.0;
+const LOWER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL: f32 = 250_000.0;
+const CONVECTION_RAYLEIGH_EXPONENT: f32 = 1.0 / 3.0;
+
+// Water weakening: each unit of transition-zone storage released adds this much volatile
+// content to the asthenosphere. Every extra percent above the reference content makes it
+// ten times less viscous and raises lubrication, up to full coupling. Plates ride the
+// mantle flow in proportion to lubrication, relative to the reference state.
+const VOLATILE_PCT_PER_WATER_STORAGE: f32 = 1.0;
+const REFERENCE_VOLATILE_CONTENT_PCT: f32 = 0.5;
+const REFERENCE_ASTHENOSPHERE_VISCOSITY: f32 = 1e19;
+const VISCOSITY_ORDERS_PER_VOLATILE_PCT: f32 = 1.0;
+const REFERENCE_LUBRICATION_FACTOR: f32 = 0.8;
+const LUBRICATION_PER_VOLATILE_PCT: f32 = 0.2;
+const MAX_LUBRICATION_FACTOR: f32 = 1.0;
+
+// Slab pull: a sinking slab drags its plate along at this speed per km³ subducted per kg/m³
+// the slab is denser than NEUTRAL_SLAB_DENSITY, up to a ceiling. Plates without a slab are
+// only partly coupled to the mantle flow beneath them.
+const SLAB_PULL_CM_PER_YEAR_PER_KM3_KG_M3: f32 = 3e-8;
+const NEUTRAL_SLAB_DENSITY: f32 = 2_800.0;
+const MAX_SLAB_PULL_CM_PER_YEAR: f32 = 15.0;
+const SLABLESS_FLOW_COUPLING: f32 = 0.6;
+// Force balance, in TN (1e12 N) per metre of plate boundary. Ridge push grows with how far
+// the plate has subsided below the ridge crest, 2-3 TN/m for mature ocean floor. Slab pull
+// is the force that alone would drive the plate at its slab-pull speed, and basal drag the
+// traction of the asthenosphere flowing beneath. A plate moves at the net force over the
+// drag resisting it.
+const RIDGE_PUSH_TN_PER_M_PER_KM: f32 = 1.0;
+const BASAL_DRAG_TN_PER_M_PER_CM: f32 = 1.0;
+const PLATE_DRAG_TN_PER_M_PER_CM: f32 = 1.0;
+
+// Magma fed into a plate's chambers per year at full volcanic activity, and the smallest
+// share of the stored magma a single eruption taps.
+const MAGMA_RECHARGE_KM3_PER_YEAR: f32 = 1e-4;
+const MIN_ERUPTED_FRACTION: f32 = 0.05;
+
+// GDH1 seafloor depth (km) against age (Stein & Stein, 1992). Half-space cooling deepens
+// the floor below the ridge crest as the square root of age; past the flattening age the
+// plate has cooled to a steady thickness and the floor levels off toward the asymptote,
+// meeting the square-root curve there.
+const RIDGE_CREST_DEPTH_KM: f32 = 2.6;
+const SUBSIDENCE_KM_PER_SQRT_MYR: f32 = 0.365;
+const SEAFLOOR_FLATTENING_AGE_MYR: f32 = 20.0;
+const ASYMPTOTIC_SEAFLOOR_DEPTH_KM: f32 = 5.651;
+const SEAFLOOR_FLATTENING_DEFICIT_KM: f32 = 2.473;
+const SEAFLOOR_FLATTENING_RATE_PER_MYR: f32 = 0.0278;
+
+// Plates shrunk below this area by subduction are considered fully consumed.
+pub const MIN_PLATE_AREA_KM2: f32 = 1_000.0;
+
+// A plate breaks apart once its accumulated stress or back-arc extension passes these.
+pub const RIFT_STRESS_THRESHOLD_MPA: f32 = 200.0;
+pub const RIFT_BACK_ARC_THRESHOLD_CM_PER_YEAR: f32 = 5.0;
+
+// Tidal heating: melt the asthenosphere holds without it, the most extra melt heating can
+// sustain, the heating per kilogram at which it reaches ~63% of that, and how quickly the
+// melt fraction adjusts.
+const ASTHENOSPHERE_BASE_MELT_PCT: f32 = 2.0;
+const MAX_TIDAL_MELT_PCT: f32 = 30.0;
+const TIDAL_MELT_SCALE_W_PER_KG: f64 = 1e-9;
+const TIDAL_MELT_RESPONSE_YEARS: f32 = 1_000_000.0;
+// Moving plates carry heat out of the asthenosphere they ride on. Every km² moving at 1 cm/yr
+// draws off this much, so about 3e8 km² of ocean floor at 5 cm/yr removes the ~30 TW lost
+// through the sea floor. Boundary-layer theory puts convective speed at Ra^(2/3), so at
+// viscosity^(-2/3): cooled, stiffer rock flows and drags the plates more slowly, and less heat
+// is drawn off, holding the layer near a steady state. The speed-up of hotter rock is capped.
+const PLATE_DRAG_COOLING_TW_PER_KM2_CM_PER_YEAR: f64 = 2e-8;
+const CONVECTIVE_VIGOUR_EXPONENT: f32 = 2.0 / 3.0;
+const MAX_CONVECTIVE_VIGOUR: f32 = 3.0;
+// Melt without tidal heating: peridotite past its solidus melts by so much per degree, and
+// mantle welling up beneath a ridge by so much per GPa it decompresses, with spreading
+// drawing it up faster the faster plates part. The solidus sits just below the reference
+// temperature, so the layer starts at the base melt fraction.
+const ASTHENOSPHERE_SOLIDUS_C: f32 = 1280.0;
+const MELT_PCT_PER_C_ABOVE_SOLIDUS: f32 = 0.1;
+const DECOMPRESSION_MELT_PCT_PER_GPA: f32 = 10.0;
+const RIDGE_DECOMPRESSION_GPA_PER_CM_PER_YEAR: f32 = 0.1;
+// Magma a volcanically active plate draws from the asthenosphere each step at the base
+// melt fraction, more or less in proportion as the melt fraction differs.
+const VOLCANIC_GROWTH_KM3: f32 = 500.0;
+
+// Present-day radiogenic heat production of mantle rock (W/kg), the bulk silicate Earth value.
+const MANTLE_RADIOGENIC_HEAT_W_PER_KG: f64 = 7.4e-12;
+
+// Share of all continental area one sutured cluster must hold to count as a supercontinent.
+pub const SUPERCONTINENT_AREA_FRACTION: f32 = 0.75;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MantleEvent {
+    SubductionStarted { plate_id: u32 },
+    TransformSlip { plate_id: u32, released_mpa: f32, magnitude: f32 },
+    Spreading { plate_id: u32, area_km2: f32 },
+    BackArcSpreading { plate_id: u32, area_km2: f32 },
+    Subduction { plate_id: u32, volume_km3: f32 },
+    VolcanicGrowth { plate_id: u32, km: f32, volume_km3: f32 },
+    TectonicDeformation { plate_id: u32, km: f32 },
+    CrustErosion { plate_id: u32, km: f32 },
+    PlumeSpawned { hot_spot_index: usize },
+    LargeIgneousProvince { plate_id: u32, volume_km3: f32, km: f32 },
+    PlateConsumed { plate_id: u32, cause: ConsumptionCause },
+    Collision { plate_id: u32, other_plate_id: u32, area_km2: f32 },
+    Orogeny { plate_id: u32, other_plate_id: u32, km: f32 },
+    VolcanicEruption { plate_id: u32, vei: u8, volume_km3: f32 },
+    SedimentRouted { plate_id: u32, other_plate_id: u32, km: f32 },
+    LithosphereQuake { magnitude: f32 },
+    ContinentalBreakup { plate_id: u32 },
+}
+
+impl MantleEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MantleEvent::SubductionStarted { .. } => "subduction_started",
+            MantleEvent::TransformSlip { .. } => "transform_slip",
+            MantleEvent::Spreading { .. } => "spreading",
+            MantleEvent::BackArcSpreading { .. } => "back_arc_spreading",
+            MantleEvent::Subduction { .. } => "subduction",
+            MantleEvent::VolcanicGrowth { .. } => "volcanic_growth",
+            MantleEvent::TectonicDeformation { .. } => "tectonic_deformation",
+            MantleEvent::CrustErosion { .. } => "crust_erosion",
+            MantleEvent::PlumeSpawned { .. } => "plume_spawned",
+            MantleEvent::LargeIgneousProvince { .. } => "large_igneous_province",
+            MantleEvent::PlateConsumed { .. } => "plate_consumed",
+            MantleEvent::Collision { .. } => "collision",
+            MantleEvent::Orogeny { .. } => "orogeny",
+            MantleEvent::VolcanicEruption { .. } => "volcanic_eruption",
+            MantleEvent::SedimentRouted { .. } => "sediment_routed",
+            MantleEvent::LithosphereQuake { .. } => "lithosphere_quake",
+            MantleEvent::ContinentalBreakup { .. } => "continental_breakup",
+        }
+    }
+}
+
+// What used a plate up: its own slab sinking, or a neighbour overriding it in a collision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConsumptionCause {
+    Subduction,
+    Collision,
+}
+
+impl ConsumptionCause {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsumptionCause::Subduction => "subduction",
+            ConsumptionCause::Collision => "collision",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum WilsonStage {
+    Rifting,
+    Spreading,
+    Subducting,
+    Collision,
+    Suturing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Plate {
+    pub id: u32,
+    pub neighbor_ids: Vec<u32>,
+    pub center_lat_deg: f32,
+    pub center_lon_deg: f32,
+    pub crust: Crust,
     pub area_km2: f32,
     pub age_myr: f32,
+    // The plate turns rigidly about its Euler pole, which is the source of truth for its
+    // motion. The speed and direction are that rotation's velocity at the plate centre, kept
+    // in step with the pole by `set_speed` and `set_motion`.
+    pub euler_pole: EulerPole,
     pub velocity_cm_per_year: f32,
     pub motion_direction_deg: f32,
     pub is_subducting: bool,
     pub is_transform_boundary: bool,
     pub shear_stress_mpa: f32,
+    pub slab_pull_cm_per_year: f32,
     pub has_back_arc_spreading: bool,
     pub back_arc_spreading_rate_cm_per_year: f32,
     pub volcanic_activity_factor: f32,
+    pub magma_chamber_km3: f32,
+    pub wilson_stage: WilsonStage,
+    pub stage_age_myr: f32,
+    // Crust added at the plate's own rift, kept apart so spreading doesn't make the older
+    // plate young again: its area and area-weighted mean age.
+    pub new_crust_area_km2: f32,
+    pub new_crust_age_myr: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundaryType {
+    Divergent,
+    Convergent,
+    Transform,
+}
+
+// One boundary between neighbouring plates, typed by how they move relative to each other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundaryClass {
+    pub plate_id: u32,
+    pub other_plate_id: u32,
+    pub kind: BoundaryType,
+    pub length_km: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Eruption {
+    pub plate_id: u32,
+    pub vei: u8,
+    pub volume_km3: f32,
+}
+
+impl Eruption {
+    // Volcanic Explosivity Index from erupted volume: VEI 2 starts at 0.001 km³ and each step
+    // above is ten times larger, up to VEI 8 at 1000 km³ and beyond.
+    pub fn vei_for_volume(volume_km3: f32) -> u8 {
+        if volume_km3 < 1e-5 {
+            0
+        } else if volume_km3 < 1e-3 {
+            1
+        } else {
+            (volume_km3.log10().floor() + 5.0).clamp(2.0, 8.0) as u8
+        }
+    }
 }
 
 impl Plate {
-    pub fn update_motion(&mut self, mantle_flow_rate: f32, mantle_direction: f32) -> (bool, f32, f32, bool) {
-        self.velocity_cm_per_year = mantle_flow_rate + (self.velocity_cm_per_year * 0.1);
-        self.motion_direction_deg = (self.motion_direction_deg + mantle_direction * 0.05) % 360.0;
-        self.age_myr += 0.1;
+    // Moves the plate under the net driving force (east, north) found by driving_force. The
+    // force sets how fast the plate turns about its Euler pole; only a plate at rest takes
+    // its pole from the force's direction.
+    pub fn update_motion(&mut self, driving_force_tn_per_m: (f32, f32), years: f32) -> (bool, f32, f32, Option<f32>) {
+        let (east, north) = driving_force_tn_per_m;
+        let speed = east.hypot(north) / PLATE_DRAG_TN_PER_M_PER_CM;
+        self.set_speed(speed, north.atan2(east).to_degrees().rem_euclid(360.0));
 
-        let mut started_subduction = false;
-        if self.age_myr > 100.0 && !self.is_subducting {
-            self.is_subducting = true;
-            started_subduction = true;
+        // Turning about a pole 90° away carries the centre along a great circle, whose
+        // heading swings round as it goes.
+        let distance_km = cm_per_year_to_km_per_year(self.velocity_cm_per_year) * years;
+        (self.center_lat_deg, self.center_lon_deg) =
+            destination(self.center_lat_deg, self.center_lon_deg, self.motion_direction_deg, distance_km);
+        if self.velocity_cm_per_year > 0.0 {
+            (self.velocity_cm_per_year, self.motion_direction_deg) = self.velocity_at(self.center_lat_deg, self.center_lon_deg);
+        }
+        self.age_myr += years_to_myr(years);
+        self.stage_age_myr += years_to_myr(years);
+        if self.new_crust_area_km2 > 0.0 {
+            self.new_crust_age_myr += years_to_myr(years);
         }
 
+        let started_subduction = self.advance_wilson_cycle() == Some(WilsonStage::Subducting);
+
         let mut spread_area = 0.0;
-        if self.age_myr < 20.0 {
-            spread_area = self.spread(10_000.0);
+        if self.wilson_stage == WilsonStage::Rifting {
+            // A continent rifts by stretching its own crust over the widening area.
+            let area_before_km2 = self.area_km2;
+            spread_area = self.spread(years);
+            if self.crust.kind == CrustType::Continental {
+                self.crust.stretch(area_before_km2 / self.area_km2);
+            }
         }
 
-        let earthquake_occurred = self.simulate_transform();
+        let released_stress = self.release_transform_stress(years);
         let back_arc_area = self.simulate_back_arc_spreading();
 
-        (started_subduction, spread_area, back_arc_area, earthquake_occurred)
+        (started_subduction, spread_area, back_arc_area, released_stress)
+    }
+
+    // Moves the plate to its next Wilson cycle stage once the current one has run its
+    // course, returning the stage entered. A subduction flag raised from outside (e.g. by
+    // a collision) ends the spreading stage early.
+    pub fn advance_wilson_cycle(&mut self) -> Option<WilsonStage> {
+        let next = match self.wilson_stage {
+            WilsonStage::Rifting if self.stage_age_myr >= RIFTING_DURATION_MYR => WilsonStage::Spreading,
+            WilsonStage::Spreading if self.is_subducting || self.age_myr > SUBDUCTION_ONSET_AGE_MYR => WilsonStage::Subducting,
+            WilsonStage::Subducting if self.stage_age_myr >= SUBDUCTION_DURATION_MYR => WilsonStage::Collision,
+            WilsonStage::Collision if self.stage_age_myr >= COLLISION_DURATION_MYR => WilsonStage::Suturing,
+            WilsonStage::Suturing if self.stage_age_myr >= SUTURING_DURATION_MYR => WilsonStage::Rifting,
+            _ => return None,
+        };
+
+        self.wilson_stage = next;
+        self.stage_age_myr = 0.0;
+        match next {
+            WilsonStage::Subducting => self.is_subducting = true,
+            WilsonStage::Collision => self.is_subducting = false,
+            WilsonStage::Rifting => self.age_myr = 0.0,
+            WilsonStage::Spreading | WilsonStage::Suturing => {}
+        }
+        Some(next)
     }
 
-    pub fn subduct(&mut self, mantle: &mut LowerMantle) -> f32 {
-        if self.is_subducting {
-            let recycled_volume = self.area_km2 * 0.01;
+    // Returns the recycled volume and whether the plate has been used up. Once another step
+    // would leave less than MIN_PLATE_AREA_KM2, the whole remnant sinks so no area goes missing.
+    pub fn subduct(&mut self, mantle: &mut LowerMantle, volatiles: &mut VolatileBudget, deep_water_capacity_gt: f64) -> (f32, bool) {
+        if self.wilson_stage == WilsonStage::Subducting {
+            let exhausted = self.area_km2 * 0.99 < MIN_PLATE_AREA_KM2;
+            let recycled_volume = if exhausted { self.area_km2 } else { self.area_km2 * 0.01 };
             mantle.mix_composition(recycled_volume);
-            self.area_km2 *= 0.99;
-            recycled_volume
+            mantle.slabs.push(Slab {
+                depth_km: SLAB_ENTRY_DEPTH_KM,
+                volume_km3: recycled_volume,
+                temperature_c: SLAB_ENTRY_TEMPERATURE_C,
+            });
+            volatiles.subduct(recycled_volume, deep_water_capacity_gt);
+            self.area_km2 -= recycled_volume;
+            let density_contrast = (self.density_proxy() - NEUTRAL_SLAB_DENSITY).max(0.0);
+            self.slab_pull_cm_per_year =
+                (SLAB_PULL_CM_PER_YEAR_PER_KM3_KG_M3 * recycled_volume * density_contrast).min(MAX_SLAB_PULL_CM_PER_YEAR);
+            (recycled_volume, exhausted)
         } else {
-            0.0
+            self.slab_pull_cm_per_year = 0.0;
+            (0.0, false)
+        }
+    }
+
+    // Opens new crust along a rift running across the plate, as long as the plate is wide,
+    // at the plate's own speed. Returns the area added.
+    pub fn spread(&mut self, years: f32) -> f32 {
+        let rift_length_km = self.area_km2.sqrt();
+        let growth_km2 = cm_per_year_to_km_per_year(self.velocity_cm_per_year) * rift_length_km * years;
+        if growth_km2 <= 0.0 {
+            return 0.0;
+        }
+        // What opened during the step is on average half the step old.
+        let added_age_myr = years_to_myr(years) / 2.0;
+        let new_area_km2 = self.new_crust_area_km2 + growth_km2;
+        self.new_crust_age_myr = (self.new_crust_age_myr * self.new_crust_area_km2 + added_age_myr * growth_km2) / new_area_km2;
+        self.new_crust_area_km2 = new_area_km2;
+        self.area_km2 += growth_km2;
+        growth_km2
+    }
+
+    // Distance of a point from the plate's centre, in radii of a circular plate of the same
+    // area: below 1 lies inside its footprint, near 1 along its margin.
+    pub fn footprint_depth(&self, lat_deg: f32, lon_deg: f32) -> f32 {
+        great_circle_km(self.center_lat_deg, self.center_lon_deg, lat_deg, lon_deg) / (self.area_km2 / std::f32::consts::PI).sqrt().max(1.0)
+    }
+
+    pub fn simulate_transform(&mut self, years: f32) -> Option<f32> {
+        self.release_transform_stress(years).map(|released_mpa| self.slip_magnitude(released_mpa))
+    }
+
+    // Velocity of the plate's surface at a point, from its rotation about the Euler pole.
+    pub fn velocity_at(&self, lat_deg: f32, lon_deg: f32) -> (f32, f32) {
+        self.euler_pole.velocity_at(lat_deg, lon_deg)
+    }
+
+    // Scales the rotation about the current pole to the given speed at the plate centre,
+    // keeping the pole where it is. With no motion to scale (at rest, or centred on the pole)
+    // the pole is found from `direction_deg` instead.
+    pub fn set_speed(&mut self, speed_cm_per_year: f32, direction_deg: f32) {
+        let (current_cm_per_year, _) = self.velocity_at(self.center_lat_deg, self.center_lon_deg);
+        if current_cm_per_year > 0.0 && self.euler_pole.rate_deg_per_myr != 0.0 {
+            self.euler_pole.rate_deg_per_myr *= speed_cm_per_year / current_cm_per_year;
+            (self.velocity_cm_per_year, self.motion_direction_deg) = self.velocity_at(self.center_lat_deg, self.center_lon_deg);
+        } else {
+            self.set_motion(speed_cm_per_year, direction_deg);
         }
     }
 
-    pub fn spread(&mut self, growth_rate_km2: f32) -> f32 {
-        self.area_km2 += growth_rate_km2;
-        self.age_myr = 0.0;
-        growth_rate_km2
+    // Sets the velocity at the plate centre by turning the plate about the pole that gives it.
+    pub fn set_motion(&mut self, speed_cm_per_year: f32, direction_deg: f32) {
+        self.euler_pole = EulerPole::from_motion(self.center_lat_deg, self.center_lon_deg, speed_cm_per_year, direction_deg);
+        self.velocity_cm_per_year = speed_cm_per_year;
+        self.motion_direction_deg = direction_deg.rem_euclid(360.0);
     }
 
-    pub fn simulate_transform(&mut self) -> bool {
+    // Shrunk too small to count as a plate; it is removed at the end of the step.
+    pub fn is_consumed(&self) -> bool {
+        self.area_km2 < MIN_PLATE_AREA_KM2
+    }
+
+    pub fn crust_volume_km3(&self) -> f64 {
+        self.area_km2 as f64 * self.crust.thickness_km as f64
+    }
+
+    // Checks the plate's own fields and then its crust, reporting both together.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let plate = Validator::new()
+            .positive("area_km2", self.area_km2)
+            .non_negative("age_myr", self.age_myr)
+            .within("center_lat_deg", self.center_lat_deg, -90.0, 90.0)
+            .non_negative("velocity_cm_per_year", self.velocity_cm_per_year)
+            .within("volcanic_activity_factor", self.volcanic_activity_factor, 0.0, 1.0)
+            .non_negative("magma_chamber_km3", self.magma_chamber_km3)
+            .finish();
+        let mut errors: Vec<ValidationError> = plate.err().unwrap_or_default();
+        errors.extend(self.crust.validate().err().unwrap_or_default());
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Gravitational sliding off the ridge: ocean floor that has cooled and subsided further
+    // below the crest pushes harder. Continents carry no ridge of their own.
+    pub fn ridge_push_tn_per_m(&self) -> f32 {
+        if self.crust.kind != CrustType::Oceanic {
+            return 0.0;
+        }
+        RIDGE_PUSH_TN_PER_M_PER_KM * (self.seafloor_depth_km() - RIDGE_CREST_DEPTH_KM).max(0.0)
+    }
+
+    pub fn slab_pull_tn_per_m(&self) -> f32 {
+        self.slab_pull_cm_per_year * PLATE_DRAG_TN_PER_M_PER_CM
+    }
+
+    // Traction of the asthenosphere flowing beneath the plate's centre as (east, north), the
+    // stronger the better lubricated it is. Plates without a slab are only partly coupled.
+    pub fn basal_drag_tn_per_m(&self, mantle: &Mantle) -> (f32, f32) {
+        let (flow_rate, flow_direction) = mantle.flow_at(self.center_lat_deg, self.center_lon_deg);
+        let lubrication = mantle.asthenosphere.lubrication_factor / REFERENCE_LUBRICATION_FACTOR;
+        let coupling = if self.slab_pull_cm_per_year > 0.0 { 1.0 } else { SLABLESS_FLOW_COUPLING };
+        let traction = BASAL_DRAG_TN_PER_M_PER_CM * flow_rate * lubrication * coupling;
+        let (sin, cos) = flow_direction.to_radians().sin_cos();
+        (traction * cos, traction * sin)
+    }
+
+    // Ridge push and slab pull act along the plate's heading and basal drag along the flow
+    // beneath; their sum as (east, north).
+    pub fn driving_force(&self, mantle: &Mantle) -> (f32, f32) {
+        let edge = self.ridge_push_tn_per_m() + self.slab_pull_tn_per_m();
+        let (sin, cos) = self.motion_direction_deg.to_radians().sin_cos();
+        let (drag_east, drag_north) = self.basal_drag_tn_per_m(mantle);
+        (edge * cos + drag_east, edge * sin + drag_north)
+    }
+
+    // Size of the net driving force.
+    pub fn compute_driving_force(&self, mantle: &Mantle) -> f32 {
+        let (east, north) = self.driving_force(mantle);
+        east.hypot(north)
+    }
+
+    // Depth of the seafloor below sea level for the plate's age. Continental plates carry no
+    // seafloor, so they sit at zero.
+    pub fn seafloor_depth_km(&self) -> f32 {
+        if self.crust.kind != CrustType::Oceanic {
+            return 0.0;
+        }
+        let age_myr = self.age_myr.max(0.0);
+        if age_myr < SEAFLOOR_FLATTENING_AGE_MYR {
+            RIDGE_CREST_DEPTH_KM + SUBSIDENCE_KM_PER_SQRT_MYR * age_myr.sqrt()
+        } else {
+            ASYMPTOTIC_SEAFLOOR_DEPTH_KM - SEAFLOOR_FLATTENING_DEFICIT_KM * (-SEAFLOOR_FLATTENING_RATE_PER_MYR * age_myr).exp()
+        }
+    }
+
+    // Moment magnitude of a rupture releasing the given stress along a boundary whose length
+    // grows with plate size.
+    pub fn slip_magnitude(&self, released_mpa: f32) -> f32 {
+        let rupture_length_km = (self.area_km2.sqrt() * 0.1).min(MAX_RUPTURE_LENGTH_KM);
+        moment_magnitude(released_mpa, rupture_length_km)
+    }
+
+    fn release_transform_stress(&mut self, years: f32) -> Option<f32> {
         if self.is_transform_boundary {
-            self.shear_stress_mpa += 5.0;
-            if self.shear_stress_mpa > 300.0 {
+            self.shear_stress_mpa += TRANSFORM_STRESS_RATE_MPA_PER_YEAR * years;
+            if self.shear_stress_mpa > TRANSFORM_SLIP_THRESHOLD_MPA {
+                let released = self.shear_stress_mpa;
                 self.shear_stress_mpa = 0.0;
-                true
+                Some(released)
             } else {
-                false
+                None
             }
         } else {
-            false
+            None
+        }
+    }
+
+    // Relative buoyancy used to decide which plate sinks at a convergent boundary: oceanic
+    // lithosphere starts denser than continental and keeps getting denser as it ages.
+    pub fn density_proxy(&self) -> f32 {
+        match self.crust.kind {
+            CrustType::Continental => self.crust.average_density,
+            CrustType::Oceanic => self.crust.average_density + self.age_myr * OCEANIC_DENSIFICATION_PER_MYR,
+        }
+    }
+
+    // Component of this plate's velocity directed at the other plate's center.
+    pub fn velocity_toward(&self, other: &Plate) -> f32 {
+        let bearing = bearing_deg(self.center_lat_deg, self.center_lon_deg, other.center_lat_deg, other.center_lon_deg);
+        self.velocity_cm_per_year * (self.motion_direction_deg - bearing).to_radians().cos()
+    }
+
+    // Erupts with a chance equal to the volcanic activity factor, emptying part of the magma
+    // chamber. More active plates tap a larger share, so they produce bigger eruptions.
+    pub fn maybe_erupt(&mut self, rng: &mut impl Rng) -> Option<Eruption> {
+        let activity = self.volcanic_activity_factor.clamp(0.0, 1.0);
+        if self.magma_chamber_km3 <= 0.0 || rng.gen::<f32>() >= activity {
+            return None;
         }
+
+        let volume_km3 = self.magma_chamber_km3 * activity * rng.gen_range(MIN_ERUPTED_FRACTION..=1.0);
+        self.magma_chamber_km3 -= volume_km3;
+        let vei = Eruption::vei_for_volume(volume_km3);
+        self.crust.inject_ash(vei);
+        Some(Eruption { plate_id: self.id, vei, volume_km3 })
     }
 
     pub fn simulate_back_arc_spreading(&mut self) -> f32 {
         if self.has_back_arc_spreading {
-            let added_area = self.back_arc_spreading_rate_cm_per_year * 1000.0;
+            let added_area = self.back_arc_spreading_rate_cm_per_year * BACK_ARC_AREA_KM2_PER_CM;
             self.area_km2 += added_area;
             added_area
         } else {
@@ -78,84 +631,526 @@ impl Plate {
     }
 }
 
+// Starts from an oceanic, spreading plate at rest at the origin with no transform or
+// back-arc activity; override only what matters and finish with `build()`. Motion is given
+// either as a velocity and direction at the centre or as an Euler pole, whichever was set.
+pub struct PlateBuilder {
+    plate: Plate,
+    euler_pole: Option<EulerPole>,
+}
+
+impl PlateBuilder {
+    pub fn new() -> Self {
+        Self {
+            plate: Plate {
+                id: 0,
+                neighbor_ids: Vec::new(),
+                center_lat_deg: 0.0,
+                center_lon_deg: 0.0,
+                crust: Crust::new(CrustType::Oceanic),
+                area_km2: 10_000_000.0,
+                age_myr: 0.0,
+                euler_pole: EulerPole::from_motion(0.0, 0.0, 0.0, 0.0),
+                velocity_cm_per_year: 0.0,
+                motion_direction_deg: 0.0,
+                is_subducting: false,
+                is_transform_boundary: false,
+                shear_stress_mpa: 0.0,
+                slab_pull_cm_per_year: 0.0,
+                has_back_arc_spreading: false,
+                back_arc_spreading_rate_cm_per_year: 0.0,
+                volcanic_activity_factor: 0.0,
+                magma_chamber_km3: 0.0,
+                wilson_stage: WilsonStage::Spreading,
+                stage_age_myr: 0.0,
+                new_crust_area_km2: 0.0,
+                new_crust_age_myr: 0.0,
+            },
+            euler_pole: None,
+        }
+    }
+
+    pub fn center(mut self, lat_deg: f32, lon_deg: f32) -> Self {
+        self.plate.center_lat_deg = lat_deg;
+        self.plate.center_lon_deg = lon_deg;
+        self
+    }
+
+    pub fn crust(mut self, kind: CrustType) -> Self {
+        self.plate.crust = Crust::new(kind);
+        self
+    }
+
+    pub fn area_km2(mut self, area_km2: f32) -> Self {
+        self.plate.area_km2 = area_km2;
+        self
+    }
+
+    pub fn age_myr(mut self, age_myr: f32) -> Self {
+        self.plate.age_myr = age_myr;
+        self
+    }
+
+    pub fn velocity(mut self, cm_per_year: f32) -> Self {
+        self.plate.velocity_cm_per_year = cm_per_year;
+        self.euler_pole = None;
+        self
+    }
+
+    pub fn direction_deg(mut self, direction_deg: f32) -> Self {
+        self.plate.motion_direction_deg = direction_deg;
+        self.euler_pole = None;
+        self
+    }
+
+    pub fn euler_pole(mut self, lat_deg: f32, lon_deg: f32, rate_deg_per_myr: f32) -> Self {
+        self.euler_pole = Some(EulerPole { lat_deg, lon_deg, rate_deg_per_myr });
+        self
+    }
+
+    pub fn transform_boundary(mut self, is_transform_boundary: bool) -> Self {
+        self.plate.is_transform_boundary = is_transform_boundary;
+        self
+    }
+
+    pub fn back_arc_spreading(mut self, rate_cm_per_year: f32) -> Self {
+        self.plate.has_back_arc_spreading = true;
+        self.plate.back_arc_spreading_rate_cm_per_year = rate_cm_per_year;
+        self
+    }
+
+    pub fn volcanic_activity(mut self, factor: f32) -> Self {
+        self.plate.volcanic_activity_factor = factor;
+        self
+    }
+
+    pub fn wilson_stage(mut self, stage: WilsonStage) -> Self {
+        self.plate.wilson_stage = stage;
+        self
+    }
+
+    pub fn build(mut self) -> Plate {
+        let plate = &mut self.plate;
+        match self.euler_pole {
+            Some(pole) => {
+                plate.euler_pole = pole;
+                (plate.velocity_cm_per_year, plate.motion_direction_deg) = pole.velocity_at(plate.center_lat_deg, plate.center_lon_deg);
+            }
+            None => plate.set_motion(plate.velocity_cm_per_year, plate.motion_direction_deg),
+        }
+        self.plate
+    }
+
+    // As `build`, but refuses a plate that fails validation, such as one with no area.
+    pub fn try_build(self) -> Result<Plate, WorldSimError> {
+        let plate = self.build();
+        plate.validate()?;
+        Ok(plate)
+    }
+}
+
+impl Default for PlateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// A plume on its way up from the D'' layer. Its broad head erupts as a flood basalt when it
+// reaches the lithosphere; the thin tail left behind feeds a long-lived hot spot.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plume {
+    pub lat_deg: f32,
+    pub lon_deg: f32,
+    pub origin_depth_km: f32,
+    pub depth_km: f32,
+    pub buoyancy: f32,
+    pub head_volume_km3: f32,
+}
+
+impl Plume {
+    // Rises through the mantle; returns whether it has reached the base of the lithosphere.
+    // Crossing the 660 km boundary costs it the blocked share of its buoyancy and head, and one
+    // left weaker than the floor stalls on the boundary for good.
+    pub fn ascend(&mut self, years: f32, lithosphere_base_km: f32, barrier: &TransitionZone) -> bool {
+        let rise_km = cm_per_year_to_km_per_year(PLUME_ASCENT_CM_PER_YEAR * self.buoyancy) * years;
+        let below_boundary = self.depth_km > barrier.lower_boundary_km;
+        self.depth_km = (self.depth_km - rise_km).max(lithosphere_base_km);
+
+        if below_boundary && self.depth_km <= barrier.lower_boundary_km {
+            let passing = 1.0 - barrier.barrier_strength();
+            self.buoyancy *= passing;
+            self.head_volume_km3 *= passing;
+            if self.is_stalled() {
+                self.depth_km = barrier.lower_boundary_km;
+                return false;
+            }
+        }
+        self.depth_km <= lithosphere_base_km
+    }
+
+    pub fn is_stalled(&self) -> bool {
+        self.buoyancy < MIN_PLUME_BUOYANCY
+    }
+}
+
+// A large low-shear-velocity province: a dense, chemically distinct pile on the core-mantle
+// boundary, given by the point above its centre and its radius measured at the surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Llsvp {
+    pub lat_deg: f32,
+    pub lon_deg: f32,
+    pub radius_km: f32,
+}
+
+impl Llsvp {
+    // How far a point lies from the province's rim, inside or out.
+    pub fn edge_distance_km(&self, lat_deg: f32, lon_deg: f32) -> f32 {
+        (great_circle_km(self.lat_deg, self.lon_deg, lat_deg, lon_deg) - self.radius_km).abs()
+    }
+
+    // Share of the sphere the province covers, as a spherical cap.
+    pub fn coverage(&self) -> f32 {
+        let angular = (self.radius_km / EARTH_RADIUS_KM).min(std::f32::consts::PI);
+        (1.0 - angular.cos()) / 2.0
+    }
+
+    // Relative chance of a plume rising at a point: highest on the rim, where the pile's
+    // edge deflects the hot boundary layer upward.
+    fn plume_weight(&self, lat_deg: f32, lon_deg: f32) -> f32 {
+        let edge = self.edge_distance_km(lat_deg, lon_deg) / LLSVP_EDGE_WIDTH_KM;
+        (-edge * edge).exp()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeatureKind {
+    MountainRange,
+    Rift,
+    Ocean,
+}
+
+// A named landmark and when it formed, for asking what happened at a place.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Feature {
+    pub name: String,
+    pub lat_deg: f32,
+    pub lon_deg: f32,
+    pub kind: FeatureKind,
+    // Simulation time the feature formed at.
+    pub birth_myr: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HotSpot {
     pub lat_deg: f32,
     pub lon_deg: f32,
     pub surface_age_myr: f32,
+    // Earlier positions as (lat, lon, age in Myr), oldest first; the island chain left behind.
+    pub track: Vec<(f32, f32, f32)>,
 }
 
 impl HotSpot {
-    pub fn migrate(&mut self, mantle_flow_rate: f32, flow_direction_deg: f32) {
-        let rad = flow_direction_deg.to_radians();
-        self.lat_deg += (mantle_flow_rate / 10.0) * rad.sin();
-        self.lon_deg += (mantle_flow_rate / 10.0) * rad.cos();
+    pub fn migrate(&mut self, mantle_flow_rate: f32, flow_direction_deg: f32, years: f32) {
+        self.track.push((self.lat_deg, self.lon_deg, self.surface_age_myr));
 
-        if self.lat_deg > 90.0 { self.lat_deg = 90.0; }
-        if self.lat_deg < -90.0 { self.lat_deg = -90.0; }
-        if self.lon_deg > 180.0 { self.lon_deg -= 360.0; }
-        if self.lon_deg < -180.0 { self.lon_deg += 360.0; }
+        // Drifting along a great circle carries a hot spot over a pole and down the far side,
+        // half way round in longitude, where stepping lat and lon apart would stick at 90°.
+        let distance_km = (mantle_flow_rate * HOT_SPOT_DRIFT_DEG_PER_FLOW).to_radians() * EARTH_RADIUS_KM;
+        (self.lat_deg, self.lon_deg) = destination(self.lat_deg, self.lon_deg, flow_direction_deg, distance_km);
 
-        self.surface_age_myr += 0.1;
+        self.surface_age_myr += years_to_myr(years);
     }
+
+    // Length of the trail from its oldest point up to the current position.
+    pub fn track_length_km(&self) -> f32 {
+        let current = (self.lat_deg, self.lon_deg, self.surface_age_myr);
+        self.track
+            .iter()
+            .chain(std::iter::once(&current))
+            .collect::<Vec<_>>()
+            .windows(2)
+            .map(|pair| great_circle_km(pair[0].0, pair[0].1, pair[1].0, pair[1].1))
+            .sum()
+    }
+}
+
+// What every mantle shell has in common, so diagnostics can walk them without caring
+// which one is which.
+pub trait MantleLayer {
+    fn thickness_km(&self) -> f32;
+    fn temperature_c(&self) -> f32;
+    fn heat_flux_in(&self) -> f32;
+    fn heat_flux_out(&self) -> f32;
+    fn composition(&self) -> &str;
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Lithosphere {
     pub thickness_km: f32,
     pub temperature_c: f32,
+    pub density: f32,
     pub viscosity: f32,
+    // Viscosity at the reference temperature, which the Arrhenius law scales from.
+    pub reference_viscosity: f32,
+    pub reference_temperature_c: f32,
     pub rigidity_factor: f32,
     pub heat_flux_in: f32,
     pub heat_flux_out: f32,
+    pub radiogenic_heat_w_per_kg: RadiogenicHeat,
     pub composition: String,
     pub tectonic_stress_mpa: f32,
 }
 
+impl Lithosphere {
+    pub fn update_viscosity(&mut self, activation_energy_j_per_mol: f32) {
+        self.viscosity = arrhenius_viscosity(self.reference_viscosity, self.reference_temperature_c, self.temperature_c, activation_energy_j_per_mol);
+    }
+
+    // Converging plates load the lithosphere; once the stress passes the yield point it
+    // fails in one earthquake, dropping back to the background level. Returns the moment
+    // magnitude of that earthquake, with the rupture running through the full thickness.
+    pub fn accumulate_stress(&mut self, plate_velocity: f32, years: f32) -> Option<f32> {
+        if plate_velocity <= 0.0 {
+            return None;
+        }
+        self.tectonic_stress_mpa += CONVERGENT_STRESS_RATE_MPA_PER_CM * plate_velocity * years;
+        if self.tectonic_stress_mpa <= LITHOSPHERE_YIELD_STRESS_MPA {
+            return None;
+        }
+
+        let released_mpa = self.tectonic_stress_mpa - LITHOSPHERE_BACKGROUND_STRESS_MPA;
+        self.tectonic_stress_mpa = LITHOSPHERE_BACKGROUND_STRESS_MPA;
+        Some(moment_magnitude(released_mpa, self.thickness_km.min(MAX_RUPTURE_LENGTH_KM)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Asthenosphere {
     pub thickness_km: f32,
     pub temperature_c: f32,
+    pub density: f32,
     pub viscosity: f32,
+    // Viscosity at the reference temperature, which the Arrhenius law scales from.
+    pub reference_viscosity: f32,
+    pub reference_temperature_c: f32,
     pub partial_melt_pct: f32,
+    // The part of the melt set by temperature and decompression, which tidal melt adds to.
+    pub ambient_melt_pct: f32,
     pub lubrication_factor: f32,
     pub heat_flux_in: f32,
     pub heat_flux_out: f32,
+    pub radiogenic_heat_w_per_kg: RadiogenicHeat,
     pub composition: String,
     pub volatile_content_pct: f32,
     pub lateral_flow_rate_cm_per_year: f32,
     pub dominant_flow_direction_deg: f32,
+    pub tidal_heating_tw: f32,
+    pub mass_kg: f64,
 }
 
 impl Asthenosphere {
-    pub fn update_lateral_flow(&mut self, core_influence: f32) {
-        self.lateral_flow_rate_cm_per_year = (core_influence * 10.0).min(20.0);
+    // Flow at a point: the background flow plus the radial push of every convection cell,
+    // quickened or slowed by how vigorously the layer convects at its temperature.
+    pub fn flow_at(&self, cells: &[ConvectionCell], lat_deg: f32, lon_deg: f32) -> (f32, f32) {
+        let background = self.dominant_flow_direction_deg.to_radians();
+        let mut east = self.lateral_flow_rate_cm_per_year * background.cos();
+        let mut north = self.lateral_flow_rate_cm_per_year * background.sin();
+        for cell in cells {
+            let (cell_east, cell_north) = cell.flow_at(lat_deg, lon_deg);
+            east += cell_east;
+            north += cell_north;
+        }
+        (east.hypot(north) * self.convective_vigour(), north.atan2(east).to_degrees().rem_euclid(360.0))
+    }
+
+    // Convective speed relative to the reference temperature, from the dry Arrhenius
+    // viscosity; water's weakening is left to the lubrication factor.
+    pub fn convective_vigour(&self) -> f32 {
+        let viscosity = arrhenius_viscosity(self.reference_viscosity, self.reference_temperature_c, self.temperature_c, UPPER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        (self.reference_viscosity / viscosity).powf(CONVECTIVE_VIGOUR_EXPONENT).min(MAX_CONVECTIVE_VIGOUR)
+    }
+
+    // Heat the plates draw out of the layer over the step, given the sum of their areas times
+    // their speeds. Returns the heat removed (TJ).
+    pub fn cool_by_plate_drag(&mut self, plate_motion_km2_cm_per_year: f32, years: f32) -> f64 {
+        let heat_tj = PLATE_DRAG_COOLING_TW_PER_KM2_CM_PER_YEAR * plate_motion_km2_cm_per_year.max(0.0) as f64 * SECONDS_PER_YEAR * years as f64;
+        if self.mass_kg > 0.0 {
+            self.temperature_c -= (heat_tj * 1e12 / (self.mass_kg * MANTLE_HEAT_CAPACITY_J_PER_KG_K)) as f32;
+        }
+        heat_tj
+    }
+
+    pub fn update_lateral_flow(&mut self, core_influence: f32, max_flow_cm_per_year: f32) {
+        self.lateral_flow_rate_cm_per_year = (core_influence * 10.0).min(max_flow_cm_per_year);
         self.dominant_flow_direction_deg = (self.dominant_flow_direction_deg + core_influence * 5.0) % 360.0;
     }
+
+    // Melt from rock hotter than the solidus plus melt from decompression beneath ridges.
+    // The melt fraction itself follows over time in `apply_tidal_heating`.
+    pub fn update_melt(&mut self, pressure_drop_gpa: f32) {
+        let thermal_pct = MELT_PCT_PER_C_ABOVE_SOLIDUS * (self.temperature_c - ASTHENOSPHERE_SOLIDUS_C).max(0.0);
+        let decompression_pct = DECOMPRESSION_MELT_PCT_PER_GPA * pressure_drop_gpa.max(0.0);
+        self.ambient_melt_pct = (thermal_pct + decompression_pct).min(100.0);
+    }
+
+    // Tidal heat is carried off by the melt it produces rather than warming the layer, so
+    // the melt fraction settles toward the ambient melt plus a level set by the heating per
+    // kilogram. Returns the heat dissipated over the step (TJ).
+    pub fn apply_tidal_heating(&mut self, years: f32) -> f64 {
+        let w_per_kg = if self.mass_kg > 0.0 { self.tidal_heating_tw as f64 * 1e12 / self.mass_kg } else { 0.0 };
+        let tidal_melt_pct = MAX_TIDAL_MELT_PCT * (1.0 - (-w_per_kg / TIDAL_MELT_SCALE_W_PER_KG).exp()) as f32;
+        let target_pct = (self.ambient_melt_pct + tidal_melt_pct).min(100.0);
+        let response = 1.0 - (-years / TIDAL_MELT_RESPONSE_YEARS).exp();
+        self.partial_melt_pct += (target_pct - self.partial_melt_pct) * response;
+        self.tidal_heating_tw as f64 * SECONDS_PER_YEAR * years as f64
+    }
+
+    // Water weakens the asthenosphere: wetter rock flows more easily and lets plates slide.
+    pub fn absorb_water(&mut self, volatile_pct: f32) {
+        self.volatile_content_pct += volatile_pct;
+        self.viscosity *= 10f32.powf(-VISCOSITY_ORDERS_PER_VOLATILE_PCT * volatile_pct);
+        let excess_pct = self.volatile_content_pct - REFERENCE_VOLATILE_CONTENT_PCT;
+        self.lubrication_factor =
+            (REFERENCE_LUBRICATION_FACTOR + LUBRICATION_PER_VOLATILE_PCT * excess_pct).clamp(0.0, MAX_LUBRICATION_FACTOR);
+    }
+
+    // Arrhenius viscosity at the current temperature, still weakened by any water absorbed.
+    pub fn update_viscosity(&mut self, activation_energy_j_per_mol: f32) {
+        let excess_pct = self.volatile_content_pct - REFERENCE_VOLATILE_CONTENT_PCT;
+        self.viscosity = arrhenius_viscosity(self.reference_viscosity, self.reference_temperature_c, self.temperature_c, activation_energy_j_per_mol)
+            * 10f32.powf(-VISCOSITY_ORDERS_PER_VOLATILE_PCT * excess_pct);
+    }
+
+    // Share of the maximum tidal melt currently present, from 0 with none to 1 when saturated.
+    pub fn tidal_melt_fraction(&self) -> f32 {
+        ((self.partial_melt_pct - self.ambient_melt_pct) / MAX_TIDAL_MELT_PCT).clamp(0.0, 1.0)
+    }
+
+    // Magma an active plate draws per step, scaled by the melt on hand.
+    pub fn volcanic_supply_km3(&self) -> f32 {
+        VOLCANIC_GROWTH_KM3 * self.partial_melt_pct / ASTHENOSPHERE_BASE_MELT_PCT
+    }
 }
 
+// An upwelling (positive strength) or downwelling (negative) centre. Flow runs radially
+// away from an upwelling, vanishing at the centre, peaking at `upwelling_strength` cm/yr
+// at `radius_km`, and fading beyond it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConvectionCell {
+    pub center_lat_deg: f32,
+    pub center_lon_deg: f32,
+    pub upwelling_strength: f32,
+    pub radius_km: f32,
+}
+
+impl ConvectionCell {
+    // Flow this cell drives at a point, as (east, north) components in cm/yr.
+    pub fn flow_at(&self, lat_deg: f32, lon_deg: f32) -> (f32, f32) {
+        let distance_km = great_circle_km(self.center_lat_deg, self.center_lon_deg, lat_deg, lon_deg);
+        if distance_km < 1.0 {
+            return (0.0, 0.0);
+        }
+        let x = distance_km / self.radius_km;
+        let speed = self.upwelling_strength * x * (0.5 - 0.5 * x * x).exp();
+        let direction = bearing_deg(self.center_lat_deg, self.center_lon_deg, lat_deg, lon_deg).to_radians();
+        (speed * direction.cos(), speed * direction.sin())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransitionZone {
     pub thickness_km: f32,
     pub temperature_c: f32,
+    pub density: f32,
     pub viscosity: f32,
+    // Viscosity at the reference temperature, which the Arrhenius law scales from.
+    pub reference_viscosity: f32,
+    pub reference_temperature_c: f32,
     pub phase_change_depth_km: f32,
+    // Depth of the endothermic transition at its base, and how steeply it deflects.
+    pub lower_boundary_km: f32,
+    pub clapeyron_slope_mpa_per_k: f32,
     pub heat_flux_in: f32,
     pub heat_flux_out: f32,
+    pub radiogenic_heat_w_per_kg: RadiogenicHeat,
     pub composition: String,
     pub water_storage_capacity: f32,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConvectionMode {
+    WholeMantle,
+    Layered,
+}
+
+impl TransitionZone {
+    pub fn update_viscosity(&mut self, activation_energy_j_per_mol: f32) {
+        self.viscosity = arrhenius_viscosity(self.reference_viscosity, self.reference_temperature_c, self.temperature_c, activation_energy_j_per_mol);
+    }
+
+    // Share of the slabs and plumes reaching the lower boundary that it holds back, from 0
+    // (open) to 1 (closed). A positive slope helps material across, so it blocks nothing.
+    pub fn barrier_strength(&self) -> f32 {
+        (self.clapeyron_slope_mpa_per_k / LAYERING_CLAPEYRON_SLOPE_MPA_PER_K).clamp(0.0, 1.0)
+    }
+
+    pub fn convection_mode(&self) -> ConvectionMode {
+        if self.barrier_strength() >= 1.0 {
+            ConvectionMode::Layered
+        } else {
+            ConvectionMode::WholeMantle
+        }
+    }
+
+    // Switches between a sealed boundary and Earth's partial one.
+    pub fn set_convection_mode(&mut self, mode: ConvectionMode) {
+        self.clapeyron_slope_mpa_per_k = match mode {
+            ConvectionMode::Layered => LAYERING_CLAPEYRON_SLOPE_MPA_PER_K,
+            ConvectionMode::WholeMantle => EARTH_CLAPEYRON_SLOPE_MPA_PER_K,
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LowerMantle {
     pub thickness_km: f32,
     pub temperature_c: f32,
+    pub density: f32,
     pub viscosity: f32,
+    // Viscosity at the reference temperature, which the Arrhenius law scales from.
+    pub reference_viscosity: f32,
+    pub reference_temperature_c: f32,
     pub deep_convection_strength: f32,
     pub heat_flux_in: f32,
     pub heat_flux_out: f32,
+    pub radiogenic_heat_w_per_kg: RadiogenicHeat,
     pub composition: String,
     pub stored_slab_volume_km3: f32,
     pub lateral_flow_rate_cm_per_year: f32,
     pub dominant_flow_direction_deg: f32,
+    pub slabs: Vec<Slab>,
 }
 
 impl LowerMantle {
+    // A hotter, runnier lower mantle convects harder, in step with its viscosity.
+    pub fn update_viscosity(&mut self, activation_energy_j_per_mol: f32) {
+        let previous = self.viscosity;
+        self.viscosity = arrhenius_viscosity(self.reference_viscosity, self.reference_temperature_c, self.temperature_c, activation_energy_j_per_mol);
+        let vigour = (previous / self.viscosity).powf(CONVECTION_RAYLEIGH_EXPONENT);
+        self.deep_convection_strength = (self.deep_convection_strength * vigour).min(1.0);
+    }
+
     pub fn mix_composition(&mut self, slab_input_km3: f32) {
         self.stored_slab_volume_km3 += slab_input_km3;
         self.deep_convection_strength += 0.01;
@@ -164,186 +1159,2077 @@ impl LowerMantle {
         }
     }
 
-    pub fn update_lateral_flow(&mut self) {
-        self.lateral_flow_rate_cm_per_year = (self.deep_convection_strength * 5.0).min(10.0);
+    pub fn update_lateral_flow(&mut self, max_flow_cm_per_year: f32) {
+        self.lateral_flow_rate_cm_per_year = (self.deep_convection_strength * 5.0).min(max_flow_cm_per_year);
         self.dominant_flow_direction_deg = (self.dominant_flow_direction_deg + self.deep_convection_strength * 3.0) % 360.0;
     }
+
+    // Moves every slab down and warms it toward the surrounding mantle. Returns the volume
+    // that reached the base of the lower mantle, which is removed from the list. The barrier
+    // at the top of the lower mantle raises the volume a ponded pile needs to break through,
+    // without limit once it closes.
+    pub fn sink_slabs(&mut self, years: f32, barrier: &TransitionZone) -> f32 {
+        let boundary_km = barrier.lower_boundary_km;
+        let warming = 1.0 - (-years / SLAB_THERMAL_EQUILIBRATION_YEARS).exp();
+        let passing = 1.0 - barrier.barrier_strength();
+        let penetration_volume_km3 = if passing > 0.0 {
+            SLAB_PENETRATION_VOLUME_KM3 * self.viscosity / REFERENCE_LOWER_MANTLE_VISCOSITY / passing
+        } else {
+            f32::INFINITY
+        };
+        let lower_rate_cm_per_year = SLAB_SINKING_RATE_CM_PER_YEAR * REFERENCE_LOWER_MANTLE_VISCOSITY / self.viscosity;
+        let base_depth_km = boundary_km + self.thickness_km;
+
+        for slab in &mut self.slabs {
+            slab.temperature_c += (self.temperature_c - slab.temperature_c) * warming;
+            if slab.depth_km < boundary_km {
+                let sunk_km = cm_per_year_to_km_per_year(SLAB_SINKING_RATE_CM_PER_YEAR) * years;
+                slab.depth_km = (slab.depth_km + sunk_km).min(boundary_km);
+            } else if slab.depth_km > boundary_km || slab.volume_km3 >= penetration_volume_km3 {
+                slab.depth_km += cm_per_year_to_km_per_year(lower_rate_cm_per_year) * years;
+            }
+        }
+
+        let (ponded, mut slabs): (Vec<Slab>, Vec<Slab>) = self.slabs.drain(..).partition(|slab| slab.depth_km == boundary_km);
+        if let Some(pile) = ponded.into_iter().reduce(Slab::merge) {
+            slabs.push(pile);
+        }
+
+        let (arrived, sinking): (Vec<Slab>, Vec<Slab>) = slabs.into_iter().partition(|slab| slab.depth_km >= base_depth_km);
+        self.slabs = sinking;
+        arrived.iter().map(|slab| slab.volume_km3).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Slab {
+    pub depth_km: f32,
+    pub volume_km3: f32,
+    pub temperature_c: f32,
+}
+
+impl Slab {
+    // Combines two slabs ponded at the same depth, mixing their temperatures by volume.
+    pub fn merge(self, other: Slab) -> Slab {
+        let volume_km3 = self.volume_km3 + other.volume_km3;
+        Slab {
+            depth_km: self.depth_km.max(other.depth_km),
+            volume_km3,
+            temperature_c: (self.temperature_c * self.volume_km3 + other.temperature_c * other.volume_km3) / volume_km3,
+        }
+    }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DPrimePrimeLayer {
     pub thickness_km: f32,
     pub temperature_c: f32,
+    pub density: f32,
     pub viscosity: f32,
+    // Viscosity at the reference temperature, which the Arrhenius law scales from.
+    pub reference_viscosity: f32,
+    pub reference_temperature_c: f32,
     pub plume_generation_potential: f32,
     pub hot_spot_count: u32,
     pub heat_flux_in: f32,
     pub heat_flux_out: f32,
+    pub radiogenic_heat_w_per_kg: RadiogenicHeat,
     pub composition: String,
+    // Share of the layer covered by the provinces; set from them by add_llsvp.
     pub chemical_heterogeneity_factor: f32,
+    pub llsvps: Vec<Llsvp>,
     pub temporal_variability: f32,
+    // Generation of the outer-core flux last taken in; see OuterCore::heat_flux_generation.
+    pub consumed_flux_generation: u64,
+}
+
+// The outer core hadn't recomputed its flux since the D'' layer last took it in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleFluxError {
+    pub generation: u64,
+    pub consumed_generation: u64,
+}
+
+impl fmt::Display for StaleFluxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "outer-core heat flux generation {} was already consumed (last consumed {}); update the outer core before transferring heat",
+            self.generation, self.consumed_generation
+        )
+    }
 }
 
+impl std::error::Error for StaleFluxError {}
+
 impl DPrimePrimeLayer {
-    pub fn transfer_heat(&mut self, incoming_flux: f32, outgoing_flux: f32) {
+    pub fn update_viscosity(&mut self, activation_energy_j_per_mol: f32) {
+        self.viscosity = arrhenius_viscosity(self.reference_viscosity, self.reference_temperature_c, self.temperature_c, activation_energy_j_per_mol);
+    }
+
+    // Heat needed to warm the layer by one kelvin, for a layer resting on a core of the
+    // given radius.
+    pub fn heat_capacity_tj_per_k(&self, core_radius_km: f32) -> f64 {
+        let mass_kg = shell_volume_m3(core_radius_km, core_radius_km + self.thickness_km) * self.density as f64;
+        mass_kg * MANTLE_HEAT_CAPACITY_J_PER_KG_K / 1e12
+    }
+
+    // Takes in the outer core's flux only if it is newer than the one used last time, so a
+    // step run out of order fails instead of reusing last step's value. The imbalance between
+    // the two fluxes across the core-mantle boundary over the step warms or cools the layer.
+    pub fn transfer_heat(&mut self, incoming_flux: f32, flux_generation: u64, outgoing_flux: f32, core_radius_km: f32, years: f32) -> Result<(), StaleFluxError> {
+        if flux_generation <= self.consumed_flux_generation {
+            return Err(StaleFluxError { generation: flux_generation, consumed_generation: self.consumed_flux_generation });
+        }
+        self.consumed_flux_generation = flux_generation;
         self.heat_flux_in = incoming_flux;
         self.heat_flux_out = outgoing_flux;
-        self.temperature_c += (incoming_flux - outgoing_flux) * 8.0;
+
+        let heat_capacity_tj_per_k = self.heat_capacity_tj_per_k(core_radius_km);
+        if heat_capacity_tj_per_k > 0.0 {
+            let net_tj = flux_to_tj_per_year(incoming_flux - outgoing_flux, sphere_area_m2(core_radius_km)) * years as f64;
+            self.temperature_c += (net_tj / heat_capacity_tj_per_k) as f32;
+        }
+        Ok(())
     }
-}
 
-pub struct Mantle {
-    pub lithosphere: Lithosphere,
-    pub asthenosphere: Asthenosphere,
-    pub transition_zone: TransitionZone,
-    pub lower_mantle: LowerMantle,
+    pub fn add_llsvp(&mut self, llsvp: Llsvp) {
+        self.llsvps.push(llsvp);
+        self.update_heterogeneity();
+    }
+
+    // Provinces are taken not to overlap, so their coverage adds.
+    pub fn update_heterogeneity(&mut self) {
+        self.chemical_heterogeneity_factor = self.llsvps.iter().map(Llsvp::coverage).sum::<f32>().min(1.0);
+    }
+
+    // Relative chance of a plume rising at a point, between the background and 1 on a rim.
+    // Without any provinces every point is equally likely.
+    pub fn plume_weight(&self, lat_deg: f32, lon_deg: f32) -> f32 {
+        if self.llsvps.is_empty() {
+            return 1.0;
+        }
+        let edge = self.llsvps.iter().map(|llsvp| llsvp.plume_weight(lat_deg, lon_deg)).fold(0.0, f32::max);
+        PLUME_BACKGROUND_WEIGHT + (1.0 - PLUME_BACKGROUND_WEIGHT) * edge
+    }
+}
+
+macro_rules! impl_mantle_layer {
+    ($($layer:ty),*) => {$(
+        impl MantleLayer for $layer {
+            fn thickness_km(&self) -> f32 { self.thickness_km }
+            fn temperature_c(&self) -> f32 { self.temperature_c }
+            fn heat_flux_in(&self) -> f32 { self.heat_flux_in }
+            fn heat_flux_out(&self) -> f32 { self.heat_flux_out }
+            fn composition(&self) -> &str { &self.composition }
+        }
+    )*};
+}
+
+impl_mantle_layer!(Lithosphere, Asthenosphere, TransitionZone, LowerMantle, DPrimePrimeLayer);
+
+// Crustal volume the dynamics have made and destroyed since the mantle was created.
+// Recycling covers slabs sinking, subducted crust reset to fresh melt and plates consumed
+// outright; erosion is what wears off the top. Everything else a step adds, from spreading
+// ridges, volcanism and thickening, counts as created, so created - recycled - eroded is
+// always the net change. Plates added or split by hand are outside the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrustBudget {
+    pub created_km3: f64,
+    pub recycled_km3: f64,
+    pub eroded_km3: f64,
+}
+
+impl CrustBudget {
+    pub fn net_km3(&self) -> f64 {
+        self.created_km3 - self.recycled_km3 - self.eroded_km3
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mantle {
+    pub lithosphere: Lithosphere,
+    pub asthenosphere: Asthenosphere,
+    pub transition_zone: TransitionZone,
+    pub lower_mantle: LowerMantle,
     pub d_prime_prime: DPrimePrimeLayer,
     pub plates: Vec<Plate>,
     pub hot_spots: Vec<HotSpot>,
-    pub crust: Crust,
+    pub plumes: Vec<Plume>,
+    pub convection_cells: Vec<ConvectionCell>,
+    // Named landmarks, oldest first.
+    pub features: Vec<Feature>,
+    pub volatiles: VolatileBudget,
+    pub crust_budget: CrustBudget,
+    pub next_plate_id: u32,
+    // The generator behind rand's StdRng, used directly so its stream position is saved and
+    // restored with the rest of the state.
+    rng: ChaCha12Rng,
 }
 
 impl Mantle {
-    pub fn new() -> Self {
-        Self {
+    #[cfg(feature = "std")]
+    pub fn new(config: &PlanetConfig) -> Self {
+        Self::with_rng(config, ChaCha12Rng::from_entropy())
+    }
+
+    pub fn new_seeded(config: &PlanetConfig, seed: u64) -> Self {
+        Self::with_rng(config, ChaCha12Rng::seed_from_u64(seed))
+    }
+
+    // Back to the state `new` built, plates and all gone, with the random stream rewound to
+    // its start so the same seed plays out the same history again.
+    pub fn reset(&mut self, config: &PlanetConfig) {
+        let mut rng = self.rng.clone();
+        rng.set_word_pos(0);
+        *self = Self::with_rng(config, rng);
+    }
+
+    fn with_rng(config: &PlanetConfig, rng: ChaCha12Rng) -> Self {
+        let thickness_scale = config.mantle_thickness_scale();
+        let density_scale = config.mantle_density_scale();
+        let asthenosphere_top_km = config.radius_km - 100.0 * thickness_scale;
+
+        let mut mantle = Self {
             lithosphere: Lithosphere {
-                thickness_km: 100.0,
+                thickness_km: 100.0 * thickness_scale,
                 temperature_c: 500.0,
+                density: LITHOSPHERE_DENSITY * density_scale,
                 viscosity: 1e22,
+                reference_viscosity: 1e22,
+                reference_temperature_c: 500.0,
                 rigidity_factor: 0.9,
                 heat_flux_in: 0.05,
                 heat_flux_out: 0.04,
+                radiogenic_heat_w_per_kg: RadiogenicHeat::primordial(MANTLE_RADIOGENIC_HEAT_W_PER_KG),
                 composition: "Peridotite-rich upper mantle".to_string(),
-                tectonic_stress_mpa: 50.0,
+                tectonic_stress_mpa: LITHOSPHERE_BACKGROUND_STRESS_MPA,
             },
             asthenosphere: Asthenosphere {
-                thickness_km: 600.0,
+                thickness_km: 600.0 * thickness_scale,
                 temperature_c: 1300.0,
-                viscosity: 1e19,
-                partial_melt_pct: 2.0,
-                lubrication_factor: 0.8,
+                density: ASTHENOSPHERE_DENSITY * density_scale,
+                viscosity: REFERENCE_ASTHENOSPHERE_VISCOSITY,
+                reference_viscosity: REFERENCE_ASTHENOSPHERE_VISCOSITY,
+                reference_temperature_c: 1300.0,
+                partial_melt_pct: ASTHENOSPHERE_BASE_MELT_PCT,
+                ambient_melt_pct: ASTHENOSPHERE_BASE_MELT_PCT,
+                lubrication_factor: REFERENCE_LUBRICATION_FACTOR,
                 heat_flux_in: 0.04,
                 heat_flux_out: 0.03,
+                radiogenic_heat_w_per_kg: RadiogenicHeat::primordial(MANTLE_RADIOGENIC_HEAT_W_PER_KG),
                 composition: "Partially molten peridotite".to_string(),
-                volatile_content_pct: 0.5,
+                volatile_content_pct: REFERENCE_VOLATILE_CONTENT_PCT,
                 lateral_flow_rate_cm_per_year: 5.0,
                 dominant_flow_direction_deg: 90.0,
+                tidal_heating_tw: config.tidal_heating_tw,
+                mass_kg: shell_volume_m3(asthenosphere_top_km - 600.0 * thickness_scale, asthenosphere_top_km)
+                    * (ASTHENOSPHERE_DENSITY * density_scale) as f64,
             },
             transition_zone: TransitionZone {
-                thickness_km: 250.0,
+                thickness_km: 250.0 * thickness_scale,
                 temperature_c: 1600.0,
+                density: TRANSITION_ZONE_DENSITY * density_scale,
                 viscosity: 1e21,
+                reference_viscosity: 1e21,
+                reference_temperature_c: 1600.0,
                 phase_change_depth_km: 410.0,
+                lower_boundary_km: UPPER_LOWER_MANTLE_BOUNDARY_KM,
+                clapeyron_slope_mpa_per_k: EARTH_CLAPEYRON_SLOPE_MPA_PER_K,
                 heat_flux_in: 0.03,
                 heat_flux_out: 0.02,
+                radiogenic_heat_w_per_kg: RadiogenicHeat::primordial(MANTLE_RADIOGENIC_HEAT_W_PER_KG),
                 composition: "High-pressure mineral phases".to_string(),
                 water_storage_capacity: 1.0,
             },
             lower_mantle: LowerMantle {
-                thickness_km: 2200.0,
+                thickness_km: 2200.0 * thickness_scale,
                 temperature_c: 2500.0,
+                density: LOWER_MANTLE_DENSITY * density_scale,
                 viscosity: 1e23,
+                reference_viscosity: 1e23,
+                reference_temperature_c: 2500.0,
                 deep_convection_strength: 0.6,
                 heat_flux_in: 0.02,
                 heat_flux_out: 0.015,
+                radiogenic_heat_w_per_kg: RadiogenicHeat::primordial(MANTLE_RADIOGENIC_HEAT_W_PER_KG),
                 composition: "Bridgmanite and ferropericlase".to_string(),
                 stored_slab_volume_km3: 0.0,
                 lateral_flow_rate_cm_per_year: 2.0,
                 dominant_flow_direction_deg: 60.0,
+                slabs: Vec::new(),
             },
             d_prime_prime: DPrimePrimeLayer {
-                thickness_km: 200.0,
+                thickness_km: 200.0 * thickness_scale,
                 temperature_c: 3000.0,
+                density: D_PRIME_PRIME_DENSITY * density_scale,
                 viscosity: 1e22,
+                reference_viscosity: 1e22,
+                reference_temperature_c: 3000.0,
                 plume_generation_potential: 0.7,
                 hot_spot_count: 1,
                 heat_flux_in: 0.015,
                 heat_flux_out: 0.02,
+                radiogenic_heat_w_per_kg: RadiogenicHeat::primordial(MANTLE_RADIOGENIC_HEAT_W_PER_KG),
                 composition: "ULVZ mixture".to_string(),
-                chemical_heterogeneity_factor: 0.3,
+                chemical_heterogeneity_factor: 0.0,
+                llsvps: vec![AFRICAN_LLSVP, PACIFIC_LLSVP],
                 temporal_variability: 0.5,
+                consumed_flux_generation: 0,
             },
             plates: Vec::new(),
             hot_spots: Vec::new(),
-            crust: Crust::new("continental"),
+            plumes: Vec::new(),
+            convection_cells: Vec::new(),
+            features: Vec::new(),
+            volatiles: VolatileBudget::new(),
+            crust_budget: CrustBudget::default(),
+            next_plate_id: 1,
+            rng,
+        };
+        mantle.d_prime_prime.update_heterogeneity();
+        mantle
+    }
+
+    // Ids only ever grow and removal keeps the order of the rest, so the plates stay in id
+    // order. Every pass over them, and every float sum along it, runs in that order, so a
+    // run comes out bit for bit the same on any thread count.
+    pub fn add_plate(&mut self, mut plate: Plate) -> u32 {
+        let id = self.next_plate_id;
+        self.next_plate_id += 1;
+        plate.id = id;
+        self.plates.push(plate);
+        id
+    }
+
+    pub fn plate(&self, id: u32) -> Option<&Plate> {
+        self.plates.iter().find(|plate| plate.id == id)
+    }
+
+    pub fn add_feature(&mut self, feature: Feature) {
+        self.features.push(feature);
+    }
+
+    // Features within `radius_km` of a point, nearest first.
+    pub fn features_near(&self, lat_deg: f32, lon_deg: f32, radius_km: f32) -> Vec<&Feature> {
+        let mut near: Vec<(f32, &Feature)> = self
+            .features
+            .iter()
+            .map(|feature| (great_circle_km(lat_deg, lon_deg, feature.lat_deg, feature.lon_deg), feature))
+            .filter(|&(distance_km, _)| distance_km <= radius_km)
+            .collect();
+        near.sort_by(|a, b| a.0.total_cmp(&b.0));
+        near.into_iter().map(|(_, feature)| feature).collect()
+    }
+
+    // Names the landmarks a step's events raised: a mountain range half way between plates
+    // that collide, a rift where a continent broke apart.
+    pub fn record_features(&mut self, events: &[MantleEvent], time_myr: f32) {
+        for event in events {
+            let raised = match *event {
+                MantleEvent::Collision { plate_id, other_plate_id, .. } | MantleEvent::Orogeny { plate_id, other_plate_id, .. } => {
+                    match (self.plate(plate_id), self.plate(other_plate_id)) {
+                        (Some(a), Some(b)) => {
+                            let (lat_deg, lon_deg) = midpoint(a.center_lat_deg, a.center_lon_deg, b.center_lat_deg, b.center_lon_deg);
+                            let name = format!("Range between plates {} and {}", plate_id.min(other_plate_id), plate_id.max(other_plate_id));
+                            Some((FeatureKind::MountainRange, name, lat_deg, lon_deg))
+                        }
+                        _ => None,
+                    }
+                }
+                MantleEvent::ContinentalBreakup { plate_id } => self
+                    .plate(plate_id)
+                    .map(|plate| (FeatureKind::Rift, format!("Rift in plate {}", plate_id), plate.center_lat_deg, plate.center_lon_deg)),
+                _ => None,
+            };
+            let Some((kind, name, lat_deg, lon_deg)) = raised else {
+                continue;
+            };
+            if self.features_near(lat_deg, lon_deg, FEATURE_MERGE_RADIUS_KM).iter().any(|feature| feature.kind == kind) {
+                continue;
+            }
+            debug!("🗺️ {} formed at ({:.1}°, {:.1}°) at {:.1} Myr.", name, lat_deg, lon_deg, time_myr);
+            self.add_feature(Feature { name, lat_deg, lon_deg, kind, birth_myr: time_myr });
         }
     }
 
-    pub fn describe(&self) {
-        println!("Mantle description:");
-        println!("  Lithosphere: Temp {:.0}°C, Stress {:.1} MPa, Composition: {}", self.lithosphere.temperature_c, self.lithosphere.tectonic_stress_mpa, self.lithosphere.composition);
-        println!("  Asthenosphere: Temp {:.0}°C, Melt {:.1}%, Volatiles {:.1}%, Composition: {}", self.asthenosphere.temperature_c, self.asthenosphere.partial_melt_pct, self.asthenosphere.volatile_content_pct, self.asthenosphere.composition);
-        println!("  Transition Zone: Temp {:.0}°C, Phase depth {:.0} km, Water capacity {:.1}", self.transition_zone.temperature_c, self.transition_zone.phase_change_depth_km, self.transition_zone.water_storage_capacity);
-        println!("  Lower Mantle: Temp {:.0}°C, Slab vol {:.1} km³, Composition: {}", self.lower_mantle.temperature_c, self.lower_mantle.stored_slab_volume_km3, self.lower_mantle.composition);
-        println!("  D'' Layer: Temp {:.0}°C, Hot spots {}, Composition: {}", self.d_prime_prime.temperature_c, self.d_prime_prime.hot_spot_count, self.d_prime_prime.composition);
-        self.crust.describe();
-    }
-
-    pub fn update_advanced_dynamics(&mut self, years: f32) {
-        self.lower_mantle.update_lateral_flow();
-        self.asthenosphere.update_lateral_flow(self.lower_mantle.deep_convection_strength);
-
-        // Track cumulative changes for consolidated output
-        let mut total_volcanic_growth = 0.0;
-        let mut total_tectonic_deformation = 0.0;
-        let mut total_subduction_volume = 0.0;
-        let mut total_spread_area = 0.0;
-        let mut total_back_arc_area = 0.0;
-        let mut subduction_events = 0;
-        let mut earthquake_events = 0;
+    pub fn plate_mut(&mut self, id: u32) -> Option<&mut Plate> {
+        self.plates.iter_mut().find(|plate| plate.id == id)
+    }
 
+    // As `plate` and `plate_mut`, for callers that treat a missing plate as an error.
+    pub fn try_plate(&self, id: u32) -> Result<&Plate, WorldSimError> {
+        self.plate(id).ok_or(WorldSimError::MissingPlate { id })
+    }
+
+    pub fn try_plate_mut(&mut self, id: u32) -> Result<&mut Plate, WorldSimError> {
+        self.plate_mut(id).ok_or(WorldSimError::MissingPlate { id })
+    }
+
+    // Links two plates as neighbours both ways. Fails without linking either if one of them
+    // doesn't exist.
+    pub fn connect_plates(&mut self, a: u32, b: u32) -> Result<(), WorldSimError> {
+        self.try_plate(a)?;
+        self.try_plate(b)?;
+        for (id, neighbor) in [(a, b), (b, a)] {
+            let plate = self.try_plate_mut(id)?;
+            if !plate.neighbor_ids.contains(&neighbor) {
+                plate.neighbor_ids.push(neighbor);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove_plate(&mut self, id: u32) -> Option<Plate> {
+        let index = self.plates.iter().position(|plate| plate.id == id)?;
+        let removed = self.plates.remove(index);
         for plate in &mut self.plates {
-            let (started_subduction, spread_area, back_arc_area, earthquake_occurred) = 
-                plate.update_motion(self.asthenosphere.lateral_flow_rate_cm_per_year, self.asthenosphere.dominant_flow_direction_deg);
-            
-            let subduction_volume = plate.subduct(&mut self.lower_mantle);
+            plate.neighbor_ids.retain(|&neighbor| neighbor != id);
+        }
+        Some(removed)
+    }
 
-            if started_subduction {
-                subduction_events += 1;
+    // Splits a plate under enough stress or back-arc extension into two halves that drift
+    // apart in opposite directions from a new spreading ridge between them. Both children
+    // keep the parent's crust and neighbours and start the Wilson cycle over at rifting.
+    pub fn rift_plate(&mut self, plate_id: u32) -> Option<(u32, u32)> {
+        let parent = self.plate(plate_id)?;
+        if parent.shear_stress_mpa <= RIFT_STRESS_THRESHOLD_MPA
+            && parent.back_arc_spreading_rate_cm_per_year <= RIFT_BACK_ARC_THRESHOLD_CM_PER_YEAR
+        {
+            return None;
+        }
+
+        let parent = self.remove_plate(plate_id)?;
+        let half_area_km2 = parent.area_km2 / 2.0;
+        let offset_km = half_area_km2.sqrt() / 2.0;
+
+        let mut children = [parent.motion_direction_deg, parent.motion_direction_deg + 180.0].map(|direction_deg| {
+            let mut child = parent.clone();
+            child.area_km2 = half_area_km2;
+            child.new_crust_area_km2 = parent.new_crust_area_km2 / 2.0;
+            (child.center_lat_deg, child.center_lon_deg) =
+                destination(parent.center_lat_deg, parent.center_lon_deg, direction_deg.rem_euclid(360.0), offset_km);
+            child.set_motion(parent.velocity_cm_per_year, direction_deg);
+            child.shear_stress_mpa = 0.0;
+            child.wilson_stage = WilsonStage::Rifting;
+            child.stage_age_myr = 0.0;
+            child
+        });
+        // The second half takes whatever rounding left over so the total area is unchanged.
+        children[1].area_km2 = parent.area_km2 - children[0].area_km2;
+
+        let [first, second] = children.map(|child| self.add_plate(child));
+        for &neighbor in &parent.neighbor_ids {
+            // A dangling neighbour id, as hand-edited state can hold, links to nothing.
+            let _ = self.connect_plates(first, neighbor);
+            let _ = self.connect_plates(second, neighbor);
+        }
+        self.connect_plates(first, second).expect("both halves were just added");
+
+        debug!("🪓 Plate {} rifted into plates {} and {}.", plate_id, first, second);
+        Some((first, second))
+    }
+
+    // Groups continental plates welded together: two continental neighbours belong to the
+    // same cluster while either of them is colliding or suturing. Plates not joined to any
+    // other form clusters of their own.
+    pub fn continental_clusters(&self) -> Vec<Vec<u32>> {
+        let continental: Vec<&Plate> = self.plates.iter().filter(|plate| plate.crust.kind == CrustType::Continental).collect();
+        let joining = |plate: &Plate| matches!(plate.wilson_stage, WilsonStage::Collision | WilsonStage::Suturing);
+
+        let mut visited = vec![false; continental.len()];
+        let mut clusters = Vec::new();
+        for start in 0..continental.len() {
+            if visited[start] {
+                continue;
             }
-            if earthquake_occurred {
-                earthquake_events += 1;
+            visited[start] = true;
+            let mut cluster = Vec::new();
+            let mut pending = vec![start];
+            while let Some(i) = pending.pop() {
+                cluster.push(continental[i].id);
+                for j in 0..continental.len() {
+                    if !visited[j]
+                        && continental[i].neighbor_ids.contains(&continental[j].id)
+                        && (joining(continental[i]) || joining(continental[j]))
+                    {
+                        visited[j] = true;
+                        pending.push(j);
+                    }
+                }
             }
+            cluster.sort_unstable();
+            clusters.push(cluster);
+        }
+        clusters
+    }
+
+    // The cluster of two or more continents holding most of the continental area, if any.
+    pub fn supercontinent(&self) -> Option<Vec<u32>> {
+        let area = |ids: &[u32]| ids.iter().filter_map(|&id| self.plate(id)).map(|plate| plate.area_km2).sum::<f32>();
+        let clusters = self.continental_clusters();
+        let total_area: f32 = clusters.iter().map(|cluster| area(cluster)).sum();
+
+        clusters
+            .into_iter()
+            .filter(|cluster| cluster.len() > 1)
+            .find(|cluster| total_area > 0.0 && area(cluster) >= SUPERCONTINENT_AREA_FRACTION * total_area)
+    }
+
+    pub fn is_supercontinent(&self) -> bool {
+        self.supercontinent().is_some()
+    }
+
+    // Drops every plate shrunk below MIN_PLATE_AREA_KM2, paired with what used it up: those
+    // whose slab ran out this step went by subduction, the rest lost their area in collisions.
+    fn remove_consumed_plates(&mut self, exhausted: &[u32]) -> Vec<(Plate, ConsumptionCause)> {
+        let consumed: Vec<u32> = self.plates.iter().filter(|plate| plate.is_consumed()).map(|plate| plate.id).collect();
+
+        consumed
+            .into_iter()
+            .filter_map(|id| {
+                let cause = if exhausted.contains(&id) { ConsumptionCause::Subduction } else { ConsumptionCause::Collision };
+                self.remove_plate(id).map(|plate| (plate, cause))
+            })
+            .collect()
+    }
+
+    // Heat content of the mantle shells stacked outward from the given core-mantle boundary
+    // radius, plus the crust carried by each plate.
+    pub fn heat_content_tj(&self, core_radius_km: f32) -> f64 {
+        let shells = [
+            (self.d_prime_prime.thickness_km, self.d_prime_prime.density, self.d_prime_prime.temperature_c),
+            (self.lower_mantle.thickness_km, self.lower_mantle.density, self.lower_mantle.temperature_c),
+            (self.transition_zone.thickness_km, self.transition_zone.density, self.transition_zone.temperature_c),
+            (self.asthenosphere.thickness_km, self.asthenosphere.density, self.asthenosphere.temperature_c),
+            (self.lithosphere.thickness_km, self.lithosphere.density, self.lithosphere.temperature_c),
+        ];
 
-            total_subduction_volume += subduction_volume;
-            total_spread_area += spread_area;
-            total_back_arc_area += back_arc_area;
+        let mut radius_km = core_radius_km;
+        let mut total_tj = 0.0;
+        for (thickness_km, density, temperature_c) in shells {
+            let mass_kg = shell_volume_m3(radius_km, radius_km + thickness_km) * density as f64;
+            total_tj += heat_content_tj(mass_kg, MANTLE_HEAT_CAPACITY_J_PER_KG_K, temperature_c);
+            radius_km += thickness_km;
+        }
+
+        for plate in &self.plates {
+            let crust = &plate.crust;
+            let temperature_c = (crust.surface_temperature_c + self.lithosphere.temperature_c) / 2.0;
+            let mass_kg = plate.area_km2 as f64 * crust.thickness_km as f64 * 1e9 * crust.average_density as f64;
+            total_tj += heat_content_tj(mass_kg, MANTLE_HEAT_CAPACITY_J_PER_KG_K, temperature_c);
+        }
+        total_tj
+    }
+
+    // Releases up to `amount` of the transition zone's stored water into the asthenosphere
+    // above. Returns how much was actually released.
+    pub fn dehydrate_transition_zone(&mut self, amount: f32) -> f32 {
+        let released = amount.clamp(0.0, self.transition_zone.water_storage_capacity);
+        self.transition_zone.water_storage_capacity -= released;
+        self.asthenosphere.absorb_water(released * VOLATILE_PCT_PER_WATER_STORAGE);
+        released
+    }
+
+    pub fn decay_radiogenic(&mut self, years: f32) {
+        self.lithosphere.radiogenic_heat_w_per_kg.decay(years);
+        self.asthenosphere.radiogenic_heat_w_per_kg.decay(years);
+        self.transition_zone.radiogenic_heat_w_per_kg.decay(years);
+        self.lower_mantle.radiogenic_heat_w_per_kg.decay(years);
+        self.d_prime_prime.radiogenic_heat_w_per_kg.decay(years);
+        for plate in &mut self.plates {
+            plate.crust.radiogenic_heat_w_per_kg.decay(years);
+            plate.crust.update_heat_flux();
+        }
+    }
+
+    // The shells from the surface down to the core-mantle boundary.
+    pub fn layers(&self) -> Vec<&dyn MantleLayer> {
+        vec![&self.lithosphere, &self.asthenosphere, &self.transition_zone, &self.lower_mantle, &self.d_prime_prime]
+    }
+
+    pub fn crustal_volume_km3(&self) -> f64 {
+        self.plates.iter().map(Plate::crust_volume_km3).sum()
+    }
+
+    pub fn crustal_mass_budget(&self) -> CrustBudget {
+        self.crust_budget
+    }
+
+    // Radiogenic heat the crust releases per square metre of surface, averaged over plate area.
+    pub fn crust_radiogenic_w_per_m2(&self) -> f64 {
+        let total_area: f64 = self.plates.iter().map(|plate| plate.area_km2 as f64).sum();
+        if total_area <= 0.0 {
+            return 0.0;
+        }
+        self.plates
+            .iter()
+            .map(|plate| plate.crust.radiogenic_heat_flux as f64 * plate.area_km2 as f64)
+            .sum::<f64>()
+            / total_area
+    }
+
+    #[cfg(feature = "std")]
+    pub fn describe(&self) {
+        println!("{}", self.summary());
+    }
+
+    pub fn summary(&self) -> String {
+        let mut lines = vec![
+            "Mantle description:".to_string(),
+            format!("  Lithosphere: Temp {:.0}°C, Stress {:.1} MPa, Composition: {}", self.lithosphere.temperature_c, self.lithosphere.tectonic_stress_mpa, self.lithosphere.composition),
+            format!("  Asthenosphere: Temp {:.0}°C, Melt {:.1}%, Volatiles {:.1}%, Composition: {}", self.asthenosphere.temperature_c, self.asthenosphere.partial_melt_pct, self.asthenosphere.volatile_content_pct, self.asthenosphere.composition),
+            format!("  Transition Zone: Temp {:.0}°C, Phase depth {:.0} km, Water capacity {:.1}", self.transition_zone.temperature_c, self.transition_zone.phase_change_depth_km, self.transition_zone.water_storage_capacity),
+            format!(
+                "  {:.0} km Barrier: Clapeyron slope {:.1} MPa/K, Blocking {:.0}%, {:?} convection",
+                self.transition_zone.lower_boundary_km,
+                self.transition_zone.clapeyron_slope_mpa_per_k,
+                self.transition_zone.barrier_strength() * 100.0,
+                self.transition_zone.convection_mode()
+            ),
+            format!("  Lower Mantle: Temp {:.0}°C, Slab vol {:.1} km³, Sinking slabs {}, Composition: {}", self.lower_mantle.temperature_c, self.lower_mantle.stored_slab_volume_km3, self.lower_mantle.slabs.len(), self.lower_mantle.composition),
+            format!(
+                "  D'' Layer: Temp {:.0}°C, Hot spots {}, Rising plumes {}, LLSVPs {} covering {:.0}%, Composition: {}",
+                self.d_prime_prime.temperature_c,
+                self.d_prime_prime.hot_spot_count,
+                self.plumes.len(),
+                self.d_prime_prime.llsvps.len(),
+                self.d_prime_prime.chemical_heterogeneity_factor * 100.0,
+                self.d_prime_prime.composition
+            ),
+            format!("  Radiogenic heating: {:.2} pW/kg", self.lower_mantle.radiogenic_heat_w_per_kg.total() * 1e12),
+            format!(
+                "  Crust budget: created {:.3e} km³, recycled {:.3e} km³, eroded {:.3e} km³, net {:.3e} km³",
+                self.crust_budget.created_km3, self.crust_budget.recycled_km3, self.crust_budget.eroded_km3, self.crust_budget.net_km3()
+            ),
+        ];
+        lines.extend(self.crust_summary_lines());
+        lines.push(self.volatiles.summary());
+        lines.join("\n")
+    }
+
+    fn crust_summary_lines(&self) -> Vec<String> {
+        let continental = self.plates.iter().filter(|plate| plate.crust.kind == CrustType::Continental).count();
+        let mut lines = vec![format!(
+            "  Crust: {} plate(s), {} continental, {} oceanic, area-weighted thickness {:.2} km",
+            self.plates.len(),
+            continental,
+            self.plates.len() - continental,
+            self.mean_crust_thickness_km()
+        )];
+        for plate in &self.plates {
+            lines.push(format!(
+                "    Plate {}: {} crust, {:.2} km thick, Age {:.1} Myr, Surface temp {:.1}°C",
+                plate.id, plate.crust.kind, plate.crust.thickness_km, plate.crust.age_myr(), plate.crust.surface_temperature_c
+            ));
+        }
+        lines
+    }
+
+    pub fn update_advanced_dynamics(&mut self, years: f32, limits: &Limits) -> Vec<MantleEvent> {
+        self.lithosphere.update_viscosity(UPPER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        self.asthenosphere.update_viscosity(UPPER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        self.transition_zone.update_viscosity(UPPER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        self.lower_mantle.update_viscosity(LOWER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        self.d_prime_prime.update_viscosity(LOWER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        self.lower_mantle.update_lateral_flow(limits.max_lower_mantle_flow_cm_per_year);
+        self.asthenosphere.update_lateral_flow(self.lower_mantle.deep_convection_strength, limits.max_asthenosphere_flow_cm_per_year);
+
+        self.asthenosphere.update_melt(self.ridge_pressure_drop_gpa());
+        let tidal_heat_tj = self.asthenosphere.apply_tidal_heating(years);
+        if tidal_heat_tj > 0.0 {
+            debug!("🌕 Tidal flexing dissipated {:.2e} TJ; asthenosphere melt now {:.1}%.", tidal_heat_tj, self.asthenosphere.partial_melt_pct);
+        }
+        let tidal_activity = self.asthenosphere.tidal_melt_fraction();
+        let volcanic_supply_km3 = self.asthenosphere.volcanic_supply_km3();
+
+        let mut events = Vec::new();
+        let volume_before_km3 = self.crustal_volume_km3();
+        let (mut recycled_km3, mut eroded_km3) = (0.0, 0.0);
+        let motions = self.update_plate_motions(years);
+        let plate_motion_km2_cm_per_year: f32 = self.plates.iter().map(|plate| plate.area_km2 * plate.velocity_cm_per_year).sum();
+        let drag_heat_tj = self.asthenosphere.cool_by_plate_drag(plate_motion_km2_cm_per_year, years);
+        if drag_heat_tj > 0.0 {
+            debug!("🧊 Plate motion drew {:.2e} TJ from the asthenosphere, now {:.0}°C.", drag_heat_tj, self.asthenosphere.temperature_c);
+        }
+        let deep_water_capacity_gt = self.transition_zone.water_storage_capacity as f64 * OCEAN_MASS_WATER_GT;
+        let mut exhausted_plates = Vec::new();
+
+        // Subduction feeds the shared lower mantle, so everything after the motion update
+        // runs in plate order whether or not the motions were computed in parallel.
+        for (plate, (started_subduction, spread_area, back_arc_area, released_stress)) in self.plates.iter_mut().zip(motions) {
+            let plate_id = plate.id;
+            plate.crust.age_provinces(years);
+            plate.crust.underlying_viscosity = self.asthenosphere.viscosity;
+            plate.crust.relax_isostasy(years);
+            // Tidally generated melt keeps every plate at least that volcanically active.
+            plate.volcanic_activity_factor = plate.volcanic_activity_factor.max(tidal_activity);
+            let volume_before_subduction_km3 = plate.crust_volume_km3();
+            let (subduction_volume, exhausted) = plate.subduct(&mut self.lower_mantle, &mut self.volatiles, deep_water_capacity_gt);
+            if exhausted {
+                exhausted_plates.push(plate_id);
+            }
+
+            if started_subduction {
+                if plate.crust.kind == CrustType::Oceanic {
+                    plate.crust.rejuvenate_subduction();
+                }
+                debug!("⚠️ Plate {} started subduction.", plate_id);
+                events.push(MantleEvent::SubductionStarted { plate_id });
+            }
+            recycled_km3 += (volume_before_subduction_km3 - plate.crust_volume_km3()).max(0.0);
+            if let Some(released_mpa) = released_stress {
+                let magnitude = plate.slip_magnitude(released_mpa);
+                debug!("⚡ Transform fault slip on plate {} released {:.1} MPa, Mw {:.1}.", plate_id, released_mpa, magnitude);
+                events.push(MantleEvent::TransformSlip { plate_id, released_mpa, magnitude });
+            }
+            if spread_area > 0.0 {
+                self.volatiles.hydrate(spread_area);
+                events.push(MantleEvent::Spreading { plate_id, area_km2: spread_area });
+            }
+            if back_arc_area > 0.0 {
+                self.volatiles.hydrate(back_arc_area);
+                events.push(MantleEvent::BackArcSpreading { plate_id, area_km2: back_arc_area });
+            }
+            if subduction_volume > 0.0 {
+                events.push(MantleEvent::Subduction { plate_id, volume_km3: subduction_volume });
+            }
 
             if plate.volcanic_activity_factor > 0.5 {
-                total_volcanic_growth += self.crust.grow_by_volcanism(500.0);
+                let km = plate.crust.grow_by_volcanism(volcanic_supply_km3);
+                self.volatiles.degas(volcanic_supply_km3);
+                events.push(MantleEvent::VolcanicGrowth { plate_id, km, volume_km3: volcanic_supply_km3 });
+            }
+            plate.magma_chamber_km3 += plate.volcanic_activity_factor * MAGMA_RECHARGE_KM3_PER_YEAR * years;
+            if let Some(eruption) = plate.maybe_erupt(&mut self.rng) {
+                debug!("🌋 Plate {} erupted {:.1} km³, VEI {}.", plate_id, eruption.volume_km3, eruption.vei);
+                events.push(MantleEvent::VolcanicEruption { plate_id, vei: eruption.vei, volume_km3: eruption.volume_km3 });
+            }
+            let km = plate.crust.update_tectonics(plate.velocity_cm_per_year, limits);
+            events.push(MantleEvent::TectonicDeformation { plate_id, km });
+
+            let km = plate.crust.erode(years, limits);
+            eroded_km3 += plate.area_km2 as f64 * km as f64;
+            events.push(MantleEvent::CrustErosion { plate_id, km });
+            plate.crust.compact_sediment(years);
+
+            if plate.crust.update_rift_transition() {
+                debug!("🌊 Plate {} thinned into new ocean floor.", plate_id);
+                events.push(MantleEvent::ContinentalBreakup { plate_id });
+            }
+        }
+
+        let settled_slab_km3 = self.lower_mantle.sink_slabs(years, &self.transition_zone);
+        if settled_slab_km3 > 0.0 {
+            let potential = &mut self.d_prime_prime.plume_generation_potential;
+            *potential = (*potential + settled_slab_km3 * PLUME_POTENTIAL_PER_SLAB_KM3).min(1.0);
+            debug!("🪨 {:.1} km³ of slab settled onto the D'' layer.", settled_slab_km3);
+            let released = self.dehydrate_transition_zone(settled_slab_km3 * TRANSITION_ZONE_DEHYDRATION_PER_SLAB_KM3);
+            if released > 0.0 {
+                debug!("💧 Slabs dehydrated the transition zone; asthenosphere viscosity now {:.2e} Pa·s.", self.asthenosphere.viscosity);
+            }
+        }
+
+        events.extend(self.route_sediment());
+
+        if let Some(magnitude) = self.lithosphere.accumulate_stress(self.convergence_rate_cm_per_year(), years) {
+            debug!("🌐 Lithospheric stress released in a Mw {:.1} earthquake.", magnitude);
+            events.push(MantleEvent::LithosphereQuake { magnitude });
+        }
+        events.extend(self.resolve_collisions(limits));
+
+        // Interiors of an assembled supercontinent lie far from any coast and dry out.
+        if let Some(cluster) = self.supercontinent() {
+            for plate in self.plates.iter_mut().filter(|plate| cluster.contains(&plate.id)) {
+                plate.crust.aridify(years);
+            }
+            debug!("🏜️ Supercontinent of plates {:?} is drying out.", cluster);
+        }
+
+        // Plates used up this step, whether by their own slab or in a collision, take no
+        // part in sediment routing or collisions and are dropped here together.
+        for (plate, cause) in self.remove_consumed_plates(&exhausted_plates) {
+            recycled_km3 += plate.crust_volume_km3();
+            debug!("🕳️ Plate {} fully consumed by {}.", plate.id, cause.name());
+            events.push(MantleEvent::PlateConsumed { plate_id: plate.id, cause });
+        }
+
+        events.extend(self.ascend_plumes(years));
+        self.generate_plumes(years);
+
+        events.extend(self.feed_hot_spot_volcanism(years));
+        for hot_spot in &mut self.hot_spots {
+            let (flow_rate, flow_direction) = self.asthenosphere.flow_at(&self.convection_cells, hot_spot.lat_deg, hot_spot.lon_deg);
+            hot_spot.migrate(flow_rate, flow_direction, years);
+        }
+
+        let created_km3 = self.crustal_volume_km3() - volume_before_km3 + recycled_km3 + eroded_km3;
+        let budget = &mut self.crust_budget;
+        budget.created_km3 += created_km3;
+        budget.recycled_km3 += recycled_km3;
+        budget.eroded_km3 += eroded_km3;
+
+        events
+    }
+
+    // Neighbouring plates moving toward each other collide. Where two continents meet
+    // neither sinks and the crust thickens into a mountain belt; otherwise the denser plate
+    // starts subducting and hands area to the plate overriding it.
+    pub fn resolve_collisions(&mut self, limits: &Limits) -> Vec<MantleEvent> {
+        let mut events = Vec::new();
+
+        for i in 0..self.plates.len() {
+            for j in (i + 1)..self.plates.len() {
+                let (head, tail) = self.plates.split_at_mut(j);
+                let (a, b) = (&mut head[i], &mut tail[0]);
+                if !a.neighbor_ids.contains(&b.id) || a.is_consumed() || b.is_consumed() {
+                    continue;
+                }
+
+                let closing_rate = a.velocity_toward(b) + b.velocity_toward(a);
+                if closing_rate <= 0.0 {
+                    continue;
+                }
+
+                if a.crust.kind == CrustType::Continental && b.crust.kind == CrustType::Continental {
+                    let (a_id, b_id) = (a.id, b.id);
+                    for (plate, other_plate_id) in [(a, b_id), (b, a_id)] {
+                        let km = plate.crust.update_tectonics(closing_rate + OROGENY_BONUS_CM_PER_YEAR, limits);
+                        debug!("🏔️ Continental collision thickened plate {} crust by {:.3} km.", plate.id, km);
+                        events.push(MantleEvent::Orogeny { plate_id: plate.id, other_plate_id, km });
+                    }
+                    continue;
+                }
+
+                let (sinking, overriding) = if a.density_proxy() >= b.density_proxy() { (a, b) } else { (b, a) };
+                sinking.is_subducting = true;
+                let area_km2 = (closing_rate * COLLISION_AREA_TRANSFER_KM2_PER_CM).min(sinking.area_km2);
+                sinking.area_km2 -= area_km2;
+                overriding.area_km2 += area_km2;
+                debug!("💥 Plate {} is sinking beneath plate {}, transferring {:.1} km².", sinking.id, overriding.id, area_km2);
+                events.push(MantleEvent::Collision { plate_id: sinking.id, other_plate_id: overriding.id, area_km2 });
+
+                let km = overriding.crust.update_tectonics(closing_rate, limits);
+                events.push(MantleEvent::Orogeny { plate_id: overriding.id, other_plate_id: sinking.id, km });
+            }
+        }
+
+        events
+    }
+
+    // Combined closing rate of every pair of neighbouring plates moving toward each other.
+    fn convergence_rate_cm_per_year(&self) -> f32 {
+        let mut total = 0.0;
+        for (i, a) in self.plates.iter().enumerate() {
+            for b in &self.plates[i + 1..] {
+                if a.neighbor_ids.contains(&b.id) && !a.is_consumed() && !b.is_consumed() {
+                    total += (a.velocity_toward(b) + b.velocity_toward(a)).max(0.0);
+                }
             }
-            total_tectonic_deformation += self.crust.update_tectonics(plate.velocity_cm_per_year);
         }
+        total
+    }
 
-        let erosion_km = self.crust.erode(years);
+    // Types every boundary between neighbouring plates from their relative velocity, split
+    // into the part across the line joining their centres and the part along it: mostly
+    // closing is convergent, mostly opening divergent, and mostly sliding past a transform.
+    // A boundary is taken to run the width of the smaller plate.
+    pub fn classify_boundaries(&self) -> Vec<BoundaryClass> {
+        let mut boundaries = Vec::new();
+        for (i, a) in self.plates.iter().enumerate() {
+            for b in &self.plates[i + 1..] {
+                if !a.neighbor_ids.contains(&b.id) {
+                    continue;
+                }
 
-        // Only show significant changes
-        if subduction_events > 0 {
-            println!("⚠️ {} plate(s) started subduction.", subduction_events);
+                let closing_rate = a.velocity_toward(b) + b.velocity_toward(a);
+                let across = bearing_deg(a.center_lat_deg, a.center_lon_deg, b.center_lat_deg, b.center_lon_deg).to_radians();
+                let sliding_rate = [(a, 1.0), (b, -1.0)]
+                    .iter()
+                    .map(|&(plate, sign)| sign * plate.velocity_cm_per_year * (plate.motion_direction_deg.to_radians() - across).sin())
+                    .sum::<f32>();
+
+                let kind = if sliding_rate.abs() > closing_rate.abs() {
+                    BoundaryType::Transform
+                } else if closing_rate > 0.0 {
+                    BoundaryType::Convergent
+                } else {
+                    BoundaryType::Divergent
+                };
+                let length_km = a.area_km2.min(b.area_km2).sqrt();
+                boundaries.push(BoundaryClass { plate_id: a.id, other_plate_id: b.id, kind, length_km });
+            }
         }
-        if earthquake_events > 0 {
-            println!("⚡ {} transform fault slip event(s) occurred!", earthquake_events);
+        boundaries
+    }
+
+    // Total length of boundary of the given type.
+    pub fn boundary_length_km(&self, kind: BoundaryType) -> f32 {
+        self.classify_boundaries().iter().filter(|boundary| boundary.kind == kind).map(|boundary| boundary.length_km).sum()
+    }
+
+    // Writes a GPlates rotation file (moving plate, age in Ma, pole lat, pole lon, angle, fixed
+    // plate) relative to the mantle frame, plate 0. Each plate gets the identity at present day
+    // and, at its age, the finite rotation carrying it back along its current heading at its
+    // current speed. The pole sits a quarter circle to the left of the heading.
+    #[cfg(feature = "std")]
+    pub fn export_rotations(&self, w: &mut impl Write) -> io::Result<()> {
+        for plate in &self.plates {
+            let (pole_lat_deg, pole_lon_deg) = destination(
+                plate.center_lat_deg,
+                plate.center_lon_deg,
+                plate.motion_direction_deg + 90.0,
+                EARTH_RADIUS_KM * std::f32::consts::FRAC_PI_2,
+            );
+            let distance_km = cm_per_year_to_km_per_year(plate.velocity_cm_per_year) * myr_to_years(plate.age_myr);
+            let angle_deg = -(distance_km / EARTH_RADIUS_KM).to_degrees();
+
+            writeln!(w, "{} 0.0 90.0 0.0 0.0 0 !plate {} {} crust", plate.id, plate.id, plate.crust.kind)?;
+            writeln!(
+                w,
+                "{} {:.1} {:.4} {:.4} {:.4} 0 !plate {} {} crust",
+                plate.id, plate.age_myr, pole_lat_deg, pole_lon_deg, angle_deg, plate.id, plate.crust.kind
+            )?;
         }
-        if total_spread_area > 1000.0 {
-            println!("🌋 Plate spreading: gained {:.1} km² new area.", total_spread_area);
+        Ok(())
+    }
+
+    // Map of which plate owns each patch of the surface and the crust it carries there.
+    pub fn rasterize(&self) -> Grid {
+        self.rasterize_with(DEFAULT_GRID_STEP_DEG, DEFAULT_GRID_STEP_DEG)
+    }
+
+    pub fn rasterize_with(&self, lat_step_deg: f32, lon_step_deg: f32) -> Grid {
+        let mut grid = Grid::new(lat_step_deg, lon_step_deg);
+        grid.rasterize(&self.plates);
+        grid
+    }
+
+    // Each plate sheds its sediment load evenly onto the neighbours standing lower than it,
+    // so highlands feed the basins around them. Plates with nowhere lower keep their load.
+    pub fn route_sediment(&mut self) -> Vec<MantleEvent> {
+        let mut events = Vec::new();
+        let mantle_density = self.lithosphere.density;
+
+        for i in 0..self.plates.len() {
+            let elevation_km = self.plates[i].crust.elevation_km(mantle_density);
+            let downhill: Vec<usize> = (0..self.plates.len())
+                .filter(|&j| {
+                    self.plates[i].neighbor_ids.contains(&self.plates[j].id)
+                        && !self.plates[i].is_consumed()
+                        && !self.plates[j].is_consumed()
+                        && self.plates[j].crust.elevation_km(mantle_density) < elevation_km
+                })
+                .collect();
+
+            for (k, &j) in downhill.iter().enumerate() {
+                let (source, target) = if i < j {
+                    let (head, tail) = self.plates.split_at_mut(j);
+                    (&mut head[i], &mut tail[0])
+                } else {
+                    let (head, tail) = self.plates.split_at_mut(i);
+                    (&mut tail[0], &mut head[j])
+                };
+                let km = source.crust.route_sediment_to(&mut target.crust, 1.0 / (downhill.len() - k) as f32);
+                if km > 0.0 {
+                    events.push(MantleEvent::SedimentRouted { plate_id: source.id, other_plate_id: target.id, km });
+                }
+            }
         }
-        if total_back_arc_area > 100.0 {
-            println!("🌊 Back-arc spreading: plate area increased by {:.1} km².", total_back_arc_area);
+
+        events
+    }
+
+    pub fn flow_at(&self, lat_deg: f32, lon_deg: f32) -> (f32, f32) {
+        self.asthenosphere.flow_at(&self.convection_cells, lat_deg, lon_deg)
+    }
+
+    // The forces on every plate are found first from the mantle as it stands. After that each
+    // plate's motion depends only on the plate and its own force, so with the `parallel`
+    // feature the plates are updated across threads; results keep plate order, and nothing is
+    // summed across threads, so the thread count can't reorder a float reduction.
+    fn update_plate_motions(&mut self, years: f32) -> Vec<(bool, f32, f32, Option<f32>)> {
+        let forces: Vec<(f32, f32)> = self.plates.iter().map(|plate| plate.driving_force(self)).collect();
+
+        #[cfg(feature = "parallel")]
+        let plates = self.plates.par_iter_mut().zip(forces.par_iter());
+        #[cfg(not(feature = "parallel"))]
+        let plates = self.plates.iter_mut().zip(forces.iter());
+
+        plates.map(|(plate, &force)| plate.update_motion(force, years)).collect()
+    }
+
+    // Launches new plumes from the top of the D'' layer, more often and more buoyant the
+    // higher its generation potential. Sites are drawn at random and kept in proportion to
+    // their plume weight, so plumes cluster along the rims of the provinces.
+    pub fn generate_plumes(&mut self, years: f32) {
+        let mut expected_plumes = self.d_prime_prime.plume_generation_potential * years / PLUME_RECURRENCE_YEARS;
+        let origin_depth_km = self.lithosphere.thickness_km
+            + self.asthenosphere.thickness_km
+            + self.transition_zone.thickness_km
+            + self.lower_mantle.thickness_km;
+
+        while expected_plumes > 0.0 {
+            if self.rng.gen::<f32>() < expected_plumes.min(1.0) {
+                let buoyancy = self.d_prime_prime.plume_generation_potential.max(MIN_PLUME_BUOYANCY);
+                let (lat_deg, lon_deg) = self.plume_site();
+                self.plumes.push(Plume {
+                    lat_deg,
+                    lon_deg,
+                    origin_depth_km,
+                    depth_km: origin_depth_km,
+                    buoyancy,
+                    head_volume_km3: MAX_PLUME_HEAD_VOLUME_KM3 * buoyancy,
+                });
+                self.d_prime_prime.plume_generation_potential *= PLUME_POTENTIAL_DECAY;
+                debug!("🔥 New mantle plume rising from the D'' layer.");
+            }
+            expected_plumes -= 1.0;
         }
-        if total_subduction_volume > 1000.0 {
-            println!("🌊 Plate subduction: recycling {:.1} km³ into mantle.", total_subduction_volume);
+    }
+
+    // The weight never falls below the background, so this takes about ten draws at most.
+    fn plume_site(&mut self) -> (f32, f32) {
+        loop {
+            let lat_deg = self.rng.gen_range(-PLUME_MAX_LATITUDE_DEG..=PLUME_MAX_LATITUDE_DEG);
+            let lon_deg = self.rng.gen_range(-180.0..180.0);
+            if self.rng.gen::<f32>() < self.d_prime_prime.plume_weight(lat_deg, lon_deg) {
+                return (lat_deg, lon_deg);
+            }
         }
-        if total_volcanic_growth > 0.001 {
-            println!("🌋 Crust growth: added {:.3} km from volcanism.", total_volcanic_growth);
+    }
+
+    // Raises every plume. One reaching the lithosphere floods the plate above with its head
+    // in a single large igneous province, then lives on as a hot spot fed by its tail. One
+    // stalled at the 660 km boundary spreads out beneath it and is lost.
+    pub fn ascend_plumes(&mut self, years: f32) -> Vec<MantleEvent> {
+        let mut events = Vec::new();
+        let lithosphere_base_km = self.lithosphere.thickness_km;
+        let mut arrived = Vec::new();
+        for mut plume in std::mem::take(&mut self.plumes) {
+            if plume.ascend(years, lithosphere_base_km, &self.transition_zone) {
+                arrived.push(plume);
+            } else if plume.is_stalled() {
+                debug!("🧱 Plume stalled at the {:.0} km boundary.", self.transition_zone.lower_boundary_km);
+            } else {
+                self.plumes.push(plume);
+            }
         }
-        if total_tectonic_deformation.abs() > 0.01 {
-            println!("⛰️ Crust tectonic adjustment: thickness changed by {:.3} km.", total_tectonic_deformation);
+
+        for plume in arrived {
+            if let Some(plate_id) = self.plate_at(plume.lat_deg, plume.lon_deg) {
+                let plate = self.plate_mut(plate_id).expect("plate_at returns an existing plate");
+                let km = plate.crust.grow_by_volcanism(plume.head_volume_km3);
+                self.volatiles.degas(plume.head_volume_km3);
+                debug!("🌋 Plume head flooded plate {} with {:.0} km³ of basalt.", plate_id, plume.head_volume_km3);
+                events.push(MantleEvent::LargeIgneousProvince { plate_id, volume_km3: plume.head_volume_km3, km });
+            }
+
+            self.hot_spots.push(HotSpot {
+                lat_deg: plume.lat_deg,
+                lon_deg: plume.lon_deg,
+                surface_age_myr: 0.0,
+                track: Vec::new(),
+            });
+            self.d_prime_prime.hot_spot_count += 1;
+            debug!("🔥 Plume tail settled into a hot spot.");
+            events.push(MantleEvent::PlumeSpawned { hot_spot_index: self.hot_spots.len() - 1 });
         }
-        if erosion_km > 0.1 {
-            println!("🌊 Crust erosion: reduced thickness by {:.3} km over {:.0} years.", erosion_km, years);
+        events
+    }
+
+    // The plate a point lies deepest inside, by the same measure the grid rasterizes with.
+    pub fn plate_at(&self, lat_deg: f32, lon_deg: f32) -> Option<u32> {
+        self.plates
+            .iter()
+            .min_by(|a, b| a.footprint_depth(lat_deg, lon_deg).total_cmp(&b.footprint_depth(lat_deg, lon_deg)))
+            .map(|plate| plate.id)
+    }
+
+    pub fn total_plate_area_km2(&self) -> f32 {
+        self.plates.iter().map(|plate| plate.area_km2).sum()
+    }
+
+    // Crust thickness averaged over every plate by area; zero with no plates.
+    pub fn mean_crust_thickness_km(&self) -> f32 {
+        let total_area_km2 = self.total_plate_area_km2();
+        if total_area_km2 > 0.0 {
+            self.plates.iter().map(|plate| plate.crust.thickness_km * plate.area_km2).sum::<f32>() / total_area_km2
+        } else {
+            0.0
         }
+    }
 
-        for hot_spot in &mut self.hot_spots {
-            hot_spot.migrate(self.asthenosphere.lateral_flow_rate_cm_per_year, self.asthenosphere.dominant_flow_direction_deg);
+    // Decompression of mantle welling up beneath rifts and ridges, averaged over every plate
+    // by area, so one fast ridge on a small plate counts for little.
+    pub fn ridge_pressure_drop_gpa(&self) -> f32 {
+        let total_area_km2 = self.total_plate_area_km2();
+        if total_area_km2 <= 0.0 {
+            return 0.0;
+        }
+        let spreading: f32 = self
+            .plates
+            .iter()
+            .filter(|plate| matches!(plate.wilson_stage, WilsonStage::Rifting | WilsonStage::Spreading))
+            .map(|plate| plate.area_km2 * plate.velocity_cm_per_year)
+            .sum();
+        RIDGE_DECOMPRESSION_GPA_PER_CM_PER_YEAR * spreading / total_area_km2
+    }
+
+    // The plate a hot spot underlies, if the hot spot sits on that plate's spreading margin
+    // (a rifting or spreading plate, within its outer band) the way Iceland sits on the ridge.
+    pub fn hotspot_on_boundary(&self, hotspot_index: usize) -> Option<u32> {
+        let hot_spot = self.hot_spots.get(hotspot_index)?;
+        let plate = self.plate(self.plate_at(hot_spot.lat_deg, hot_spot.lon_deg)?)?;
+        let spreading = matches!(plate.wilson_stage, WilsonStage::Rifting | WilsonStage::Spreading);
+        let on_margin = RIDGE_MARGIN_DEPTH.contains(&plate.footprint_depth(hot_spot.lat_deg, hot_spot.lon_deg));
+        (spreading && on_margin).then_some(plate.id)
+    }
+
+    // Each hot spot feeds volcanism into the plate above it, several times more where it
+    // coincides with a spreading ridge.
+    fn feed_hot_spot_volcanism(&mut self, years: f32) -> Vec<MantleEvent> {
+        let mut events = Vec::new();
+        for index in 0..self.hot_spots.len() {
+            let hot_spot = &self.hot_spots[index];
+            let Some(plate_id) = self.plate_at(hot_spot.lat_deg, hot_spot.lon_deg) else {
+                continue;
+            };
+            let on_ridge = self.hotspot_on_boundary(index).is_some();
+            let amplification = if on_ridge { RIDGE_HOT_SPOT_AMPLIFICATION } else { 1.0 };
+            let magma_km3 = HOT_SPOT_MAGMA_KM3_PER_YEAR * years * amplification;
+
+            let plate = self.plate_mut(plate_id).expect("plate_at returns an existing plate");
+            let km = plate.crust.grow_by_volcanism(magma_km3);
+            self.volatiles.degas(magma_km3);
+            if on_ridge {
+                debug!("🌋 Hot spot {} sits on the spreading margin of plate {}; volcanism amplified.", index, plate_id);
+            }
+            events.push(MantleEvent::VolcanicGrowth { plate_id, km, volume_km3: magma_km3 });
+        }
+        events
+    }
+}
+
+// Moment magnitude of a rupture across the seismogenic width: the seismic moment of a crack
+// scales with the stress drop times its area to the 3/2 power.
+fn moment_magnitude(released_mpa: f32, rupture_length_km: f32) -> f32 {
+    let rupture_area_m2 = (rupture_length_km * SEISMOGENIC_WIDTH_KM) as f64 * 1e6;
+    let stress_drop_pa = (released_mpa * SLIP_STRESS_DROP_FRACTION) as f64 * 1e6;
+    let moment_nm = stress_drop_pa * rupture_area_m2.powf(1.5);
+    ((2.0 / 3.0) * (moment_nm.log10() - 9.1)) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn earth_mantle() -> Mantle {
+        Mantle::new_seeded(&PlanetConfig::earth(), 42)
+    }
+
+    // Bit-exact f64 fields rely on serde_json's `float_roundtrip` feature; without it the
+    // last digit of values like `mass_kg` can come back different.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn a_populated_mantle_survives_a_serde_round_trip() {
+        let mut mantle = earth_mantle();
+        let a = mantle.add_plate(PlateBuilder::new().area_km2(6e7).center(10.0, 20.0).build());
+        let b = mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).center(-30.0, 120.0).age_myr(250.0).build());
+        mantle.connect_plates(a, b).unwrap();
+        mantle.hot_spots.push(HotSpot { lat_deg: 19.4, lon_deg: -155.3, surface_age_myr: 70.0, track: vec![(18.0, -150.0, 60.0)] });
+        mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
+        assert!(!mantle.plates.is_empty() && !mantle.hot_spots.is_empty());
+
+        let json = serde_json::to_string(&mantle).unwrap();
+        let restored: Mantle = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, mantle);
+        assert_eq!(restored.asthenosphere.mass_kg.to_bits(), mantle.asthenosphere.mass_kg.to_bits());
+        assert_eq!(restored.lower_mantle.composition, mantle.lower_mantle.composition);
+    }
+
+    #[test]
+    fn only_a_plate_past_the_onset_age_starts_subducting() {
+        let mut mantle = earth_mantle();
+        mantle.add_plate(PlateBuilder::new().center(0.0, 0.0).age_myr(10.0).build());
+        let old = mantle.add_plate(PlateBuilder::new().center(0.0, 90.0).age_myr(SUBDUCTION_ONSET_AGE_MYR + 1.0).build());
+        mantle.add_plate(PlateBuilder::new().center(0.0, 180.0).age_myr(50.0).build());
+
+        let events = mantle.update_advanced_dynamics(100_000.0, &Limits::default());
+        let started: Vec<_> = events.iter().filter(|event| matches!(event, MantleEvent::SubductionStarted { .. })).collect();
+        assert_eq!(started, [&MantleEvent::SubductionStarted { plate_id: old }]);
+    }
+
+    #[test]
+    fn a_charged_d_prime_prime_layer_spawns_hot_spots() {
+        let mut mantle = earth_mantle();
+        mantle.d_prime_prime.plume_generation_potential = 1.0;
+        let (hot_spots_before, count_before) = (mantle.hot_spots.len(), mantle.d_prime_prime.hot_spot_count);
+
+        for _ in 0..100 {
+            mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
         }
+        assert!(mantle.hot_spots.len() > hot_spots_before);
+        assert_eq!(mantle.d_prime_prime.hot_spot_count - count_before, (mantle.hot_spots.len() - hot_spots_before) as u32);
+        assert!(mantle.d_prime_prime.plume_generation_potential < 1.0);
+    }
+
+    #[test]
+    fn removing_a_plate_keeps_the_other_ids() {
+        let mut mantle = earth_mantle();
+        let ids: Vec<u32> = (0..3).map(|_| mantle.add_plate(PlateBuilder::new().build())).collect();
+        assert_eq!(ids, [1, 2, 3]);
+
+        assert_eq!(mantle.remove_plate(ids[1]).map(|plate| plate.id), Some(ids[1]));
+        assert!(mantle.plate(ids[1]).is_none());
+        assert_eq!(mantle.plate(ids[0]).map(|plate| plate.id), Some(ids[0]));
+        assert_eq!(mantle.plate(ids[2]).map(|plate| plate.id), Some(ids[2]));
+        assert_eq!(mantle.add_plate(PlateBuilder::new().build()), 4);
+    }
+
+    fn first_slip(plate: &mut Plate) -> f32 {
+        (0..10_000).find_map(|_| plate.simulate_transform(100_000.0)).expect("stress builds to the slip threshold")
+    }
+
+    #[test]
+    fn a_transform_fault_slips_with_a_plausible_magnitude() {
+        let mut plate = PlateBuilder::new().transform_boundary(true).build();
+        let magnitude = first_slip(&mut plate);
+        assert!((4.0..=9.5).contains(&magnitude), "Mw {}", magnitude);
+        assert_eq!(plate.shear_stress_mpa, 0.0);
+
+        let mut small = PlateBuilder::new().area_km2(1e5).transform_boundary(true).build();
+        assert!(first_slip(&mut small) < magnitude);
+    }
+
+    #[test]
+    fn a_plate_runs_through_the_whole_wilson_cycle() {
+        let mut plate = PlateBuilder::new().wilson_stage(WilsonStage::Rifting).build();
+        let mut stages = vec![plate.wilson_stage];
+        for _ in 0..10_000 {
+            plate.update_motion((0.0, 0.0), 100_000.0);
+            if stages.last() != Some(&plate.wilson_stage) {
+                stages.push(plate.wilson_stage);
+            }
+            if stages.len() == 6 {
+                break;
+            }
+        }
+        assert_eq!(
+            stages,
+            [
+                WilsonStage::Rifting,
+                WilsonStage::Spreading,
+                WilsonStage::Subducting,
+                WilsonStage::Collision,
+                WilsonStage::Suturing,
+                WilsonStage::Rifting
+            ]
+        );
+    }
+
+    #[test]
+    fn a_rift_opens_crust_at_its_own_speed_without_rejuvenating_the_plate() {
+        let rifting = || PlateBuilder::new().area_km2(1e7).velocity(5.0).age_myr(80.0).wilson_stage(WilsonStage::Rifting).build();
+        let opened = |years: f32| rifting().spread(years);
+
+        // 5 cm/yr along a rift as long as the plate is wide, for a million years.
+        let expected_km2 = 5e-5 * 1e7f32.sqrt() * 1e6;
+        assert!((opened(1e6) - expected_km2).abs() < 1e-3 * expected_km2, "{} vs {}", opened(1e6), expected_km2);
+        assert!((opened(2e6) / opened(1e6) - 2.0).abs() < 1e-4);
+        assert_eq!(PlateBuilder::new().wilson_stage(WilsonStage::Rifting).build().spread(1e6), 0.0);
+
+        let mut plate = rifting();
+        let area_before_km2 = plate.area_km2;
+        for _ in 0..2 {
+            plate.update_motion((5.0 * PLATE_DRAG_TN_PER_M_PER_CM, 0.0), 1e6);
+        }
+        assert_eq!(plate.wilson_stage, WilsonStage::Rifting);
+        assert_eq!(plate.age_myr, 82.0);
+        assert!((plate.new_crust_area_km2 - (plate.area_km2 - area_before_km2)).abs() < 1.0);
+        // About half the new crust opened a million years before the rest.
+        assert!((plate.new_crust_age_myr - 1.0).abs() < 0.05, "{}", plate.new_crust_age_myr);
+    }
+
+    // Two neighbours 20° apart on the equator, closing at 5 cm/yr each.
+    fn converging_pair(mantle: &mut Mantle, west: CrustType, east: CrustType) -> (u32, u32) {
+        let a = mantle.add_plate(PlateBuilder::new().crust(west).center(0.0, 0.0).velocity(5.0).direction_deg(0.0).build());
+        let b = mantle.add_plate(PlateBuilder::new().crust(east).center(0.0, 20.0).velocity(5.0).direction_deg(180.0).build());
+        mantle.connect_plates(a, b).unwrap();
+        (a, b)
+    }
+
+    #[test]
+    fn ocean_sinks_under_a_continent_that_thickens() {
+        let mut mantle = earth_mantle();
+        let (ocean, continent) = converging_pair(&mut mantle, CrustType::Oceanic, CrustType::Continental);
+        let thickness_before = mantle.plate(continent).unwrap().crust.thickness_km;
+
+        let events = mantle.resolve_collisions(&Limits::default());
+        assert!(events.iter().any(|event| matches!(event, MantleEvent::Collision { plate_id, other_plate_id, .. } if *plate_id == ocean && *other_plate_id == continent)));
+        assert!(mantle.plate(ocean).unwrap().is_subducting);
+        assert!(!mantle.plate(continent).unwrap().is_subducting);
+        assert!(mantle.plate(continent).unwrap().crust.thickness_km > thickness_before);
+    }
+
+    #[test]
+    fn colliding_continents_thicken_up_to_the_cap() {
+        let mut mantle = earth_mantle();
+        let (a, b) = converging_pair(&mut mantle, CrustType::Continental, CrustType::Continental);
+        let limits = Limits::default();
+        for _ in 0..1_000 {
+            mantle.resolve_collisions(&limits);
+        }
+        for id in [a, b] {
+            let plate = mantle.plate(id).unwrap();
+            assert!(!plate.is_subducting);
+            assert_eq!(plate.crust.thickness_km, limits.max_crust_thickness_km);
+        }
+    }
+
+    #[test]
+    fn each_plate_carries_its_own_crust() {
+        let mut mantle = earth_mantle();
+        let continent = mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).center(0.0, 0.0).build());
+        let ocean = mantle.add_plate(PlateBuilder::new().center(0.0, 120.0).build());
+        let thickness = |mantle: &Mantle, id: u32| mantle.plate(id).unwrap().crust.thickness_km;
+        let (continent_before, ocean_before) = (thickness(&mantle, continent), thickness(&mantle, ocean));
+
+        mantle.plate_mut(ocean).unwrap().crust.grow_by_volcanism(1_000_000.0);
+        assert!(thickness(&mantle, ocean) > ocean_before);
+        assert_eq!(thickness(&mantle, continent), continent_before);
+        assert_eq!(mantle.plate(continent).unwrap().crust.provinces.len(), 1);
+
+        let limits = Limits::default();
+        let eroded_continent = mantle.plate_mut(continent).unwrap().crust.erode(1_000_000.0, &limits);
+        let eroded_ocean = mantle.plate_mut(ocean).unwrap().crust.erode(1_000_000.0, &limits);
+        assert!(eroded_continent > 0.0 && eroded_ocean > 0.0);
+        assert_ne!(eroded_continent, eroded_ocean);
+        assert_eq!(thickness(&mantle, continent), continent_before - eroded_continent);
+    }
+
+    #[test]
+    fn the_builder_fills_in_a_valid_default_plate() {
+        let plate = PlateBuilder::new().area_km2(1e8).build();
+        assert!(plate.validate().is_ok());
+        assert_eq!(plate.area_km2, 1e8);
+        assert_eq!(plate.crust.kind, CrustType::Oceanic);
+        assert_eq!((plate.center_lat_deg, plate.center_lon_deg), (0.0, 0.0));
+        assert_eq!((plate.age_myr, plate.velocity_cm_per_year), (0.0, 0.0));
+        assert_eq!(plate.wilson_stage, WilsonStage::Spreading);
+        assert!(!plate.is_transform_boundary && !plate.has_back_arc_spreading);
+    }
+
+    #[test]
+    fn the_builder_keeps_overridden_fields() {
+        let plate = PlateBuilder::new()
+            .crust(CrustType::Continental)
+            .center(45.0, -30.0)
+            .velocity(4.0)
+            .direction_deg(90.0)
+            .back_arc_spreading(2.0)
+            .build();
+        assert_eq!(plate.crust.kind, CrustType::Continental);
+        assert_eq!((plate.center_lat_deg, plate.center_lon_deg), (45.0, -30.0));
+        assert_eq!((plate.velocity_cm_per_year, plate.motion_direction_deg), (4.0, 90.0));
+        assert!(plate.has_back_arc_spreading);
+        assert_eq!(plate.back_arc_spreading_rate_cm_per_year, 2.0);
+        assert_eq!(plate.area_km2, PlateBuilder::new().build().area_km2);
+    }
+
+    fn hot_spot_at(lat_deg: f32, lon_deg: f32) -> HotSpot {
+        HotSpot { lat_deg, lon_deg, surface_age_myr: 0.0, track: Vec::new() }
+    }
+
+    #[test]
+    fn a_change_in_flow_bends_the_hot_spot_track() {
+        let mut hot_spot = hot_spot_at(0.0, 0.0);
+        for _ in 0..5 {
+            hot_spot.migrate(5.0, 90.0, 100_000.0);
+        }
+        for _ in 0..5 {
+            hot_spot.migrate(5.0, 0.0, 100_000.0);
+        }
+        assert_eq!(hot_spot.track.len(), 10);
+        assert!(hot_spot.track.windows(2).all(|pair| pair[0].2 < pair[1].2));
+
+        let heading = |from: usize, to: usize| {
+            let (a, b) = (hot_spot.track[from], hot_spot.track[to]);
+            bearing_deg(a.0, a.1, b.0, b.1)
+        };
+        assert!((heading(0, 4) - 90.0).abs() < 0.1);
+        assert!(heading(5, 9).abs() < 0.1);
+
+        let leg_km = great_circle_km(0.0, 0.0, 0.0, 0.5);
+        assert!((hot_spot.track_length_km() - 2.0 * 5.0 * leg_km).abs() < 1.0);
+    }
+
+    #[test]
+    fn a_hot_spot_crossing_the_pole_comes_down_the_far_side() {
+        let mut hot_spot = hot_spot_at(89.0, 30.0);
+        // 20 × 0.1° of drift due north (flow directions run anticlockwise from east): one
+        // degree up to the pole and one down the other side.
+        hot_spot.migrate(20.0, 90.0, 100_000.0);
+
+        assert!((hot_spot.lat_deg - 89.0).abs() < 0.01);
+        let flip = (hot_spot.lon_deg - 30.0).rem_euclid(360.0);
+        assert!((flip - 180.0).abs() < 0.1);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_plate_motion_matches_a_sequential_pass() {
+        let mut mantle = earth_mantle();
+        for i in 0..500 {
+            let kind = if i % 3 == 0 { CrustType::Continental } else { CrustType::Oceanic };
+            let plate = PlateBuilder::new()
+                .crust(kind)
+                .center((i % 170) as f32 - 85.0, (i * 37 % 360) as f32 - 180.0)
+                .age_myr((i % 150) as f32)
+                .transform_boundary(i % 7 == 0)
+                .build();
+            mantle.add_plate(plate);
+        }
+
+        let mut sequential = mantle.clone();
+        let forces: Vec<(f32, f32)> = sequential.plates.iter().map(|plate| plate.driving_force(&sequential)).collect();
+        let expected: Vec<_> = sequential.plates.iter_mut().zip(forces).map(|(plate, force)| plate.update_motion(force, 1_000_000.0)).collect();
+
+        assert_eq!(mantle.update_plate_motions(1_000_000.0), expected);
+        assert_eq!(mantle.plates, sequential.plates);
+    }
+
+    #[test]
+    fn an_upwelling_between_two_plates_pushes_them_apart() {
+        let mut mantle = earth_mantle();
+        mantle.asthenosphere.lateral_flow_rate_cm_per_year = 0.0;
+        mantle.convection_cells.push(ConvectionCell { center_lat_deg: 0.0, center_lon_deg: 0.0, upwelling_strength: 10.0, radius_km: 1_500.0 });
+        let west = mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).center(0.0, -10.0).build());
+        let east = mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).center(0.0, 10.0).build());
+
+        mantle.update_plate_motions(100_000.0);
+        let (west, east) = (mantle.plate(west).unwrap(), mantle.plate(east).unwrap());
+        assert!(west.velocity_toward(east) < 0.0);
+        assert!(east.velocity_toward(west) < 0.0);
+    }
+
+    #[test]
+    fn rifting_splits_a_plate_into_two_halves_of_the_same_area() {
+        let mut mantle = earth_mantle();
+        let calm = mantle.add_plate(PlateBuilder::new().area_km2(1e8).build());
+        assert_eq!(mantle.rift_plate(calm), None);
+
+        let stressed = mantle.add_plate(PlateBuilder::new().area_km2(1e8).velocity(3.0).direction_deg(90.0).build());
+        mantle.plate_mut(stressed).unwrap().shear_stress_mpa = RIFT_STRESS_THRESHOLD_MPA + 1.0;
+        let (first, second) = mantle.rift_plate(stressed).expect("stress above the threshold rifts the plate");
+
+        assert!(mantle.plate(stressed).is_none());
+        assert!(first != stressed && second != stressed && first != second);
+        let (first, second) = (mantle.plate(first).unwrap(), mantle.plate(second).unwrap());
+        assert_eq!(first.area_km2 + second.area_km2, 1e8);
+        assert_eq!((first.motion_direction_deg - second.motion_direction_deg).abs(), 180.0);
+        assert!(first.neighbor_ids.contains(&second.id) && second.neighbor_ids.contains(&first.id));
+    }
+
+    #[test]
+    fn a_subducted_slab_sinks_to_the_base_and_charges_plumes() {
+        let mut mantle = earth_mantle();
+        mantle.d_prime_prime.plume_generation_potential = 0.0;
+        let mut plate = PlateBuilder::new().area_km2(1e9).wilson_stage(WilsonStage::Subducting).build();
+        let (volume_km3, _) = plate.subduct(&mut mantle.lower_mantle, &mut mantle.volatiles, OCEAN_MASS_WATER_GT);
+        assert_eq!(mantle.lower_mantle.slabs.len(), 1);
+        assert_eq!(mantle.lower_mantle.slabs[0].depth_km, SLAB_ENTRY_DEPTH_KM);
+
+        let mut steps = 0;
+        while !mantle.lower_mantle.slabs.is_empty() {
+            assert_eq!(mantle.d_prime_prime.plume_generation_potential, 0.0);
+            mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
+            steps += 1;
+            assert!(steps < 1_000, "the slab never reached the base");
+        }
+        let expected = volume_km3 * PLUME_POTENTIAL_PER_SLAB_KM3;
+        assert!((mantle.d_prime_prime.plume_generation_potential - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn a_large_eruption_cools_the_surface_until_the_ash_clears() {
+        use crate::earth::crust::DEFAULT_SOLAR_CONSTANT_W_PER_M2;
+        let (limits, greenhouse_factor) = (Limits::default(), 0.6);
+        let mut plate = PlateBuilder::new().crust(CrustType::Continental).build();
+        for _ in 0..1_000 {
+            plate.crust.update_surface_temperature(10.0, DEFAULT_SOLAR_CONSTANT_W_PER_M2, greenhouse_factor, &limits);
+        }
+        let settled_c = plate.crust.surface_temperature_c;
+
+        plate.volcanic_activity_factor = 1.0;
+        plate.magma_chamber_km3 = 10_000.0;
+        let eruption = plate.maybe_erupt(&mut ChaCha12Rng::seed_from_u64(9)).expect("a full chamber at full activity erupts");
+        assert!(eruption.vei >= 7, "VEI {}", eruption.vei);
+        assert!(plate.crust.surface_temperature_c < settled_c - 0.5);
+
+        for _ in 0..1_000 {
+            plate.crust.update_surface_temperature(10.0, DEFAULT_SOLAR_CONSTANT_W_PER_M2, greenhouse_factor, &limits);
+        }
+        assert!((plate.crust.surface_temperature_c - settled_c).abs() < 0.1, "{} vs {}", plate.crust.surface_temperature_c, settled_c);
+    }
+
+    #[test]
+    fn a_subducting_plate_outruns_one_without_a_slab() {
+        let velocity_after_steps = |stage: WilsonStage| {
+            let mut mantle = earth_mantle();
+            let id = mantle.add_plate(PlateBuilder::new().area_km2(1e8).age_myr(60.0).wilson_stage(stage).build());
+            for _ in 0..10 {
+                mantle.update_advanced_dynamics(100_000.0, &Limits::default());
+            }
+            let plate = mantle.plate(id).unwrap();
+            assert_eq!(plate.wilson_stage, stage);
+            (plate.velocity_cm_per_year, plate.slab_pull_cm_per_year)
+        };
+
+        let (subducting, pull) = velocity_after_steps(WilsonStage::Subducting);
+        let (spreading, no_pull) = velocity_after_steps(WilsonStage::Spreading);
+        assert!(pull > 0.0 && no_pull == 0.0);
+        assert!(subducting > spreading, "{} vs {} cm/yr", subducting, spreading);
+    }
+
+    #[test]
+    fn slab_pull_drives_a_plate_faster_than_ridge_push_alone() {
+        // Still asthenosphere, so no basal drag and only the edge forces act.
+        let mut mantle = earth_mantle();
+        mantle.convection_cells.clear();
+        mantle.asthenosphere.lateral_flow_rate_cm_per_year = 0.0;
+
+        let ridge_pushed = PlateBuilder::new().crust(CrustType::Oceanic).age_myr(60.0).build();
+        let mut slab_pulled = ridge_pushed.clone();
+        slab_pulled.slab_pull_cm_per_year = 5.0;
+
+        assert_eq!(ridge_pushed.compute_driving_force(&mantle), ridge_pushed.ridge_push_tn_per_m());
+        assert!(ridge_pushed.ridge_push_tn_per_m() > 0.0);
+        assert!(slab_pulled.compute_driving_force(&mantle) > ridge_pushed.compute_driving_force(&mantle));
+
+        let speed = |mut plate: Plate| {
+            let force = plate.driving_force(&mantle);
+            plate.update_motion(force, 100_000.0);
+            plate.velocity_cm_per_year
+        };
+        let (pushed, pulled) = (speed(ridge_pushed), speed(slab_pulled));
+        assert!(pushed > 0.0);
+        assert!(pulled > pushed, "{} vs {} cm/yr", pulled, pushed);
+    }
+
+    #[test]
+    fn plates_slow_as_their_drag_cools_a_hot_asthenosphere() {
+        // Hot enough to convect well above the reference vigour, short of its cap.
+        let mut mantle = earth_mantle();
+        mantle.asthenosphere.temperature_c += 50.0;
+        assert!(mantle.asthenosphere.convective_vigour() < MAX_CONVECTIVE_VIGOUR);
+        for lon_deg in [-120.0, 0.0, 120.0] {
+            mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).area_km2(5e7).center(0.0, lon_deg).build());
+        }
+        let mean_velocity = |mantle: &Mantle| mantle.plates.iter().map(|plate| plate.velocity_cm_per_year).sum::<f32>() / mantle.plates.len() as f32;
+
+        mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
+        let (hot_c, vigorous) = (mantle.asthenosphere.temperature_c, mean_velocity(&mantle));
+        for _ in 0..50 {
+            mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
+        }
+        let (cooled_c, settled) = (mantle.asthenosphere.temperature_c, mean_velocity(&mantle));
+
+        assert!(cooled_c < hot_c, "{} °C did not cool from {} °C", cooled_c, hot_c);
+        assert!(settled < vigorous, "{} cm/yr did not slow from {} cm/yr", settled, vigorous);
+    }
+
+    #[test]
+    fn a_collision_raises_a_named_range_between_the_plates() {
+        let mut mantle = earth_mantle();
+        let (a, b) = converging_pair(&mut mantle, CrustType::Continental, CrustType::Continental);
+        let events = mantle.resolve_collisions(&Limits::default());
+        assert!(events.iter().any(|event| matches!(event, MantleEvent::Collision { .. } | MantleEvent::Orogeny { .. })));
+
+        mantle.record_features(&events, 12.5);
+        let near = mantle.features_near(0.0, 10.0, 500.0);
+        assert_eq!(near.len(), 1);
+        assert_eq!(near[0].kind, FeatureKind::MountainRange);
+        assert_eq!(near[0].name, format!("Range between plates {} and {}", a, b));
+        assert_eq!(near[0].birth_myr, 12.5);
+        assert!(great_circle_km(near[0].lat_deg, near[0].lon_deg, 0.0, 10.0) < 1.0);
+
+        // The same collision carrying on grows that range rather than naming another.
+        let events = mantle.resolve_collisions(&Limits::default());
+        mantle.record_features(&events, 12.6);
+        assert_eq!(mantle.features.len(), 1);
+        assert!(mantle.features_near(45.0, 10.0, 500.0).is_empty());
+    }
+
+    #[test]
+    fn stepping_a_clone_leaves_the_original_alone() {
+        let mut original = earth_mantle();
+        original.add_plate(PlateBuilder::new().velocity(3.0).build());
+        let untouched = original.clone();
+        let mut fork = original.clone();
+        assert_eq!(fork, original);
+
+        fork.update_advanced_dynamics(1_000_000.0, &Limits::default());
+        assert_ne!(fork, original);
+        assert_eq!(original, untouched);
+    }
+
+    #[test]
+    fn the_mantle_summary_spells_degrees_correctly() {
+        let summary = earth_mantle().summary();
+        assert!(summary.contains("Lithosphere: Temp"));
+        assert!(summary.contains("°C") && !summary.contains("Â°"));
+    }
+
+    #[test]
+    fn three_colliding_continents_assemble_a_drying_supercontinent() {
+        let mut mantle = earth_mantle();
+        let ids: Vec<u32> = [0.0, 30.0, 60.0]
+            .into_iter()
+            .map(|lon_deg| mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).center(0.0, lon_deg).build()))
+            .collect();
+        mantle.add_plate(PlateBuilder::new().center(0.0, 180.0).build());
+        mantle.connect_plates(ids[0], ids[1]).unwrap();
+        mantle.connect_plates(ids[1], ids[2]).unwrap();
+        assert!(!mantle.is_supercontinent());
+        assert_eq!(mantle.continental_clusters().len(), 3);
+
+        for &id in &ids {
+            mantle.plate_mut(id).unwrap().wilson_stage = WilsonStage::Collision;
+        }
+        assert!(mantle.is_supercontinent());
+        let mut cluster = mantle.supercontinent().unwrap();
+        cluster.sort();
+        assert_eq!(cluster, ids);
+
+        let groundwater_before = mantle.plate(ids[1]).unwrap().crust.surface.groundwater_content_pct;
+        mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
+        assert!(mantle.plate(ids[1]).unwrap().crust.surface.groundwater_content_pct < groundwater_before);
+    }
+
+    #[test]
+    fn sustained_convergence_releases_stress_periodically() {
+        let mut lithosphere = earth_mantle().lithosphere;
+        let quakes: Vec<(usize, f32)> = (0..2_000)
+            .filter_map(|step| lithosphere.accumulate_stress(5.0, 100_000.0).map(|magnitude| (step, magnitude)))
+            .collect();
+        assert!(quakes.len() >= 3, "only {} quakes", quakes.len());
+        assert!(lithosphere.tectonic_stress_mpa <= LITHOSPHERE_YIELD_STRESS_MPA);
+
+        // Every cycle after the first starts from the same background stress, so the quakes
+        // come at a fixed interval with the same magnitude.
+        let intervals: Vec<usize> = quakes.windows(2).map(|pair| pair[1].0 - pair[0].0).collect();
+        assert!(intervals.windows(2).all(|pair| pair[0].abs_diff(pair[1]) <= 1), "{:?}", intervals);
+        assert!(quakes[1..].windows(2).all(|pair| (pair[0].1 - pair[1].1).abs() < 0.01));
+
+        assert_eq!(lithosphere.accumulate_stress(0.0, 100_000.0), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn rotations_are_written_for_every_plate() {
+        let mut mantle = earth_mantle();
+        let a = mantle.add_plate(PlateBuilder::new().center(10.0, 20.0).velocity(5.0).direction_deg(90.0).age_myr(40.0).build());
+        let b = mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).center(-30.0, 120.0).velocity(2.0).age_myr(100.0).build());
+
+        let mut rot = Vec::new();
+        mantle.export_rotations(&mut rot).unwrap();
+        let text = String::from_utf8(rot).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        for (id, age) in [(a, "40.0"), (b, "100.0")] {
+            let plate_lines: Vec<&&str> = lines.iter().filter(|line| line.starts_with(&format!("{} ", id))).collect();
+            assert_eq!(plate_lines.len(), 2);
+            assert!(plate_lines[0].starts_with(&format!("{} 0.0 90.0 0.0 0.0 0", id)));
+            assert_eq!(plate_lines[1].split_whitespace().nth(1), Some(age));
+            assert!(plate_lines[1].contains(&format!("!plate {}", id)));
+        }
+    }
+
+    #[test]
+    fn stronger_tidal_heating_melts_more_of_the_asthenosphere() {
+        let melt_after_flexing = |tidal_heating_tw: f32| {
+            let mut mantle = earth_mantle();
+            mantle.asthenosphere.tidal_heating_tw = tidal_heating_tw;
+            let plate = mantle.add_plate(PlateBuilder::new().volcanic_activity(0.0).build());
+            for _ in 0..20 {
+                mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
+            }
+            (mantle.asthenosphere.partial_melt_pct, mantle.plate(plate).unwrap().volcanic_activity_factor)
+        };
+
+        let (none, quiet) = melt_after_flexing(0.0);
+        let (some, _) = melt_after_flexing(10.0);
+        let (io_like, active) = melt_after_flexing(PlanetConfig::io().tidal_heating_tw);
+        assert!(none < some && some < io_like, "{} < {} < {}", none, some, io_like);
+        assert!(active > quiet);
+    }
+
+    #[test]
+    fn a_small_subducting_plate_is_consumed_and_removed() {
+        let mut mantle = earth_mantle();
+        let plate = PlateBuilder::new().area_km2(MIN_PLATE_AREA_KM2 * 1.05).wilson_stage(WilsonStage::Subducting).build();
+        let id = mantle.add_plate(plate);
+
+        let mut steps = 0;
+        loop {
+            let remnant_km3 = mantle.plate(id).expect("the plate is still there").crust_volume_km3();
+            let recycled_before_km3 = mantle.crust_budget.recycled_km3;
+            let events = mantle.update_advanced_dynamics(100_000.0, &Limits::default());
+            steps += 1;
+            if events.contains(&MantleEvent::PlateConsumed { plate_id: id, cause: ConsumptionCause::Subduction }) {
+                // The whole remnant went back into the mantle on the final step.
+                assert!(mantle.crust_budget.recycled_km3 - recycled_before_km3 >= remnant_km3 * 0.999);
+                break;
+            }
+            assert!(steps < 20, "the plate was never used up");
+        }
+        assert!(mantle.plate(id).is_none() && mantle.plates.is_empty());
+    }
+
+    #[test]
+    fn try_build_refuses_a_plate_that_fails_validation() {
+        assert!(PlateBuilder::new().area_km2(5e6).try_build().is_ok());
+
+        let err = PlateBuilder::new().area_km2(0.0).try_build().unwrap_err();
+        assert!(matches!(&err, WorldSimError::OutOfRange(violation) if violation.field == "area_km2"), "{err}");
+        let err = PlateBuilder::new().volcanic_activity(1.5).try_build().unwrap_err();
+        assert!(matches!(&err, WorldSimError::OutOfRange(violation) if violation.field == "volcanic_activity_factor"), "{err}");
+    }
+
+    #[test]
+    fn a_plate_overridden_in_a_collision_is_consumed_by_the_collision() {
+        let mut mantle = earth_mantle();
+        let (ocean, continent) = converging_pair(&mut mantle, CrustType::Oceanic, CrustType::Continental);
+        mantle.plate_mut(ocean).unwrap().area_km2 = MIN_PLATE_AREA_KM2 * 5.0;
+
+        let events = mantle.update_advanced_dynamics(100_000.0, &Limits::default());
+        assert!(events.contains(&MantleEvent::PlateConsumed { plate_id: ocean, cause: ConsumptionCause::Collision }), "{:?}", events);
+        assert_eq!(events.iter().filter(|event| matches!(event, MantleEvent::PlateConsumed { .. })).count(), 1);
+        assert!(mantle.plate(ocean).is_none() && mantle.plate(continent).is_some());
+    }
+
+    #[test]
+    fn plumes_cluster_on_the_rim_of_an_llsvp() {
+        let llsvp = Llsvp { lat_deg: 0.0, lon_deg: 0.0, radius_km: 3_000.0 };
+        let near_rim = |llsvps: Vec<Llsvp>| {
+            let mut mantle = earth_mantle();
+            mantle.d_prime_prime.llsvps = llsvps;
+            mantle.d_prime_prime.update_heterogeneity();
+            (0..2_000)
+                .map(|_| mantle.plume_site())
+                .filter(|&(lat_deg, lon_deg)| llsvp.edge_distance_km(lat_deg, lon_deg) < LLSVP_EDGE_WIDTH_KM)
+                .count()
+        };
+
+        let uniform = near_rim(Vec::new());
+        let anchored = near_rim(vec![llsvp]);
+        assert!(anchored > 2 * uniform, "{} plumes by the rim against {} with no province", anchored, uniform);
+
+        let mut mantle = earth_mantle();
+        mantle.d_prime_prime.llsvps.clear();
+        mantle.d_prime_prime.add_llsvp(llsvp);
+        assert_eq!(mantle.d_prime_prime.chemical_heterogeneity_factor, llsvp.coverage());
+    }
+
+    #[test]
+    fn decompression_under_ridges_melts_more_and_grows_more_crust() {
+        let melt_after = |pressure_drop_gpa: f32| {
+            let mut asthenosphere = earth_mantle().asthenosphere;
+            asthenosphere.update_melt(pressure_drop_gpa);
+            asthenosphere.apply_tidal_heating(1e9);
+            asthenosphere
+        };
+        let (still, upwelling) = (melt_after(0.0), melt_after(1.0));
+        assert!(upwelling.ambient_melt_pct > still.ambient_melt_pct);
+        assert!(upwelling.partial_melt_pct > still.partial_melt_pct);
+
+        let grown_km = |asthenosphere: &Asthenosphere| {
+            let mut crust = Crust::new(CrustType::Oceanic);
+            let before_km = crust.thickness_km;
+            crust.grow_by_volcanism(asthenosphere.volcanic_supply_km3());
+            crust.thickness_km - before_km
+        };
+        assert!(grown_km(&upwelling) > grown_km(&still));
+
+        let mut spreading = earth_mantle();
+        assert_eq!(spreading.ridge_pressure_drop_gpa(), 0.0);
+        spreading.add_plate(PlateBuilder::new().wilson_stage(WilsonStage::Spreading).velocity(5.0).build());
+        assert!(spreading.ridge_pressure_drop_gpa() > 0.0);
+    }
+
+    #[test]
+    fn seafloor_deepens_with_the_square_root_of_age() {
+        let depth = |age_myr: f32| PlateBuilder::new().crust(CrustType::Oceanic).age_myr(age_myr).build().seafloor_depth_km();
+
+        let subsidence = |age_myr: f32| depth(age_myr) - depth(0.0);
+        assert!((subsidence(16.0) / subsidence(4.0) - 2.0).abs() < 1e-4);
+        assert!((subsidence(10.0) - SUBSIDENCE_KM_PER_SQRT_MYR * 10.0f32.sqrt()).abs() < 1e-4);
+
+        assert!(depth(100.0) > depth(10.0));
+        // Old seafloor flattens out, sinking less than the square root law would have it.
+        assert!(subsidence(100.0) < SUBSIDENCE_KM_PER_SQRT_MYR * 10.0);
+        assert_eq!(PlateBuilder::new().crust(CrustType::Continental).age_myr(100.0).build().seafloor_depth_km(), 0.0);
+    }
+
+    // Sinks one slab from just above the 660 for up to 500 Myr, returning how much reached
+    // the base of the lower mantle and the depths of whatever is still sinking.
+    fn sink_through_barrier(mode: ConvectionMode, volume_km3: f32) -> (f32, Vec<f32>) {
+        let mut mantle = earth_mantle();
+        mantle.transition_zone.set_convection_mode(mode);
+        let boundary_km = mantle.transition_zone.lower_boundary_km;
+        mantle.lower_mantle.slabs = vec![Slab { depth_km: boundary_km - 50.0, volume_km3, temperature_c: 600.0 }];
+
+        let mut arrived_km3 = 0.0;
+        for _ in 0..500 {
+            arrived_km3 += mantle.lower_mantle.sink_slabs(1_000_000.0, &mantle.transition_zone);
+        }
+        (arrived_km3, mantle.lower_mantle.slabs.iter().map(|slab| slab.depth_km).collect())
+    }
+
+    #[test]
+    fn slabs_stall_at_a_closed_660_and_sink_through_an_open_one() {
+        let mantle = earth_mantle();
+        let boundary_km = mantle.transition_zone.lower_boundary_km;
+        // Big enough to break through Earth's partial barrier in its stiff lower mantle.
+        let volume_km3 = SLAB_PENETRATION_VOLUME_KM3 * 20.0;
+
+        let (arrived_km3, depths) = sink_through_barrier(ConvectionMode::Layered, volume_km3);
+        assert_eq!(arrived_km3, 0.0);
+        assert_eq!(depths, [boundary_km]);
+
+        let (arrived_km3, depths) = sink_through_barrier(ConvectionMode::WholeMantle, volume_km3);
+        assert_eq!(arrived_km3, volume_km3);
+        assert!(depths.is_empty());
+
+        let mut layered = earth_mantle();
+        layered.transition_zone.set_convection_mode(ConvectionMode::Layered);
+        assert_eq!(layered.transition_zone.barrier_strength(), 1.0);
+        assert_eq!(layered.transition_zone.convection_mode(), ConvectionMode::Layered);
+        assert!(mantle.transition_zone.barrier_strength() < 1.0);
+        assert_eq!(mantle.transition_zone.convection_mode(), ConvectionMode::WholeMantle);
+    }
+
+    #[test]
+    fn boundaries_are_classified_by_relative_motion() {
+        let mut converging = earth_mantle();
+        let (a, b) = converging_pair(&mut converging, CrustType::Oceanic, CrustType::Oceanic);
+        assert_eq!(converging.classify_boundaries(), vec![BoundaryClass { plate_id: a, other_plate_id: b, kind: BoundaryType::Convergent, length_km: 10_000_000.0f32.sqrt() }]);
+
+        let mut diverging = earth_mantle();
+        let (a, b) = converging_pair(&mut diverging, CrustType::Oceanic, CrustType::Oceanic);
+        diverging.plate_mut(a).unwrap().motion_direction_deg = 180.0;
+        diverging.plate_mut(b).unwrap().motion_direction_deg = 0.0;
+        let boundaries = diverging.classify_boundaries();
+        assert_eq!(boundaries.len(), 1);
+        assert_eq!(boundaries[0].kind, BoundaryType::Divergent);
+        assert_eq!(diverging.boundary_length_km(BoundaryType::Divergent), boundaries[0].length_km);
+        assert_eq!(diverging.boundary_length_km(BoundaryType::Convergent), 0.0);
+
+        let mut sliding = earth_mantle();
+        let (a, b) = converging_pair(&mut sliding, CrustType::Oceanic, CrustType::Oceanic);
+        sliding.plate_mut(a).unwrap().motion_direction_deg = 90.0;
+        sliding.plate_mut(b).unwrap().motion_direction_deg = 270.0;
+        assert_eq!(sliding.classify_boundaries()[0].kind, BoundaryType::Transform);
+
+        let mut apart = earth_mantle();
+        apart.add_plate(PlateBuilder::new().center(0.0, 0.0).build());
+        apart.add_plate(PlateBuilder::new().center(0.0, 20.0).build());
+        assert!(apart.classify_boundaries().is_empty());
+    }
+
+    #[test]
+    fn heat_flux_summed_through_the_layers_matches_the_fields() {
+        let mut mantle = earth_mantle();
+        for _ in 0..5 {
+            mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
+        }
+
+        let layers = mantle.layers();
+        assert_eq!(layers.len(), 5);
+        assert_eq!(layers[0].thickness_km(), mantle.lithosphere.thickness_km);
+        assert_eq!(layers[4].temperature_c(), mantle.d_prime_prime.temperature_c);
+
+        let through_trait = layers.iter().fold(0.0, |total, layer| total + layer.heat_flux_out());
+        let by_hand = mantle.lithosphere.heat_flux_out
+            + mantle.asthenosphere.heat_flux_out
+            + mantle.transition_zone.heat_flux_out
+            + mantle.lower_mantle.heat_flux_out
+            + mantle.d_prime_prime.heat_flux_out;
+        assert_eq!(through_trait, by_hand);
+        assert!(through_trait > 0.0);
+
+        let into_layers: f32 = layers.iter().map(|layer| layer.heat_flux_in()).sum();
+        assert_eq!(
+            into_layers,
+            mantle.lithosphere.heat_flux_in
+                + mantle.asthenosphere.heat_flux_in
+                + mantle.transition_zone.heat_flux_in
+                + mantle.lower_mantle.heat_flux_in
+                + mantle.d_prime_prime.heat_flux_in
+        );
+    }
+
+    #[test]
+    fn the_crust_budget_accounts_for_every_change_in_volume() {
+        let mut mantle = earth_mantle();
+        converging_pair(&mut mantle, CrustType::Oceanic, CrustType::Continental);
+        mantle.add_plate(PlateBuilder::new().center(-30.0, 90.0).wilson_stage(WilsonStage::Spreading).build());
+        mantle.hot_spots.push(hot_spot_at(-30.0, 90.0));
+
+        let volume_before_km3 = mantle.crustal_volume_km3();
+        for _ in 0..30 {
+            mantle.update_advanced_dynamics(1_000_000.0, &Limits::default());
+        }
+        let budget = mantle.crustal_mass_budget();
+        assert!(budget.created_km3 > 0.0 && budget.recycled_km3 > 0.0 && budget.eroded_km3 > 0.0);
+
+        let change_km3 = mantle.crustal_volume_km3() - volume_before_km3;
+        assert!((budget.net_km3() - change_km3).abs() <= 1e-6 * volume_before_km3, "{} vs {}", budget.net_km3(), change_km3);
+    }
+
+    #[test]
+    fn a_plume_head_floods_far_more_crust_than_a_hot_spot() {
+        let mut mantle = earth_mantle();
+        let plate = mantle.add_plate(PlateBuilder::new().center(0.0, 0.0).build());
+        let lithosphere_base_km = mantle.lithosphere.thickness_km;
+        mantle.plumes.push(Plume {
+            lat_deg: 0.0,
+            lon_deg: 0.0,
+            origin_depth_km: 2_800.0,
+            depth_km: lithosphere_base_km + 1.0,
+            buoyancy: 0.5,
+            head_volume_km3: MAX_PLUME_HEAD_VOLUME_KM3 * 0.5,
+        });
+
+        let events = mantle.ascend_plumes(1_000_000.0);
+        let lip_km = events
+            .iter()
+            .find_map(|event| match *event {
+                MantleEvent::LargeIgneousProvince { plate_id, km, .. } if plate_id == plate => Some(km),
+                _ => None,
+            })
+            .expect("the plume head erupted");
+        assert!(mantle.plumes.is_empty());
+        assert_eq!(mantle.hot_spots.len(), 1);
+
+        // The tail it left behind now feeds the plate at the ordinary hot-spot rate.
+        let ordinary_km: f32 = mantle
+            .feed_hot_spot_volcanism(1_000_000.0)
+            .iter()
+            .map(|event| match *event {
+                MantleEvent::VolcanicGrowth { km, .. } => km,
+                _ => 0.0,
+            })
+            .sum();
+        assert!(ordinary_km > 0.0);
+        assert!(lip_km > 10.0 * ordinary_km, "LIP {} km vs {} km", lip_km, ordinary_km);
+    }
+
+    #[test]
+    fn dehydrating_the_transition_zone_weakens_the_asthenosphere() {
+        let mut dry = earth_mantle();
+        let plate = dry.add_plate(PlateBuilder::new().center(10.0, 30.0).build());
+        let mut wet = dry.clone();
+
+        let released = wet.dehydrate_transition_zone(0.5);
+        assert!(released > 0.0);
+        assert_eq!(wet.transition_zone.water_storage_capacity, dry.transition_zone.water_storage_capacity - released);
+        assert!(wet.asthenosphere.volatile_content_pct > dry.asthenosphere.volatile_content_pct);
+        assert!(wet.asthenosphere.viscosity < dry.asthenosphere.viscosity);
+        assert!(wet.asthenosphere.lubrication_factor > dry.asthenosphere.lubrication_factor);
+
+        dry.update_plate_motions(100_000.0);
+        wet.update_plate_motions(100_000.0);
+        let speed = |mantle: &Mantle| mantle.plate(plate).unwrap().velocity_cm_per_year;
+        assert!(speed(&wet) > speed(&dry), "{} vs {} cm/yr", speed(&wet), speed(&dry));
+    }
+
+    #[test]
+    fn a_heated_lower_mantle_runs_thinner_and_convects_harder() {
+        let mut lower = earth_mantle().lower_mantle;
+        lower.deep_convection_strength = 0.5;
+        lower.update_viscosity(LOWER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        let (viscosity, convection) = (lower.viscosity, lower.deep_convection_strength);
+
+        lower.temperature_c += 200.0;
+        lower.update_viscosity(LOWER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        assert!(lower.viscosity < viscosity, "{:e} vs {:e}", lower.viscosity, viscosity);
+        assert!(lower.deep_convection_strength > convection);
+
+        // Cooling back down undoes it.
+        lower.temperature_c -= 200.0;
+        lower.update_viscosity(LOWER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        assert!((lower.viscosity / viscosity - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn every_heated_layer_loses_viscosity() {
+        let mut mantle = earth_mantle();
+        let before = [mantle.lithosphere.viscosity, mantle.transition_zone.viscosity, mantle.d_prime_prime.viscosity];
+        mantle.lithosphere.temperature_c += 100.0;
+        mantle.transition_zone.temperature_c += 100.0;
+        mantle.d_prime_prime.temperature_c += 100.0;
+        mantle.lithosphere.update_viscosity(UPPER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        mantle.transition_zone.update_viscosity(UPPER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        mantle.d_prime_prime.update_viscosity(LOWER_MANTLE_ACTIVATION_ENERGY_J_PER_MOL);
+        let after = [mantle.lithosphere.viscosity, mantle.transition_zone.viscosity, mantle.d_prime_prime.viscosity];
+        assert!(before.iter().zip(&after).all(|(before, after)| after < before), "{:?} -> {:?}", before, after);
+    }
+
+    #[test]
+    fn a_hot_spot_on_a_spreading_margin_erupts_more() {
+        let growth_with_hot_spot_at = |lon_deg: f32| {
+            let mut mantle = earth_mantle();
+            let plate = mantle.add_plate(PlateBuilder::new().area_km2(1e7).center(0.0, 0.0).build());
+            mantle.hot_spots.push(hot_spot_at(0.0, lon_deg));
+            let on_boundary = mantle.hotspot_on_boundary(0);
+            let events = mantle.feed_hot_spot_volcanism(1_000_000.0);
+            assert!(events.iter().all(|event| matches!(event, MantleEvent::VolcanicGrowth { plate_id, .. } if *plate_id == plate)));
+            let volume_km3: f32 = events
+                .iter()
+                .map(|event| match *event {
+                    MantleEvent::VolcanicGrowth { volume_km3, .. } => volume_km3,
+                    _ => 0.0,
+                })
+                .sum();
+            (on_boundary.is_some_and(|id| id == plate), volume_km3)
+        };
+
+        // The plate reaches about 1,800 km from its centre, so 15° out is on its margin.
+        let (mid_plate, interior_km3) = growth_with_hot_spot_at(0.0);
+        let (on_ridge, ridge_km3) = growth_with_hot_spot_at(15.0);
+        assert!(!mid_plate && on_ridge);
+        assert_eq!(ridge_km3, interior_km3 * RIDGE_HOT_SPOT_AMPLIFICATION);
+
+        // A quarter of the way round the planet it is nowhere near the plate, margin or not.
+        let (far_off, far_km3) = growth_with_hot_spot_at(90.0);
+        assert!(!far_off);
+        assert_eq!(far_km3, interior_km3);
+    }
+
+    #[test]
+    fn d_prime_prime_warms_in_proportion_to_the_step_length() {
+        let core_radius_km = PlanetConfig::earth().core_radius_km;
+        let warming = |years: f32| {
+            let mut layer = earth_mantle().d_prime_prime;
+            let before = layer.temperature_c;
+            layer.transfer_heat(0.09, layer.consumed_flux_generation + 1, 0.05, core_radius_km, years).unwrap();
+            layer.temperature_c - before
+        };
+
+        let (short, long) = (warming(1_000_000.0), warming(2_000_000.0));
+        assert!(short > 0.0, "a net inflow did not warm the layer: {short}");
+        assert!((long / short - 2.0).abs() < 1e-3, "doubling the step gave {long} against {short}");
+
+        let mut layer = earth_mantle().d_prime_prime;
+        let before = layer.temperature_c;
+        layer.transfer_heat(0.05, layer.consumed_flux_generation + 1, 0.05, core_radius_km, 1_000_000.0).unwrap();
+        assert_eq!(layer.temperature_c, before);
     }
 }
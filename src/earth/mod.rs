@@ -0,0 +1,764 @@
+pub mod atmosphere;
+pub mod inner_core;
+pub mod outer_core;
+pub mod mantle;
+pub mod monte_carlo;
+pub mod observer;
+pub mod crust;
+pub mod error;
+pub mod fingerprint;
+pub mod ocean;
+pub mod planet;
+pub mod rotation;
+pub mod scenario;
+pub mod seismic;
+pub mod thermal;
+pub mod geo;
+pub mod grid;
+pub mod limits;
+pub mod simulation;
+pub mod snapshot;
+pub mod telemetry;
+pub mod units;
+pub mod validation;
+pub mod volatiles;
+
+use atmosphere::Atmosphere;
+use error::WorldSimError;
+use inner_core::InnerCore;
+use limits::Limits;
+use outer_core::OuterCore;
+use mantle::{Eruption, Mantle, MantleEvent, Plate};
+use observer::EarthObserver;
+use ocean::Ocean;
+use planet::PlanetConfig;
+use rotation::{InertiaTensor, REFERENCE_SPIN_AXIS};
+use seismic::{p_wave_velocity_km_per_s, Material, RadialSample};
+use snapshot::EarthSnapshot;
+use thermal::{
+    diffuse_layers, ConductiveLayer, CORE_HEAT_CAPACITY_J_PER_KG_K, DEFAULT_SURFACE_TEMPERATURE_C,
+    DEFAULT_THERMAL_CONDUCTIVITY_W_PER_M_K, MANTLE_HEAT_CAPACITY_J_PER_KG_K,
+};
+use units::{km_to_m, years_to_myr, YEARS_PER_MYR};
+use validation::ValidationError;
+
+// Relative mismatch tolerated between the heat one layer hands off and the next receives.
+const HEAT_HANDOFF_TOLERANCE: f64 = 1e-6;
+
+// Density contrasts (kg/m³) of the mass anomalies that tilt the planet off its axis: the hot
+// rock of a rising plume head and the dense piles of the large low-shear-velocity provinces.
+const PLUME_DENSITY_DEFICIT_KG_M3: f32 = 30.0;
+const LLSVP_DENSITY_EXCESS_KG_M3: f32 = 50.0;
+
+// Cloning forks a state, random generators included, so both copies evolve identically
+// until driven differently. Equality compares every field exactly, floats included, with no
+// tolerance.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Earth {
+    pub planet: PlanetConfig,
+    // Absolute age of the planet, which sets how bright its star is.
+    pub age_years: f64,
+    // Time simulated since the planet was built, advanced once per step. Every layer's clock
+    // and the planet's age are read from it, so none can drift from the others.
+    pub absolute_time_myr: f64,
+    pub inner_core: InnerCore,
+    pub outer_core: OuterCore,
+    pub mantle: Mantle,
+    pub ocean: Ocean,
+    pub atmosphere: Atmosphere,
+    pub thermal_conductivity_w_per_m_k: f32,
+    // Bounds every update holds its layer within; see `Limits`.
+    pub limits: Limits,
+}
+
+impl Earth {
+    // Seeding from the operating system's entropy needs `std`; without it, build the planet
+    // with one of the seeded constructors instead.
+    #[cfg(feature = "std")]
+    pub fn new() -> Self {
+        Self::with_planet(PlanetConfig::earth())
+    }
+
+    pub fn new_seeded(seed: u64) -> Self {
+        Self::with_planet_seeded(PlanetConfig::earth(), seed)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn with_planet(planet: PlanetConfig) -> Self {
+        let inner_core = InnerCore::new(&planet);
+        let outer_core = OuterCore::new(&inner_core, &planet);
+        let mantle = Mantle::new(&planet);
+        let age_years = planet.start_age_years;
+
+        Self {
+            planet,
+            age_years,
+            absolute_time_myr: 0.0,
+            inner_core,
+            outer_core,
+            mantle,
+            ocean: Ocean::new(),
+            atmosphere: Atmosphere::new(),
+            thermal_conductivity_w_per_m_k: DEFAULT_THERMAL_CONDUCTIVITY_W_PER_M_K,
+            limits: Limits::default(),
+        }
+    }
+
+    // As `with_planet`, but refuses a planet whose starting state fails validation.
+    #[cfg(feature = "std")]
+    pub fn try_with_planet(planet: PlanetConfig) -> Result<Self, WorldSimError> {
+        let earth = Self::with_planet(planet);
+        earth.validate()?;
+        Ok(earth)
+    }
+
+    pub fn with_planet_seeded(planet: PlanetConfig, seed: u64) -> Self {
+        let inner_core = InnerCore::new(&planet);
+        let outer_core = OuterCore::new_seeded(&inner_core, &planet, seed);
+        let mantle = Mantle::new_seeded(&planet, seed.wrapping_add(1));
+        let age_years = planet.start_age_years;
+
+        Self {
+            planet,
+            age_years,
+            absolute_time_myr: 0.0,
+            inner_core,
+            outer_core,
+            mantle,
+            ocean: Ocean::new(),
+            atmosphere: Atmosphere::new(),
+            thermal_conductivity_w_per_m_k: DEFAULT_THERMAL_CONDUCTIVITY_W_PER_M_K,
+            limits: Limits::default(),
+        }
+    }
+
+    // Validates every layer that can check itself, along with each plate and the limits, and
+    // reports all the violations together.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let results = [self.inner_core.validate(), self.outer_core.validate(), self.limits.validate()];
+        for result in results.into_iter().chain(self.mantle.plates.iter().map(Plate::validate)) {
+            errors.extend(result.err().unwrap_or_default());
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Back to the state the constructor built for this planet, with each random stream
+    // rewound rather than reseeded, so the run that follows repeats the first one exactly.
+    // The limits and thermal conductivity are settings rather than state, so any the caller
+    // changed are kept.
+    pub fn reset(&mut self) {
+        self.age_years = self.planet.start_age_years;
+        self.absolute_time_myr = 0.0;
+        self.inner_core.reset(&self.planet);
+        self.outer_core.reset(&self.inner_core, &self.planet);
+        self.mantle.reset(&self.planet);
+        self.ocean.reset();
+        self.atmosphere.reset();
+    }
+
+    // As `reset`, but starting the random streams over from a new seed.
+    pub fn reset_seeded(&mut self, seed: u64) {
+        let (limits, thermal_conductivity_w_per_m_k) = (self.limits, self.thermal_conductivity_w_per_m_k);
+        *self = Self::with_planet_seeded(self.planet.clone(), seed);
+        self.limits = limits;
+        self.thermal_conductivity_w_per_m_k = thermal_conductivity_w_per_m_k;
+    }
+
+    // Fails only if the layers ran out of order, in which case the step stops part way and
+    // the state should be thrown away.
+    pub fn step(&mut self, years: f32) -> Result<Vec<MantleEvent>, WorldSimError> {
+        self.step_with(years, None)
+    }
+
+    // As `step`, telling the observer about reversals, subductions and eruptions as they happen.
+    pub fn step_observed(&mut self, years: f32, observer: &mut dyn EarthObserver) -> Result<Vec<MantleEvent>, WorldSimError> {
+        self.step_with(years, Some(observer))
+    }
+
+    fn step_with(&mut self, years: f32, observer: Option<&mut dyn EarthObserver>) -> Result<Vec<MantleEvent>, WorldSimError> {
+        let reversals_before = self.outer_core.reversal_history.len();
+        self.absolute_time_myr += years_to_myr(years) as f64;
+        self.age_years = self.planet.start_age_years + self.absolute_time_myr * YEARS_PER_MYR as f64;
+        let time_myr = self.absolute_time_myr as f32;
+
+        self.inner_core.update_crystallization(years, time_myr);
+        self.outer_core.update_dynamics(&self.inner_core, years, time_myr);
+        let events = self.mantle.update_advanced_dynamics(years, &self.limits);
+        self.mantle.record_features(&events, time_myr);
+        self.update_climate(years, &events);
+
+        // The D'' layer takes whatever the outer core just delivered and passes on
+        // what the lower mantle above it draws off. A flux left over from an earlier step
+        // means the updates above ran out of order, which must not pass silently.
+        self.mantle.d_prime_prime.transfer_heat(
+            self.outer_core.heat_flux_to_mantle_w_per_m2,
+            self.outer_core.heat_flux_generation,
+            self.mantle.lower_mantle.heat_flux_in,
+            self.outer_core.core_radius_km,
+            years,
+        )?;
+        // Only checked in debug builds: a mismatch here is a bug in the layer updates, not
+        // something a caller can act on, so release builds skip the bookkeeping.
+        debug_assert!(self.heat_handoff_is_consistent(), "heat handoff between core layers is out of balance");
+        self.conduct_heat(years);
+        self.mantle.decay_radiogenic(years);
+
+        let new_ridge_area_km2: f32 = events
+            .iter()
+            .map(|event| match *event {
+                MantleEvent::Spreading { area_km2, .. } | MantleEvent::BackArcSpreading { area_km2, .. } => area_km2,
+                _ => 0.0,
+            })
+            .sum();
+        self.ocean.subside(years);
+        self.ocean.adjust_for_ridge_volume(new_ridge_area_km2);
+
+        if let Some(observer) = observer {
+            for &t_myr in &self.outer_core.reversal_history[reversals_before..] {
+                observer.on_reversal(t_myr);
+            }
+            for event in &events {
+                match *event {
+                    MantleEvent::SubductionStarted { plate_id } => observer.on_subduction_start(plate_id),
+                    MantleEvent::VolcanicEruption { plate_id, vei, volume_km3 } => {
+                        observer.on_eruption(&Eruption { plate_id, vei, volume_km3 })
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    // Magma reaching the surface this step outgasses CO2 and weathering at the current mean
+    // surface temperature draws it down. Each plate's surface then settles toward the energy
+    // balance for the sunlight at the planet's current age under the resulting greenhouse, so
+    // a young planet under a faint star needs more CO2 to stay unfrozen.
+    fn update_climate(&mut self, years: f32, events: &[MantleEvent]) {
+        let magma_km3: f32 = events
+            .iter()
+            .map(|event| match *event {
+                MantleEvent::VolcanicGrowth { volume_km3, .. }
+                | MantleEvent::VolcanicEruption { volume_km3, .. }
+                | MantleEvent::LargeIgneousProvince { volume_km3, .. } => volume_km3,
+                _ => 0.0,
+            })
+            .sum();
+        self.atmosphere.outgas(magma_km3);
+        let (surface_temperature_c, _) = self.mean_surface();
+        self.atmosphere.weather(years, surface_temperature_c);
+
+        let greenhouse_factor = self.atmosphere.greenhouse_factor(self.planet.greenhouse_factor);
+        let solar_constant_w_per_m2 = self.planet.solar_constant_at(self.age_years);
+        for plate in &mut self.mantle.plates {
+            plate.crust.update_surface_temperature(years, solar_constant_w_per_m2, greenhouse_factor, &self.limits);
+        }
+    }
+
+    // Relaxes layer temperatures toward a conductive profile, from the inner core up to the
+    // lithosphere, cooling against the crust's mean surface temperature through its thickness
+    // while the silicate layers warm by their radiogenic heat.
+    fn conduct_heat(&mut self, years: f32) {
+        let inner_volume_m3 = thermal::shell_volume_m3(0.0, self.inner_core.radius_km);
+        let mantle = &self.mantle;
+        // The crust has no cell of its own, so its radiogenic heat warms the lithosphere below.
+        let lithosphere_kg_per_m2 = mantle.lithosphere.density as f64 * km_to_m(mantle.lithosphere.thickness_km as f64);
+        let lithosphere_heat_w_per_kg =
+            mantle.lithosphere.radiogenic_heat_w_per_kg.total() + mantle.crust_radiogenic_w_per_m2() / lithosphere_kg_per_m2;
+        let mut layers = [
+            (self.inner_core.radius_km, self.inner_core.temperature_c, (self.inner_core.mass_kg / inner_volume_m3) as f32, CORE_HEAT_CAPACITY_J_PER_KG_K, 0.0),
+            (self.outer_core.thickness_km, self.outer_core.temperature_c, self.outer_core.density, CORE_HEAT_CAPACITY_J_PER_KG_K, 0.0),
+            (mantle.d_prime_prime.thickness_km, mantle.d_prime_prime.temperature_c, mantle.d_prime_prime.density, MANTLE_HEAT_CAPACITY_J_PER_KG_K, mantle.d_prime_prime.radiogenic_heat_w_per_kg.total()),
+            (mantle.lower_mantle.thickness_km, mantle.lower_mantle.temperature_c, mantle.lower_mantle.density, MANTLE_HEAT_CAPACITY_J_PER_KG_K, mantle.lower_mantle.radiogenic_heat_w_per_kg.total()),
+            (mantle.transition_zone.thickness_km, mantle.transition_zone.temperature_c, mantle.transition_zone.density, MANTLE_HEAT_CAPACITY_J_PER_KG_K, mantle.transition_zone.radiogenic_heat_w_per_kg.total()),
+            (mantle.asthenosphere.thickness_km, mantle.asthenosphere.temperature_c, mantle.asthenosphere.density, MANTLE_HEAT_CAPACITY_J_PER_KG_K, mantle.asthenosphere.radiogenic_heat_w_per_kg.total()),
+            (mantle.lithosphere.thickness_km, mantle.lithosphere.temperature_c, mantle.lithosphere.density, MANTLE_HEAT_CAPACITY_J_PER_KG_K, lithosphere_heat_w_per_kg),
+        ]
+        .map(|(thickness_km, temperature_c, density, heat_capacity_j_per_kg_k, heat_production_w_per_kg)| ConductiveLayer {
+            thickness_km,
+            temperature_c,
+            density,
+            heat_capacity_j_per_kg_k,
+            heat_production_w_per_kg,
+        });
+
+        let (surface_temperature_c, crust_km) = self.mean_surface();
+        diffuse_layers(&mut layers, self.thermal_conductivity_w_per_m_k, surface_temperature_c, crust_km, years);
+
+        let [inner, outer, d_prime_prime, lower, transition, astheno, litho] = layers.map(|layer| layer.temperature_c);
+        self.inner_core.temperature_c = inner;
+        self.outer_core.temperature_c = outer;
+        self.mantle.d_prime_prime.temperature_c = d_prime_prime;
+        self.mantle.lower_mantle.temperature_c = lower;
+        self.mantle.transition_zone.temperature_c = transition;
+        self.mantle.asthenosphere.temperature_c = astheno;
+        self.mantle.lithosphere.temperature_c = litho;
+    }
+
+    // Area-weighted surface temperature and crust thickness over all plates.
+    fn mean_surface(&self) -> (f32, f32) {
+        let plates = &self.mantle.plates;
+        let plate_area_km2: f32 = plates.iter().map(|plate| plate.area_km2).sum();
+        if plate_area_km2 <= 0.0 {
+            return (DEFAULT_SURFACE_TEMPERATURE_C, 0.0);
+        }
+        let weighted = |value: fn(&mantle::Plate) -> f32| {
+            plates.iter().map(|plate| value(plate) * plate.area_km2).sum::<f32>() / plate_area_km2
+        };
+        (weighted(|plate| plate.crust.surface_temperature_c), weighted(|plate| plate.crust.thickness_km))
+    }
+
+    // Geotherm: each layer's temperature is taken to hold at the middle of its depth range,
+    // and the inner core's at the centre, with straight lines in between and up to the
+    // surface temperature at the top. Layers are stacked downward from the mean crust.
+    // Depths outside the planet are clamped to the surface or centre.
+    pub fn temperature_at_depth_km(&self, depth_km: f32) -> f32 {
+        let (surface_temperature_c, crust_km) = self.mean_surface();
+        let mantle = &self.mantle;
+        let mut profile = vec![(0.0, surface_temperature_c)];
+        let mut top_km = 0.0;
+        for (thickness_km, temperature_c) in [
+            (crust_km, surface_temperature_c),
+            (mantle.lithosphere.thickness_km, mantle.lithosphere.temperature_c),
+            (mantle.asthenosphere.thickness_km, mantle.asthenosphere.temperature_c),
+            (mantle.transition_zone.thickness_km, mantle.transition_zone.temperature_c),
+            (mantle.lower_mantle.thickness_km, mantle.lower_mantle.temperature_c),
+            (mantle.d_prime_prime.thickness_km, mantle.d_prime_prime.temperature_c),
+            (self.outer_core.thickness_km, self.outer_core.temperature_c),
+        ] {
+            profile.push((top_km + thickness_km / 2.0, temperature_c));
+            top_km += thickness_km;
+        }
+        let centre_km = self.planet.radius_km.max(top_km);
+        profile.push((centre_km, self.inner_core.temperature_c));
+
+        let depth_km = depth_km.clamp(0.0, centre_km);
+        profile
+            .windows(2)
+            .find(|pair| depth_km <= pair[1].0)
+            .map(|pair| {
+                let ((upper_km, upper_c), (lower_km, lower_c)) = (pair[0], pair[1]);
+                if lower_km <= upper_km {
+                    return lower_c;
+                }
+                upper_c + (lower_c - upper_c) * (depth_km - upper_km) / (lower_km - upper_km)
+            })
+            .unwrap_or(self.inner_core.temperature_c)
+    }
+
+    // Density and P-wave velocity at n_samples radii spaced evenly from the centre to the
+    // surface, in PREM's order. Each shell is uniform at its own density, with a Birch's-law
+    // velocity shifted by how far the shell has drifted from its starting temperature. The
+    // crust, lithosphere, asthenosphere and transition zone stack down from the surface,
+    // D'' sits on the core and the lower mantle fills the gap between.
+    pub fn radial_profile(&self, n_samples: usize) -> Vec<RadialSample> {
+        let (_, crust_km) = self.mean_surface();
+        let plates = &self.mantle.plates;
+        let plate_area_km2: f32 = plates.iter().map(|plate| plate.area_km2).sum();
+        let crust_density = if plate_area_km2 > 0.0 {
+            plates.iter().map(|plate| plate.crust.average_density * plate.area_km2).sum::<f32>() / plate_area_km2
+        } else {
+            0.0
+        };
+
+        let mantle = &self.mantle;
+        let upper_mantle_km = crust_km + mantle.lithosphere.thickness_km + mantle.asthenosphere.thickness_km + mantle.transition_zone.thickness_km;
+        let lower_mantle_km = (self.planet.mantle_depth_km() - upper_mantle_km - mantle.d_prime_prime.thickness_km).max(0.0);
+        let inner_core_radius_km = self.inner_core.radius_km.min(self.planet.core_radius_km);
+        let anomaly = |temperature_c: f32, reference_c: f32| temperature_c - reference_c;
+
+        // (thickness, density, material, temperature anomaly) from the surface inward.
+        let layers = [
+            (crust_km, crust_density, Material::Silicate, 0.0),
+            (mantle.lithosphere.thickness_km, mantle.lithosphere.density, Material::Silicate, anomaly(mantle.lithosphere.temperature_c, mantle.lithosphere.reference_temperature_c)),
+            (mantle.asthenosphere.thickness_km, mantle.asthenosphere.density, Material::Silicate, anomaly(mantle.asthenosphere.temperature_c, mantle.asthenosphere.reference_temperature_c)),
+            (mantle.transition_zone.thickness_km, mantle.transition_zone.density, Material::Silicate, anomaly(mantle.transition_zone.temperature_c, mantle.transition_zone.reference_temperature_c)),
+            (lower_mantle_km, mantle.lower_mantle.density, Material::Silicate, anomaly(mantle.lower_mantle.temperature_c, mantle.lower_mantle.reference_temperature_c)),
+            (mantle.d_prime_prime.thickness_km, mantle.d_prime_prime.density, Material::Silicate, anomaly(mantle.d_prime_prime.temperature_c, mantle.d_prime_prime.reference_temperature_c)),
+            (self.planet.core_radius_km - inner_core_radius_km, self.planet.outer_core_density, Material::Iron, anomaly(self.outer_core.temperature_c, self.planet.outer_core_temperature_c)),
+            (inner_core_radius_km, self.inner_core.density, Material::Iron, anomaly(self.inner_core.temperature_c, self.planet.inner_core_temperature_c)),
+        ];
+
+        // Bottom radius of each shell with any thickness, with its density and velocity.
+        let mut shells = Vec::new();
+        let mut top_km = self.planet.radius_km;
+        for (thickness_km, density, material, anomaly_c) in layers {
+            if thickness_km <= 0.0 || top_km <= 0.0 {
+                continue;
+            }
+            top_km = (top_km - thickness_km).max(0.0);
+            shells.push((top_km, density, p_wave_velocity_km_per_s(material, density, anomaly_c)));
+        }
+
+        (0..n_samples)
+            .map(|i| {
+                let radius_km = if n_samples > 1 { self.planet.radius_km * i as f32 / (n_samples - 1) as f32 } else { self.planet.radius_km };
+                let (density, p_velocity_km_per_s) = shells
+                    .iter()
+                    .find(|shell| radius_km >= shell.0)
+                    .or(shells.last())
+                    .map_or((0.0, 0.0), |&(_, density, velocity)| (density, velocity));
+                RadialSample { radius_km, density, p_velocity_km_per_s }
+            })
+            .collect()
+    }
+
+    // The cores and a mantle of uniform mean density as spherical shells, plus the masses that
+    // break the symmetry: each plate's crust at its centre, the deficit of every rising plume
+    // head, and the excess of each province pile on the core.
+    pub fn moment_of_inertia(&self) -> InertiaTensor {
+        let mut tensor = [[0.0; 3]; 3];
+        let inner_core_radius_km = self.inner_core.radius_km.min(self.planet.core_radius_km);
+        rotation::add_shell(&mut tensor, 0.0, inner_core_radius_km, self.inner_core.density);
+        rotation::add_shell(&mut tensor, inner_core_radius_km, self.planet.core_radius_km, self.planet.outer_core_density);
+        rotation::add_shell(&mut tensor, self.planet.core_radius_km, self.planet.radius_km, self.planet.mean_mantle_density());
+
+        for plate in &self.mantle.plates {
+            let mass_kg = plate.crust_volume_km3() * km_to_m(1.0).powi(3) * plate.crust.average_density as f64;
+            rotation::add_point_mass(&mut tensor, mass_kg, self.planet.radius_km, plate.center_lat_deg, plate.center_lon_deg);
+        }
+        for plume in &self.mantle.plumes {
+            let mass_kg = -(plume.head_volume_km3 as f64) * km_to_m(1.0).powi(3) * PLUME_DENSITY_DEFICIT_KG_M3 as f64;
+            rotation::add_point_mass(&mut tensor, mass_kg, self.planet.radius_km - plume.depth_km, plume.lat_deg, plume.lon_deg);
+        }
+        let d_prime_prime = &self.mantle.d_prime_prime;
+        let pile_radius_km = self.planet.core_radius_km + d_prime_prime.thickness_km / 2.0;
+        for llsvp in &d_prime_prime.llsvps {
+            let volume_m3 = llsvp.coverage() as f64 * thermal::sphere_area_m2(pile_radius_km) * km_to_m(d_prime_prime.thickness_km as f64);
+            rotation::add_point_mass(&mut tensor, volume_m3 * LLSVP_DENSITY_EXCESS_KG_M3 as f64, pile_radius_km, llsvp.lat_deg, llsvp.lon_deg);
+        }
+        tensor
+    }
+
+    // True polar wander since `previous`: how far the axis of greatest moment has turned. The
+    // earlier axis is the one nearest the geographic pole, and the new one the nearest to it.
+    pub fn polar_wander_deg(&self, previous: &Self) -> f32 {
+        let before = rotation::principal_axis(&previous.moment_of_inertia(), REFERENCE_SPIN_AXIS);
+        let after = rotation::principal_axis(&self.moment_of_inertia(), before);
+        rotation::angle_between_deg(before, after)
+    }
+
+    pub fn snapshot(&self) -> EarthSnapshot {
+        EarthSnapshot::capture(self)
+    }
+
+    // The snapshot a step would end in, taken from a copy so nothing here changes. The copy
+    // carries the same random streams, so stepping for real afterwards lands on exactly this.
+    pub fn preview_step(&self, years: f32) -> Result<EarthSnapshot, WorldSimError> {
+        let mut preview = self.clone();
+        preview.step(years)?;
+        Ok(preview.snapshot())
+    }
+
+    // Stable hash of the entire state, for catching unintended drift between versions. It is
+    // taken over the `Debug` rendering, so changing any state type's fields changes it too.
+    pub fn state_fingerprint(&self) -> u64 {
+        fingerprint::fingerprint(self)
+    }
+
+    pub fn total_heat_content_tj(&self) -> f64 {
+        self.inner_core.heat_content_tj()
+            + self.outer_core.heat_content_tj()
+            + self.mantle.heat_content_tj(self.outer_core.core_radius_km)
+    }
+
+    // Everything the inner core released must have reached the outer core, everything the
+    // outer core sent on must be accounted for by that inflow plus its own cooling, and the
+    // D'' layer must be fed exactly the flux the outer core delivered. Used by the
+    // debug-only assertion in `step`.
+    fn heat_handoff_is_consistent(&self) -> bool {
+        let within_tolerance = |a: f64, b: f64| (a - b).abs() <= HEAT_HANDOFF_TOLERANCE * a.abs().max(b.abs()).max(1.0);
+
+        let inner_to_outer = within_tolerance(
+            self.inner_core.cumulative_heat_released_tj,
+            self.outer_core.cumulative_heat_from_inner_tj,
+        );
+        let outer_balance = within_tolerance(
+            self.outer_core.cumulative_heat_to_mantle_tj,
+            self.outer_core.cumulative_heat_from_inner_tj + self.outer_core.cumulative_secular_cooling_tj,
+        );
+        let outer_to_mantle = self.outer_core.heat_flux_to_mantle_w_per_m2 == self.mantle.d_prime_prime.heat_flux_in;
+
+        inner_to_outer && outer_balance && outer_to_mantle
+    }
+
+    #[cfg(feature = "std")]
+    pub fn describe(&self) {
+        println!(
+            "Planet: {}, Radius {:.0} km, Gravity {:.2} m/s², Age {:.3} Gyr, Solar constant {:.0} W/m²",
+            self.planet.name,
+            self.planet.radius_km,
+            self.planet.surface_gravity_m_per_s2,
+            self.age_years / 1e9,
+            self.planet.solar_constant_at(self.age_years)
+        );
+        self.inner_core.describe();
+        self.outer_core.describe();
+        self.mantle.describe();
+        self.ocean.describe();
+        println!("  Flooded continental area: {:.1} km²", self.ocean.flooded_continental_area(&self.mantle.plates));
+        println!(
+            "  Plate basins: {:.3e} km³, mean floor depth {:.2} km by age",
+            self.ocean.basin_volume_km3(&self.mantle.plates),
+            self.ocean.mean_basin_depth_km(&self.mantle.plates)
+        );
+        self.atmosphere.describe();
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Earth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crust::CrustType;
+    use mantle::{HotSpot, PlateBuilder};
+
+    #[test]
+    fn inner_core_grows_every_step() {
+        let mut earth = Earth::new_seeded(1);
+        let mut radius_km = earth.inner_core.radius_km;
+        for _ in 0..10 {
+            earth.step(1_000_000.0).unwrap();
+            assert!(earth.inner_core.radius_km > radius_km, "{} did not grow past {}", earth.inner_core.radius_km, radius_km);
+            radius_km = earth.inner_core.radius_km;
+        }
+    }
+
+    #[test]
+    fn the_mantle_never_gets_more_heat_than_the_core_released() {
+        let mut earth = Earth::new_seeded(3);
+        for _ in 0..20 {
+            earth.step(1_000_000.0).unwrap();
+            let outer_core = &earth.outer_core;
+            let released_tj = earth.inner_core.cumulative_heat_released_tj + outer_core.cumulative_secular_cooling_tj;
+            assert!(outer_core.cumulative_heat_to_mantle_tj <= released_tj * (1.0 + HEAT_HANDOFF_TOLERANCE));
+            assert!(earth.heat_handoff_is_consistent());
+        }
+    }
+
+    #[test]
+    fn step_rejects_a_stale_heat_flux() {
+        let mut earth = Earth::new_seeded(7);
+        assert!(earth.step(100_000.0).is_ok());
+
+        // Pretend D'' already took in a flux newer than anything the outer core will deliver.
+        earth.mantle.d_prime_prime.consumed_flux_generation = u64::MAX;
+        assert!(matches!(earth.step(100_000.0), Err(WorldSimError::StaleHeatFlux(_))));
+    }
+
+    #[test]
+    fn the_same_flux_cannot_be_taken_twice() {
+        let mut earth = Earth::new_seeded(7);
+        let generation = earth.mantle.d_prime_prime.consumed_flux_generation + 1;
+        let layer = &mut earth.mantle.d_prime_prime;
+        assert!(layer.transfer_heat(80.0, generation, 60.0, 3_480.0, 1.0).is_ok());
+        let err = layer.transfer_heat(80.0, generation, 60.0, 3_480.0, 1.0).unwrap_err();
+        assert_eq!(err.generation, generation);
+        assert_eq!(err.consumed_generation, generation);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn a_planet_with_an_impossible_core_is_refused() {
+        assert!(Earth::try_with_planet(PlanetConfig::mars()).is_ok());
+
+        let mut frozen_solid = PlanetConfig::earth();
+        frozen_solid.inner_core_temperature_c = -300.0;
+        let err = Earth::try_with_planet(frozen_solid).unwrap_err();
+        assert!(matches!(&err, WorldSimError::OutOfRange(violation) if violation.field == "temperature_c"), "{err}");
+    }
+
+    #[test]
+    fn a_mars_preset_builds_a_smaller_cooler_core() {
+        let earth = Earth::new_seeded(5);
+        let mars = Earth::with_planet_seeded(PlanetConfig::mars(), 5);
+        assert_ne!(mars.inner_core.radius_km, earth.inner_core.radius_km);
+        assert!(mars.inner_core.radius_km < earth.inner_core.radius_km);
+        assert!(mars.outer_core.temperature_c < earth.outer_core.temperature_c);
+        assert_eq!(mars.planet, PlanetConfig::mars());
+    }
+
+    #[test]
+    fn the_geotherm_is_continuous_and_rises_toward_the_centre() {
+        let earth = Earth::new_seeded(2);
+        let radius_km = earth.planet.radius_km;
+        let samples: Vec<f32> = (0..=(radius_km / 10.0) as usize).map(|i| earth.temperature_at_depth_km(i as f32 * 10.0)).collect();
+        assert!(samples.windows(2).all(|pair| pair[1] >= pair[0]), "the geotherm cools somewhere with depth");
+        assert!(samples[samples.len() - 1] > samples[0]);
+
+        let mantle = &earth.mantle;
+        let mut boundary_km = earth.mean_surface().1;
+        for thickness_km in [
+            mantle.lithosphere.thickness_km,
+            mantle.asthenosphere.thickness_km,
+            mantle.transition_zone.thickness_km,
+            mantle.lower_mantle.thickness_km,
+            mantle.d_prime_prime.thickness_km,
+        ] {
+            boundary_km += thickness_km;
+            let jump_c = earth.temperature_at_depth_km(boundary_km + 0.01) - earth.temperature_at_depth_km(boundary_km - 0.01);
+            assert!(jump_c.abs() < 1.0, "{:.1} °C jump at {} km", jump_c, boundary_km);
+        }
+
+        assert_eq!(earth.temperature_at_depth_km(radius_km + 1_000.0), earth.temperature_at_depth_km(radius_km));
+        assert_eq!(earth.temperature_at_depth_km(-5.0), earth.temperature_at_depth_km(0.0));
+    }
+
+    #[test]
+    fn density_jumps_at_the_inner_core_and_core_mantle_boundaries() {
+        let earth = Earth::new_seeded(4);
+        let radius_km = earth.planet.radius_km;
+        let profile = earth.radial_profile(radius_km as usize + 1);
+        assert_eq!(profile.len(), radius_km as usize + 1);
+        assert!(profile.iter().all(|sample| sample.density > 0.0 && sample.p_velocity_km_per_s > 0.0));
+
+        let density_at = |target_km: f32| {
+            profile
+                .iter()
+                .min_by(|a, b| (a.radius_km - target_km).abs().total_cmp(&(b.radius_km - target_km).abs()))
+                .unwrap()
+                .density
+        };
+        let jump = |boundary_km: f32| density_at(boundary_km - 5.0) - density_at(boundary_km + 5.0);
+
+        let inner_core_boundary = jump(earth.inner_core.radius_km);
+        let core_mantle_boundary = jump(earth.planet.core_radius_km);
+        assert!(inner_core_boundary > 0.0, "no jump at the inner-core boundary");
+        assert!(core_mantle_boundary > 4_000.0, "only {} kg/m³ at the core-mantle boundary", core_mantle_boundary);
+        assert!(density_at(0.0) > density_at(radius_km));
+    }
+
+    #[test]
+    fn preview_step_leaves_the_earth_alone() {
+        let mut earth = Earth::new_seeded(6);
+        let untouched = earth.clone();
+        let current = earth.snapshot();
+
+        let preview = earth.preview_step(1_000_000.0).unwrap();
+        assert_eq!(earth, untouched);
+        assert_ne!(preview, current);
+        assert!(!current.diff(&preview).is_empty());
+
+        earth.step(1_000_000.0).unwrap();
+        assert_eq!(earth.snapshot(), preview);
+    }
+
+    #[test]
+    fn reset_restores_a_freshly_built_earth() {
+        let mut earth = Earth::new_seeded(9);
+        for _ in 0..10 {
+            earth.step(1_000_000.0).unwrap();
+        }
+        assert_ne!(earth, Earth::new_seeded(9));
+
+        earth.reset();
+        assert_eq!(earth, Earth::new_seeded(9));
+        assert_eq!(earth.absolute_time_myr, 0.0);
+        assert_eq!(earth.outer_core.total_time_myr, 0.0);
+
+        // The streams were rewound, so the replay matches a fresh run step for step.
+        let mut fresh = Earth::new_seeded(9);
+        for _ in 0..3 {
+            assert_eq!(earth.step(1_000_000.0).unwrap(), fresh.step(1_000_000.0).unwrap());
+        }
+        assert_eq!(earth, fresh);
+
+        earth.reset_seeded(10);
+        assert_eq!(earth, Earth::new_seeded(10));
+    }
+
+    #[test]
+    fn reset_keeps_the_callers_settings() {
+        let mut earth = Earth::new_seeded(9);
+        earth.limits.max_crust_thickness_km = 60.0;
+        earth.thermal_conductivity_w_per_m_k = 3.0;
+        earth.step(1_000_000.0).unwrap();
+
+        earth.reset();
+        assert_eq!(earth.absolute_time_myr, 0.0);
+        assert_eq!((earth.limits.max_crust_thickness_km, earth.thermal_conductivity_w_per_m_k), (60.0, 3.0));
+        earth.reset_seeded(10);
+        assert_eq!((earth.limits.max_crust_thickness_km, earth.thermal_conductivity_w_per_m_k), (60.0, 3.0));
+    }
+
+    #[test]
+    fn every_layer_keeps_the_planet_clock() {
+        let mut earth = Earth::new_seeded(12);
+        let plate = earth.mantle.add_plate(PlateBuilder::new().age_myr(30.0).build());
+        earth.mantle.hot_spots.push(HotSpot { lat_deg: 19.0, lon_deg: -155.0, surface_age_myr: 0.0, track: Vec::new() });
+
+        for years in [1_000_000.0, 250_000.0, 2_500_000.0, 50_000.0] {
+            earth.step(years).unwrap();
+            let time_myr = earth.absolute_time_myr as f32;
+            assert_eq!(earth.outer_core.total_time_myr, time_myr);
+            assert_eq!(earth.inner_core.age_myr, earth.inner_core.initial_age_myr + time_myr);
+            assert!((earth.mantle.plate(plate).unwrap().age_myr - 30.0 - time_myr).abs() < 1e-4);
+            assert!((earth.mantle.hot_spots[0].surface_age_myr - time_myr).abs() < 1e-4);
+        }
+        assert!((earth.absolute_time_myr - 3.8).abs() < 1e-9);
+        assert!((earth.age_years - earth.planet.start_age_years - 3_800_000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn a_builder_euler_pole_survives_stepping() {
+        let mut earth = Earth::new_seeded(14);
+        let plate = earth.mantle.add_plate(PlateBuilder::new().center(10.0, 0.0).euler_pole(60.0, 30.0, 0.5).build());
+
+        for _ in 0..3 {
+            earth.step(1_000_000.0).unwrap();
+            let plate = earth.mantle.plate(plate).unwrap();
+            assert_eq!((plate.euler_pole.lat_deg, plate.euler_pole.lon_deg), (60.0, 30.0));
+            // The scalar motion is read off the pole, not the other way round.
+            assert_eq!((plate.velocity_cm_per_year, plate.motion_direction_deg), plate.velocity_at(plate.center_lat_deg, plate.center_lon_deg));
+            assert!(plate.velocity_cm_per_year > 0.0);
+        }
+    }
+
+    #[test]
+    fn an_off_axis_mass_makes_the_pole_wander() {
+        let mut symmetric = Earth::new_seeded(13);
+        symmetric.mantle.d_prime_prime.llsvps.clear();
+        symmetric.mantle.plumes.clear();
+        assert_eq!(symmetric.polar_wander_deg(&symmetric), 0.0);
+
+        let with_continent_at = |lat_deg: f32| {
+            let mut earth = symmetric.clone();
+            earth.mantle.add_plate(PlateBuilder::new().crust(CrustType::Continental).area_km2(5e7).center(lat_deg, 30.0).build());
+            earth
+        };
+
+        // Spin keeps the mass on the equator, so one there leaves the axis where it was, while
+        // one at 45° drags the axis round until it is.
+        assert!(with_continent_at(0.0).polar_wander_deg(&symmetric) < 1e-3);
+        let wander_deg = with_continent_at(45.0).polar_wander_deg(&symmetric);
+        assert!(wander_deg > 1.0, "only {}° of wander", wander_deg);
+    }
+
+    // Only built without `std`, so it checks that the seeded core steps on its own; run it
+    // with `cargo test --no-default-features`.
+    #[cfg(not(feature = "std"))]
+    #[test]
+    fn the_seeded_core_steps_without_std() {
+        let mut earth = Earth::with_planet_seeded(PlanetConfig::mars(), 21);
+        let mut replay = Earth::with_planet_seeded(PlanetConfig::mars(), 21);
+        for _ in 0..20 {
+            assert_eq!(earth.step(1_000_000.0).unwrap(), replay.step(1_000_000.0).unwrap());
+        }
+        assert_eq!(earth.state_fingerprint(), replay.state_fingerprint());
+        assert!(earth.absolute_time_myr > 19.9);
+        assert!(earth.validate().is_ok());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::earth::error::WorldSimError;
+use crate::earth::scenario::Scenario;
+use crate::earth::simulation::SimulationReport;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Mean and sample standard deviation of one quantity across runs.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SampleStats {
+    pub mean: f32,
+    pub std_dev: f32,
+}
+
+impl SampleStats {
+    // Bessel-corrected, so a single run has no spread rather than an undefined one.
+    pub fn from_samples(samples: &[f32]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let n = samples.len() as f64;
+        let mean = samples.iter().map(|&x| x as f64).sum::<f64>() / n;
+        if samples.len() < 2 {
+            return Self { mean: mean as f32, std_dev: 0.0 };
+        }
+        let variance = samples.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        Self { mean: mean as f32, std_dev: variance.sqrt() as f32 }
+    }
+}
+
+// How the stochastic parts of a scenario (reversals, plumes, eruptions) spread its outcome.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonteCarloStats {
+    pub seeds: Vec<u64>,
+    pub reversals: SampleStats,
+    pub large_igneous_provinces: SampleStats,
+    pub subducted_volume_km3: SampleStats,
+    pub inner_core_radius_km: SampleStats,
+}
+
+impl MonteCarloStats {
+    pub fn runs(&self) -> usize {
+        self.seeds.len()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn describe(&self) {
+        println!("Monte Carlo over {} run(s):", self.runs());
+        println!("  Reversals: {:.2} ± {:.2}", self.reversals.mean, self.reversals.std_dev);
+        println!("  Large igneous provinces: {:.2} ± {:.2}", self.large_igneous_provinces.mean, self.large_igneous_provinces.std_dev);
+        println!("  Subducted: {:.1} ± {:.1} km³", self.subducted_volume_km3.mean, self.subducted_volume_km3.std_dev);
+        println!("  Inner core radius: {:.2} ± {:.2} km", self.inner_core_radius_km.mean, self.inner_core_radius_km.std_dev);
+    }
+}
+
+// Runs the scenario once for each seed in 0..n_runs.
+pub fn monte_carlo(scenario: &Scenario, n_runs: usize) -> Result<MonteCarloStats, WorldSimError> {
+    let seeds: Vec<u64> = (0..n_runs as u64).collect();
+    monte_carlo_seeds(scenario, &seeds)
+}
+
+// Runs the scenario once per seed, replacing its own seed. Runs are independent, so with
+// the `parallel` feature they spread across threads; reports keep seed order either way. The
+// first run to fail fails the whole batch.
+pub fn monte_carlo_seeds(scenario: &Scenario, seeds: &[u64]) -> Result<MonteCarloStats, WorldSimError> {
+    let run = |&seed: &u64| Scenario { seed, ..scenario.clone() }.run();
+
+    #[cfg(feature = "parallel")]
+    let reports: Vec<SimulationReport> = seeds.par_iter().map(run).collect::<Result<_, _>>()?;
+    #[cfg(not(feature = "parallel"))]
+    let reports: Vec<SimulationReport> = seeds.iter().map(run).collect::<Result<_, _>>()?;
+
+    let sample = |f: fn(&SimulationReport) -> f32| SampleStats::from_samples(&reports.iter().map(f).collect::<Vec<_>>());
+    Ok(MonteCarloStats {
+        seeds: seeds.to_vec(),
+        reversals: sample(|report| report.reversals as f32),
+        large_igneous_provinces: sample(|report| report.event_counts.get("large_igneous_province").copied().unwrap_or(0) as f32),
+        subducted_volume_km3: sample(|report| report.subducted_volume_km3),
+        inner_core_radius_km: sample(|report| report.inner_core_radius_km),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::limits::Limits;
+    use crate::earth::scenario::PlanetPreset;
+
+    #[test]
+    fn twenty_seeds_give_twenty_samples_with_finite_spread() {
+        let scenario = Scenario {
+            planet: PlanetPreset::Earth,
+            seed: 0,
+            total_years: 2_000_000.0,
+            step_years: 1_000_000.0,
+            plates: Vec::new(),
+            hot_spots: Vec::new(),
+            limits: Limits::default(),
+        };
+
+        let stats = monte_carlo(&scenario, 20).unwrap();
+        assert_eq!(stats.runs(), 20);
+        assert_eq!(stats.seeds, (0..20).collect::<Vec<u64>>());
+        for sample in [stats.reversals, stats.large_igneous_provinces, stats.subducted_volume_km3, stats.inner_core_radius_km] {
+            assert!(sample.mean.is_finite());
+            assert!(sample.std_dev.is_finite() && sample.std_dev >= 0.0);
+        }
+    }
+}
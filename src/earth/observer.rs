@@ -0,0 +1,68 @@
+use crate::earth::mantle::Eruption;
+
+// Hooks for discrete events during a step. Every method does nothing by default, so an
+// observer only implements the events it cares about.
+pub trait EarthObserver {
+    fn on_reversal(&mut self, _t_myr: f32) {}
+    fn on_subduction_start(&mut self, _plate_id: u32) {}
+    fn on_eruption(&mut self, _e: &Eruption) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::mantle::{MantleEvent, PlateBuilder};
+    use crate::earth::Earth;
+
+    #[derive(Default)]
+    struct Counter {
+        reversals: Vec<f32>,
+        subductions: Vec<u32>,
+        eruptions: usize,
+    }
+
+    impl EarthObserver for Counter {
+        fn on_reversal(&mut self, t_myr: f32) {
+            self.reversals.push(t_myr);
+        }
+
+        fn on_subduction_start(&mut self, plate_id: u32) {
+            self.subductions.push(plate_id);
+        }
+
+        fn on_eruption(&mut self, _e: &Eruption) {
+            self.eruptions += 1;
+        }
+    }
+
+    // Only cares about one kind of event and leaves the rest to the defaults.
+    struct Quiet;
+
+    impl EarthObserver for Quiet {}
+
+    #[test]
+    fn a_counting_observer_hears_about_every_event() {
+        // An old plate ready to sink, and a second one erupting as often as it can.
+        let mut earth = Earth::new_seeded(13);
+        let old = earth.mantle.add_plate(PlateBuilder::new().center(0.0, 0.0).age_myr(150.0).build());
+        earth.mantle.add_plate(PlateBuilder::new().center(0.0, 120.0).volcanic_activity(1.0).build());
+        // A weak inner-core contribution keeps the field below the reversal threshold, so
+        // every step draws for a reversal.
+        earth.inner_core.magnetic_contribution_factor = 0.1;
+
+        let mut counter = Counter::default();
+        let mut eruptions = 0;
+        for _ in 0..200 {
+            let events = earth.step_observed(100_000.0, &mut counter).unwrap();
+            eruptions += events.iter().filter(|event| matches!(event, MantleEvent::VolcanicEruption { .. })).count();
+        }
+
+        assert!(!counter.reversals.is_empty());
+        assert_eq!(counter.reversals, earth.outer_core.reversal_history);
+        assert_eq!(counter.subductions, [old]);
+        assert!(eruptions > 0);
+        assert_eq!(counter.eruptions, eruptions);
+
+        assert!(earth.step_observed(100_000.0, &mut Quiet).is_ok());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::earth::crust::CrustType;
+use crate::earth::mantle::Plate;
+use crate::earth::units::myr_to_years;
+
+// Present-day ocean: water volume (km³) and the area it covers (km²).
+const PRESENT_OCEAN_VOLUME_KM3: f32 = 1.335e9;
+const PRESENT_OCEAN_AREA_KM2: f32 = 3.61e8;
+// Young ridge crust stands this far above the abyssal floor it eventually cools into,
+// and thermal subsidence takes it back down with this e-folding time.
+const RIDGE_RELIEF_KM: f32 = 2.0;
+const RIDGE_SUBSIDENCE_MYR: f32 = 60.0;
+// Continental surfaces are taken to spread evenly from the present shoreline up to twice
+// their mean elevation, so a rise in sea level floods a proportional share of them.
+const CONTINENT_MEAN_ELEVATION_M: f32 = 840.0;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ocean {
+    pub volume_km3: f32,
+    pub area_km2: f32,
+    // Mean depth of the seafloor below the present-day datum; shallower ridges reduce it.
+    pub mean_depth_km: f32,
+    pub reference_depth_km: f32,
+    pub sea_level_m: f32,
+}
+
+impl Ocean {
+    pub fn new() -> Self {
+        let mean_depth_km = PRESENT_OCEAN_VOLUME_KM3 / PRESENT_OCEAN_AREA_KM2;
+        Self {
+            volume_km3: PRESENT_OCEAN_VOLUME_KM3,
+            area_km2: PRESENT_OCEAN_AREA_KM2,
+            mean_depth_km,
+            reference_depth_km: mean_depth_km,
+            sea_level_m: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    // New ridge crust is hot and buoyant, so it displaces water and pushes the sea up.
+    pub fn adjust_for_ridge_volume(&mut self, spreading_area_km2: f32) -> f32 {
+        let displaced_km3 = spreading_area_km2 * RIDGE_RELIEF_KM;
+        self.mean_depth_km -= displaced_km3 / self.area_km2;
+        self.update_sea_level()
+    }
+
+    // Ridges cool and sink back toward the reference depth as they age.
+    pub fn subside(&mut self, years: f32) -> f32 {
+        let relaxation = 1.0 - (-years / myr_to_years(RIDGE_SUBSIDENCE_MYR)).exp();
+        self.mean_depth_km += (self.reference_depth_km - self.mean_depth_km) * relaxation;
+        self.update_sea_level()
+    }
+
+    // Continental area below sea level. Isostatic subsidence of a plate's crust lowers its
+    // land surface by the same amount, so a sinking continent floods even at constant sea level.
+    pub fn flooded_continental_area(&self, plates: &[Plate]) -> f32 {
+        plates
+            .iter()
+            .filter(|plate| plate.crust.kind == CrustType::Continental)
+            .map(|plate| {
+                let relative_rise_m = self.sea_level_m + plate.crust.isostasy_adjustment_km * 1_000.0;
+                let flooded_fraction = (relative_rise_m / (2.0 * CONTINENT_MEAN_ELEVATION_M)).clamp(0.0, 1.0);
+                plate.area_km2 * flooded_fraction
+            })
+            .sum()
+    }
+
+    // Water the oceanic plates' basins would hold down to their age-predicted floors, and
+    // the area-weighted depth of those floors. The mean is zero with no oceanic plates.
+    pub fn basin_volume_km3(&self, plates: &[Plate]) -> f64 {
+        plates.iter().map(|plate| plate.area_km2 as f64 * plate.seafloor_depth_km() as f64).sum()
+    }
+
+    pub fn mean_basin_depth_km(&self, plates: &[Plate]) -> f32 {
+        let oceanic_area_km2: f64 = plates
+            .iter()
+            .filter(|plate| plate.crust.kind == CrustType::Oceanic)
+            .map(|plate| plate.area_km2 as f64)
+            .sum();
+        if oceanic_area_km2 <= 0.0 {
+            return 0.0;
+        }
+        (self.basin_volume_km3(plates) / oceanic_area_km2) as f32
+    }
+
+    #[cfg(feature = "std")]
+    pub fn describe(&self) {
+        println!("Ocean:");
+        println!("  Volume: {:.3e} km³ over {:.3e} km²", self.volume_km3, self.area_km2);
+        println!("  Mean seafloor depth: {:.3} km", self.mean_depth_km);
+        println!("  Sea level: {:+.1} m", self.sea_level_m);
+    }
+
+    fn update_sea_level(&mut self) -> f32 {
+        self.sea_level_m = (self.volume_km3 / self.area_km2 - self.mean_depth_km) * 1_000.0;
+        self.sea_level_m
+    }
+}
+
+impl Default for Ocean {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::mantle::PlateBuilder;
+
+    #[test]
+    fn faster_spreading_raises_the_sea_further() {
+        let mut slow = Ocean::new();
+        let mut fast = Ocean::new();
+        let slow_rise_m = slow.adjust_for_ridge_volume(1_000_000.0);
+        let fast_rise_m = fast.adjust_for_ridge_volume(5_000_000.0);
+        assert!(slow_rise_m > 0.0);
+        assert!(fast_rise_m > slow_rise_m);
+
+        // The ridges cool and the sea falls back.
+        fast.subside(myr_to_years(600.0));
+        assert!(fast.sea_level_m.abs() < 1e-3 * fast_rise_m);
+    }
+
+    #[test]
+    fn a_higher_sea_floods_more_of_the_continents() {
+        let continent = PlateBuilder::new().crust(CrustType::Continental).area_km2(1e7).build();
+        let mut ocean = Ocean::new();
+        assert_eq!(ocean.flooded_continental_area(std::slice::from_ref(&continent)), 0.0);
+
+        ocean.adjust_for_ridge_volume(5_000_000.0);
+        assert!(ocean.flooded_continental_area(&[continent]) > 0.0);
+    }
+}
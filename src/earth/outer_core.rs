@@ -1,8 +1,90 @@
 use crate::earth::inner_core::InnerCore;
-use rand::random;
+use crate::earth::planet::PlanetConfig;
+use crate::earth::thermal::{flux_to_tj_per_year, heat_content_tj, shell_volume_m3, sphere_area_m2, CORE_HEAT_CAPACITY_J_PER_KG_K};
+use crate::earth::units::km_to_m;
+use crate::earth::validation::{ValidationError, Validator};
+use log::info;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 
+pub const REVERSAL_FIELD_THRESHOLD: f32 = 0.3;
+// Chances per 100 kyr of a reversal while the field is weak, and of a heat anomaly
+// while convection is vigorous.
+const REVERSAL_CHANCE_PER_100_KYR: f32 = 0.05;
+const HEAT_ANOMALY_CHANCE_PER_100_KYR: f32 = 0.1;
+
+// Latent heat release from inner-core freezing (TJ/yr) that on its own would drive
+// convection at full strength; Earth's present ~1 mm/yr of growth supplies about half.
+const LATENT_HEAT_FOR_FULL_CONVECTION_TJ_PER_YEAR: f32 = 100.0;
+// Gravitational energy from light elements rising off the growing inner core (TJ/yr) that
+// on its own would drive full convection; Earth's present growth also supplies about half.
+const GRAVITATIONAL_ENERGY_FOR_FULL_CONVECTION_TJ_PER_YEAR: f32 = 8e8;
+
+// Convection below the dynamo threshold for this long lets the field decay away entirely.
+pub const DEFAULT_DYNAMO_THRESHOLD: f32 = 0.1;
+const DYNAMO_SHUTDOWN_YEARS: f32 = 50_000_000.0;
+
+// Once the inner core has grown to within this of the core-mantle boundary, the outer core
+// is treated as frozen solid.
+pub const OUTER_CORE_FREEZE_MARGIN_KM: f32 = 10.0;
+
+// Fastest the outer core can cool to make up for heat the inner core doesn't supply.
+const MAX_SECULAR_COOLING_K_PER_YEAR: f64 = 1e-7;
+
+// Present-day Earth's dipole moment (A·m²), and the field strength and core radius that
+// produce it here. Other states scale it with field strength and the core's volume.
+const EARTH_DIPOLE_MOMENT_AM2: f64 = 8.0e22;
+const EARTH_FIELD_STRENGTH: f32 = 0.45;
+const EARTH_CORE_RADIUS_KM: f32 = 3_421.0;
+// Vacuum permeability over 4π (T·m/A).
+const MU_0_OVER_4PI: f64 = 1e-7;
+
+// Share of the convective flow that goes into toroidal motion; the rest is poloidal.
+pub const DEFAULT_TOROIDAL_SHARE: f32 = 0.7;
+
+// How convection strength and the inner core's contribution factor combine into field strength.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DynamoModel {
+    // Field proportional to convection.
+    #[default]
+    Linear,
+    // Follows the linear model while convection is weak, then levels off at `max_field`.
+    Saturating { max_field: f32 },
+    // Any other law, given convection strength and contribution factor. Can't be saved.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(fn(f32, f32) -> f32),
+}
+
+// Custom laws are equal only when they are the same function.
+impl PartialEq for DynamoModel {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DynamoModel::Linear, DynamoModel::Linear) => true,
+            (DynamoModel::Saturating { max_field: a }, DynamoModel::Saturating { max_field: b }) => a == b,
+            (DynamoModel::Custom(a), DynamoModel::Custom(b)) => std::ptr::fn_addr_eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+impl DynamoModel {
+    pub fn field_strength(self, convection_strength: f32, contribution_factor: f32) -> f32 {
+        let linear = convection_strength * contribution_factor;
+        match self {
+            DynamoModel::Linear => linear,
+            DynamoModel::Saturating { max_field } if max_field > 0.0 => max_field * (linear / max_field).tanh(),
+            DynamoModel::Saturating { .. } => 0.0,
+            DynamoModel::Custom(law) => law(convection_strength, contribution_factor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OuterCore {
     pub thickness_km: f32,
+    pub core_radius_km: f32,
     pub temperature_c: f32,
     pub density: f32,
     pub iron_pct: f32,
@@ -11,46 +93,99 @@ pub struct OuterCore {
     pub light_elements_enrichment: f32,
     pub convection_strength: f32,
     pub magnetic_field_strength: f32,
-    pub heat_flux_from_inner_mw_per_m2: f32,
-    pub heat_flux_to_mantle_mw_per_m2: f32,
+    pub heat_flux_from_inner_w_per_m2: f32,
+    pub heat_flux_to_mantle_w_per_m2: f32,
+    // Bumped every time the flux to the mantle is recomputed, so a consumer can tell a fresh
+    // value from one it has already used.
+    pub heat_flux_generation: u64,
     pub total_time_myr: f32,
     pub magnetic_polarity_normal: bool,
+    pub dynamo_active: bool,
+    pub dynamo_threshold: f32,
+    // How long convection has stayed below the dynamo threshold.
+    pub subcritical_years: f32,
+    pub dynamo_model: DynamoModel,
+    pub toroidal_share: f32,
     pub toroidal_flow_factor: f32,
     pub poloidal_flow_factor: f32,
     pub has_heat_anomaly: bool,
+    // `total_time_myr` at each reversal, oldest first.
+    pub reversal_history: Vec<f32>,
     pub cumulative_heat_to_mantle_tj: f64,
+    pub cumulative_heat_from_inner_tj: f64,
+    pub cumulative_secular_cooling_tj: f64,
+    // The generator behind rand's StdRng, used directly so its stream position is saved and
+    // restored with the rest of the state.
+    rng: ChaCha12Rng,
 }
 
 impl OuterCore {
-    pub fn new(inner_core: &InnerCore) -> Self {
-        let convection_strength = (inner_core.heat_flux_mw_per_m2 / 0.1).min(1.0);
+    #[cfg(feature = "std")]
+    pub fn new(inner_core: &InnerCore, config: &PlanetConfig) -> Self {
+        Self::with_rng(inner_core, config, ChaCha12Rng::from_entropy())
+    }
+
+    pub fn new_seeded(inner_core: &InnerCore, config: &PlanetConfig, seed: u64) -> Self {
+        Self::with_rng(inner_core, config, ChaCha12Rng::seed_from_u64(seed))
+    }
+
+    // Back to the state `new` built, over the given inner core, with the random stream
+    // rewound to its start so the same seed plays out the same history again.
+    pub fn reset(&mut self, inner_core: &InnerCore, config: &PlanetConfig) {
+        let mut rng = self.rng.clone();
+        rng.set_word_pos(0);
+        *self = Self::with_rng(inner_core, config, rng);
+    }
+
+    fn with_rng(inner_core: &InnerCore, config: &PlanetConfig, rng: ChaCha12Rng) -> Self {
+        let convection_strength = convection_driven_by(inner_core);
+
+        let thickness_km = config.core_radius_km - inner_core.radius_km;
 
         Self {
-            thickness_km: 2200.0,
-            temperature_c: 4500.0,
-            density: 11000.0,
+            thickness_km,
+            core_radius_km: config.core_radius_km,
+            temperature_c: config.outer_core_temperature_c,
+            density: config.outer_core_density,
             iron_pct: 85.0,
             nickel_pct: 5.0,
             light_elements_pct: 10.0,
             light_elements_enrichment: 0.0,
             convection_strength,
-            magnetic_field_strength: convection_strength * inner_core.magnetic_contribution_factor,
-            heat_flux_from_inner_mw_per_m2: inner_core.heat_flux_mw_per_m2,
-            heat_flux_to_mantle_mw_per_m2: 0.04,
+            magnetic_field_strength: DynamoModel::default().field_strength(convection_strength, inner_core.magnetic_contribution_factor),
+            heat_flux_from_inner_w_per_m2: inner_core.heat_flux_w_per_m2,
+            heat_flux_to_mantle_w_per_m2: 0.04,
+            heat_flux_generation: 0,
             total_time_myr: 0.0,
             magnetic_polarity_normal: true,
-            toroidal_flow_factor: convection_strength * 0.7,
-            poloidal_flow_factor: convection_strength * 0.3,
+            dynamo_active: true,
+            dynamo_threshold: DEFAULT_DYNAMO_THRESHOLD,
+            subcritical_years: 0.0,
+            dynamo_model: DynamoModel::default(),
+            toroidal_share: DEFAULT_TOROIDAL_SHARE,
+            toroidal_flow_factor: convection_strength * DEFAULT_TOROIDAL_SHARE,
+            poloidal_flow_factor: convection_strength * (1.0 - DEFAULT_TOROIDAL_SHARE),
             has_heat_anomaly: false,
+            reversal_history: Vec::new(),
             cumulative_heat_to_mantle_tj: 0.0,
+            cumulative_heat_from_inner_tj: 0.0,
+            cumulative_secular_cooling_tj: 0.0,
+            rng,
         }
     }
 
-    pub fn update_dynamics(&mut self, inner_core: &InnerCore, years: f32) {
-        self.total_time_myr += years / 1_000_000.0;
+    // `absolute_time_myr` is the planet's clock at the end of this step.
+    pub fn update_dynamics(&mut self, inner_core: &InnerCore, years: f32, absolute_time_myr: f32) {
+        self.total_time_myr = absolute_time_myr;
 
-        self.heat_flux_from_inner_mw_per_m2 = inner_core.heat_flux_mw_per_m2;
-        self.convection_strength = (self.heat_flux_from_inner_mw_per_m2 / 0.1).min(1.0);
+        self.heat_flux_from_inner_w_per_m2 = inner_core.heat_flux_w_per_m2;
+        self.convection_strength = convection_driven_by(inner_core);
+        self.thickness_km = (self.core_radius_km - inner_core.radius_km).max(0.0);
+        if self.is_frozen() {
+            self.freeze();
+            self.transfer_heat_to_mantle(inner_core.heat_output_tj_per_year() * years as f64, years);
+            return;
+        }
 
         let enrichment_delta = (inner_core.crystallization_rate_mm_per_year * years * 0.00001).min(1.0);
         self.enrich_light_elements(enrichment_delta);
@@ -59,57 +194,439 @@ impl OuterCore {
             self.convection_strength *= 0.95;
         }
 
-        self.magnetic_field_strength = self.convection_strength * inner_core.magnetic_contribution_factor;
+        self.update_dynamo(years);
+        self.magnetic_field_strength = if self.dynamo_active {
+            self.dynamo_model.field_strength(self.convection_strength, inner_core.magnetic_contribution_factor)
+        } else {
+            0.0
+        };
 
-        self.toroidal_flow_factor = self.convection_strength * 0.7;
-        self.poloidal_flow_factor = self.convection_strength * 0.3;
+        self.toroidal_flow_factor = self.convection_strength * self.toroidal_share;
+        self.poloidal_flow_factor = self.convection_strength * (1.0 - self.toroidal_share);
 
-        self.maybe_trigger_reversal();
-        self.maybe_generate_heat_anomaly();
-        self.transfer_heat_to_mantle(years);
+        self.maybe_trigger_reversal(years);
+        self.maybe_generate_heat_anomaly(years);
+        self.transfer_heat_to_mantle(inner_core.heat_output_tj_per_year() * years as f64, years);
     }
 
-    pub fn transfer_heat_to_mantle(&mut self, years: f32) {
-        self.heat_flux_to_mantle_mw_per_m2 = self.convection_strength * 0.06;
+    pub fn is_frozen(&self) -> bool {
+        self.thickness_km <= OUTER_CORE_FREEZE_MARGIN_KM
+    }
 
-        let heat_per_year_tj = (self.heat_flux_to_mantle_mw_per_m2 as f64 * 1e6 * 3.1536e7) / 1e12;
-        self.cumulative_heat_to_mantle_tj += heat_per_year_tj * years as f64;
+    // With no liquid left there is nothing to convect: the dynamo dies at once and the field,
+    // flows and reversals with it.
+    fn freeze(&mut self) {
+        self.thickness_km = 0.0;
+        self.convection_strength = 0.0;
+        self.toroidal_flow_factor = 0.0;
+        self.poloidal_flow_factor = 0.0;
+        self.magnetic_field_strength = 0.0;
+        self.has_heat_anomaly = false;
+        if self.dynamo_active {
+            self.dynamo_active = false;
+            info!("🧲 Outer core has frozen solid at {:.2} million years; the dynamo is gone for good.", self.total_time_myr);
+        }
     }
 
+    // Convection asks for a CMB flux, but the outer core can only pass on what the inner
+    // core supplied plus what it gives up by cooling, so the delivered flux is capped by
+    // that budget and any shortfall lowers the outer core's temperature.
+    pub fn transfer_heat_to_mantle(&mut self, heat_from_inner_tj: f64, years: f32) {
+        let cmb_area_m2 = sphere_area_m2(self.core_radius_km);
+        let demanded_flux = self.convection_strength * 0.06;
+        let demanded_tj = flux_to_tj_per_year(demanded_flux, cmb_area_m2) * years as f64;
+
+        let heat_capacity_tj_per_k = self.heat_capacity_tj_per_k();
+        let max_cooling_tj = heat_capacity_tj_per_k * MAX_SECULAR_COOLING_K_PER_YEAR * years as f64;
+        // A frozen outer core has no liquid left to store heat in, so it conducts straight through.
+        let delivered_tj = if self.is_frozen() { heat_from_inner_tj } else { demanded_tj.min(heat_from_inner_tj + max_cooling_tj) };
+        let cooling_tj = delivered_tj - heat_from_inner_tj;
+
+        if heat_capacity_tj_per_k > 0.0 {
+            self.temperature_c -= (cooling_tj / heat_capacity_tj_per_k) as f32;
+        }
+        self.cumulative_heat_from_inner_tj += heat_from_inner_tj;
+        self.cumulative_secular_cooling_tj += cooling_tj;
+        self.cumulative_heat_to_mantle_tj += delivered_tj;
+
+        self.heat_flux_to_mantle_w_per_m2 = if years > 0.0 {
+            (delivered_tj / (flux_to_tj_per_year(1.0, cmb_area_m2) * years as f64)) as f32
+        } else {
+            demanded_flux
+        };
+        self.heat_flux_generation += 1;
+    }
+
+    pub fn dipole_moment_am2(&self) -> f64 {
+        let volume_ratio = (self.core_radius_km / EARTH_CORE_RADIUS_KM).powi(3) as f64;
+        EARTH_DIPOLE_MOMENT_AM2 * (self.magnetic_field_strength / EARTH_FIELD_STRENGTH) as f64 * volume_ratio
+    }
+
+    // Surface field of an axial dipole, rising from its equatorial value to twice that at
+    // the poles.
+    pub fn surface_field_ut(&self, planet_radius_km: f32, lat_deg: f32) -> f32 {
+        let radius_m = km_to_m(planet_radius_km as f64);
+        let equatorial_ut = MU_0_OVER_4PI * self.dipole_moment_am2() / radius_m.powi(3) * 1e6;
+        let sin_lat = lat_deg.to_radians().sin();
+        equatorial_ut as f32 * (1.0 + 3.0 * sin_lat * sin_lat).sqrt()
+    }
+
+    pub fn mass_kg(&self) -> f64 {
+        shell_volume_m3(self.core_radius_km - self.thickness_km, self.core_radius_km) * self.density as f64
+    }
+
+    fn heat_capacity_tj_per_k(&self) -> f64 {
+        self.mass_kg() * CORE_HEAT_CAPACITY_J_PER_KG_K / 1e12
+    }
+
+    pub fn heat_content_tj(&self) -> f64 {
+        heat_content_tj(self.mass_kg(), CORE_HEAT_CAPACITY_J_PER_KG_K, self.temperature_c)
+    }
+
+    // Freezing iron and nickel onto the inner core leaves the light elements behind in the
+    // liquid. The enrichment is how far, in percent, the light-element share has risen above
+    // where it started, so full enrichment doubles it; iron and nickel give up the difference
+    // in proportion to what each holds, keeping the total.
     pub fn enrich_light_elements(&mut self, delta: f32) {
-        self.light_elements_enrichment = (self.light_elements_enrichment + delta).clamp(0.0, 100.0);
+        let previous = self.light_elements_enrichment;
+        self.light_elements_enrichment = (previous + delta).clamp(0.0, 100.0);
+
+        let metals_pct = self.iron_pct + self.nickel_pct;
+        let growth = (100.0 + self.light_elements_enrichment) / (100.0 + previous);
+        let shift_pct = (self.light_elements_pct * (growth - 1.0)).min(metals_pct);
+        if metals_pct > 0.0 {
+            let remaining = (metals_pct - shift_pct) / metals_pct;
+            self.iron_pct *= remaining;
+            self.nickel_pct *= remaining;
+        }
+        self.light_elements_pct += shift_pct;
     }
 
-    pub fn maybe_trigger_reversal(&mut self) {
-        if self.magnetic_field_strength < 0.3 && random::<f32>() < 0.05 {
+    // The dynamo dies once convection has been too weak to sustain it for long enough, and
+    // restarts as soon as convection is back above the threshold.
+    fn update_dynamo(&mut self, years: f32) {
+        if self.convection_strength >= self.dynamo_threshold {
+            self.subcritical_years = 0.0;
+            if !self.dynamo_active {
+                self.dynamo_active = true;
+                info!("🧲 Dynamo restarted at {:.2} million years.", self.total_time_myr);
+            }
+            return;
+        }
+
+        self.subcritical_years += years;
+        if self.dynamo_active && self.subcritical_years >= DYNAMO_SHUTDOWN_YEARS {
+            self.dynamo_active = false;
+            info!("🧲 Dynamo shut down at {:.2} million years; the magnetic field has collapsed.", self.total_time_myr);
+        }
+    }
+
+    pub fn maybe_trigger_reversal(&mut self, years: f32) {
+        if self.dynamo_active
+            && self.magnetic_field_strength < REVERSAL_FIELD_THRESHOLD
+            && self.rng.gen::<f32>() < chance_over(REVERSAL_CHANCE_PER_100_KYR, years)
+        {
             self.magnetic_polarity_normal = !self.magnetic_polarity_normal;
-            println!("⚡ Magnetic field reversal occurred!");
+            self.reversal_history.push(self.total_time_myr);
+            info!("⚡ Magnetic field reversal occurred at {:.2} million years!", self.total_time_myr);
+        }
+    }
+
+    // Mean spacing between recorded reversals; needs at least two to say anything.
+    pub fn average_reversal_interval_myr(&self) -> Option<f32> {
+        let (first, last) = (self.reversal_history.first()?, self.reversal_history.last()?);
+        let intervals = self.reversal_history.len() - 1;
+        if intervals == 0 {
+            return None;
         }
+        Some((last - first) / intervals as f32)
     }
 
-    pub fn maybe_generate_heat_anomaly(&mut self) {
-        if self.convection_strength > 0.8 && random::<f32>() < 0.1 {
+    pub fn maybe_generate_heat_anomaly(&mut self, years: f32) {
+        if self.convection_strength > 0.8 && self.rng.gen::<f32>() < chance_over(HEAT_ANOMALY_CHANCE_PER_100_KYR, years) {
             self.has_heat_anomaly = true;
-            println!("🔥 Local heat anomaly generated at CMB!");
+            info!("🔥 Local heat anomaly generated at CMB!");
         }
     }
 
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        Validator::new()
+            .composition("iron_pct + nickel_pct + light_elements_pct", &[self.iron_pct, self.nickel_pct, self.light_elements_pct])
+            .non_negative("thickness_km", self.thickness_km)
+            .positive("density", self.density)
+            .non_negative("dynamo_threshold", self.dynamo_threshold)
+            .within("toroidal_share", self.toroidal_share, 0.0, 1.0)
+            .above_absolute_zero("temperature_c", self.temperature_c)
+            .finish()
+    }
+
+    #[cfg(feature = "std")]
     pub fn describe(&self) {
-        println!("Outer Core:");
-        println!("  Thickness: {:.0} km", self.thickness_km);
-        println!("  Temperature: {:.0} °C", self.temperature_c);
-        println!("  Density: {:.0} kg/m³", self.density);
-        println!("  Composition: {:.1}% iron, {:.1}% nickel, {:.1}% light elements", self.iron_pct, self.nickel_pct, self.light_elements_pct);
-        println!("  Light element enrichment: {:.2}%", self.light_elements_enrichment);
-        println!("  Convection strength: {:.2}", self.convection_strength);
-        println!("  Magnetic field strength: {:.2}", self.magnetic_field_strength);
-        println!("  Magnetic polarity normal: {}", self.magnetic_polarity_normal);
-        println!("  Toroidal flow factor: {:.2}", self.toroidal_flow_factor);
-        println!("  Poloidal flow factor: {:.2}", self.poloidal_flow_factor);
-        println!("  Heat flux from inner core: {:.3} MW/m²", self.heat_flux_from_inner_mw_per_m2);
-        println!("  Heat flux to mantle: {:.3} MW/m²", self.heat_flux_to_mantle_mw_per_m2);
-        println!("  Cumulative heat to mantle: {:.2e} TJ", self.cumulative_heat_to_mantle_tj);
-        println!("  Local heat anomaly: {}", self.has_heat_anomaly);
-        println!("  Total simulated time: {:.2} million years", self.total_time_myr);
+        println!("{}", self.summary());
+    }
+
+    pub fn summary(&self) -> String {
+        let mut lines = vec!["Outer Core:".to_string()];
+        lines.push(format!("  Thickness: {:.0} km", self.thickness_km));
+        lines.push(format!("  Temperature: {:.0} °C", self.temperature_c));
+        lines.push(format!("  Density: {:.0} kg/m³", self.density));
+        lines.push(format!("  Composition: {:.1}% iron, {:.1}% nickel, {:.1}% light elements", self.iron_pct, self.nickel_pct, self.light_elements_pct));
+        lines.push(format!("  Light element enrichment: {:.2}%", self.light_elements_enrichment));
+        lines.push(format!("  Convection strength: {:.2}", self.convection_strength));
+        lines.push(format!("  Magnetic field strength: {:.2}", self.magnetic_field_strength));
+        lines.push(format!("  Dipole moment: {:.2e} A·m²", self.dipole_moment_am2()));
+        lines.push(format!("  Magnetic polarity normal: {}", self.magnetic_polarity_normal));
+        lines.push(format!("  Dynamo active: {}", self.dynamo_active));
+        lines.push(format!("  Toroidal flow factor: {:.2}", self.toroidal_flow_factor));
+        lines.push(format!("  Poloidal flow factor: {:.2}", self.poloidal_flow_factor));
+        lines.push(format!("  Heat flux from inner core: {:.3} W/m²", self.heat_flux_from_inner_w_per_m2));
+        lines.push(format!("  Heat flux to mantle: {:.3} W/m²", self.heat_flux_to_mantle_w_per_m2));
+        lines.push(format!("  Cumulative heat to mantle: {:.2e} TJ", self.cumulative_heat_to_mantle_tj));
+        lines.push(format!("  Cumulative secular cooling: {:.2e} TJ", self.cumulative_secular_cooling_tj));
+        lines.push(format!("  Reversals recorded: {}", self.reversal_history.len()));
+        if let Some(interval) = self.average_reversal_interval_myr() {
+            lines.push(format!("  Average reversal interval: {:.2} million years", interval));
+        }
+        lines.push(format!("  Local heat anomaly: {}", self.has_heat_anomaly));
+        lines.push(format!("  Total simulated time: {:.2} million years", self.total_time_myr));
+        lines.join("\n")
+    }
+}
+
+// Convective vigour powered by the inner core: the heat conducted out of it, plus the latent
+// heat and the buoyant light elements its crystallization frees at the boundary, which is
+// what keeps the geodynamo going.
+fn convection_driven_by(inner_core: &InnerCore) -> f32 {
+    let thermal = inner_core.heat_flux_w_per_m2 / 0.1;
+    let latent = inner_core.latent_heat_release_tj_per_year / LATENT_HEAT_FOR_FULL_CONVECTION_TJ_PER_YEAR;
+    let compositional = inner_core.gravitational_energy_release_tj_per_year / GRAVITATIONAL_ENERGY_FOR_FULL_CONVECTION_TJ_PER_YEAR;
+    (thermal + latent + compositional).min(1.0)
+}
+
+// Probability of at least one occurrence over `years` for an event with the given
+// chance per 100 kyr.
+fn chance_over(chance_per_100_kyr: f32, years: f32) -> f32 {
+    1.0 - (1.0 - chance_per_100_kyr).powf(years / 100_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_core(seed: u64) -> OuterCore {
+        let config = PlanetConfig::earth();
+        OuterCore::new_seeded(&InnerCore::new(&config), &config, seed)
+    }
+
+    // Holds the field under the reversal threshold so every 100 kyr is a fresh draw.
+    fn force_reversals(core: &mut OuterCore, steps: usize) {
+        for step in 0..steps {
+            core.total_time_myr = step as f32 * 0.1;
+            core.magnetic_field_strength = 0.1;
+            core.maybe_trigger_reversal(100_000.0);
+        }
+    }
+
+    #[test]
+    fn the_same_seed_replays_the_same_reversals() {
+        let mut first = seeded_core(11);
+        let mut second = seeded_core(11);
+        force_reversals(&mut first, 500);
+        force_reversals(&mut second, 500);
+        assert!(!first.reversal_history.is_empty());
+        assert_eq!(first.reversal_history, second.reversal_history);
+    }
+
+    #[test]
+    fn forced_reversals_are_timestamped_and_spaced() {
+        let mut core = seeded_core(4);
+        assert_eq!(core.average_reversal_interval_myr(), None);
+
+        force_reversals(&mut core, 1_000);
+        let history = &core.reversal_history;
+        assert!(history.len() >= 3, "only {} reversals", history.len());
+        assert!(history.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(core.magnetic_polarity_normal == history.len().is_multiple_of(2));
+
+        let expected = (history[history.len() - 1] - history[0]) / (history.len() - 1) as f32;
+        assert_eq!(core.average_reversal_interval_myr(), Some(expected));
+
+        core.reversal_history = vec![1.0, 3.0, 7.0];
+        assert_eq!(core.average_reversal_interval_myr(), Some(3.0));
+        core.reversal_history.truncate(1);
+        assert_eq!(core.average_reversal_interval_myr(), None);
+    }
+
+    // Keeps each test thread's records apart, since the logger is shared by the whole process.
+    struct CapturingLogger;
+
+    std::thread_local! {
+        static CAPTURED: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            CAPTURED.with(|captured| captured.borrow_mut().push(record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger;
+
+    #[test]
+    fn a_reversal_logs_exactly_one_record() {
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let mut core = seeded_core(2);
+        core.magnetic_field_strength = 0.1;
+        let records = (0..1_000)
+            .find_map(|_| {
+                CAPTURED.with(|captured| captured.borrow_mut().clear());
+                core.maybe_trigger_reversal(100_000.0);
+                (!core.reversal_history.is_empty()).then(|| CAPTURED.with(|captured| captured.take()))
+            })
+            .expect("a weak field reverses eventually");
+        assert_eq!(records.len(), 1);
+        assert!(records[0].contains("reversal"));
+    }
+
+    #[test]
+    fn sustained_weak_convection_shuts_the_dynamo_off() {
+        let mut core = seeded_core(6);
+        let mut stalled = InnerCore::new(&PlanetConfig::earth());
+        stalled.heat_flux_w_per_m2 = 0.0;
+        stalled.latent_heat_release_tj_per_year = 0.0;
+        stalled.gravitational_energy_release_tj_per_year = 0.0;
+
+        let steps = (DYNAMO_SHUTDOWN_YEARS / 1_000_000.0) as usize;
+        for step in 1..steps {
+            core.update_dynamics(&stalled, 1_000_000.0, step as f32);
+            assert!(core.dynamo_active, "shut down early, after {} Myr", step);
+        }
+        core.update_dynamics(&stalled, 1_000_000.0, steps as f32);
+        assert!(!core.dynamo_active);
+        assert_eq!(core.magnetic_field_strength, 0.0);
+
+        let reversals = core.reversal_history.len();
+        for step in 1..=100 {
+            core.update_dynamics(&stalled, 1_000_000.0, (steps + step) as f32);
+        }
+        assert_eq!(core.reversal_history.len(), reversals);
+        assert_eq!(core.magnetic_field_strength, 0.0);
+    }
+
+    #[test]
+    fn faster_crystallization_strengthens_the_field() {
+        let field_after_freezing_at = |coupling_mm_per_year_per_k: f32| {
+            let config = PlanetConfig::earth();
+            let mut inner_core = InnerCore::new(&config);
+            inner_core.set_cooling_model(coupling_mm_per_year_per_k);
+            inner_core.update_crystallization(100_000.0, 0.1);
+            let mut core = OuterCore::new_seeded(&inner_core, &config, 8);
+            core.update_dynamics(&inner_core, 100_000.0, 0.1);
+            (inner_core.crystallization_rate_mm_per_year, core.magnetic_field_strength)
+        };
+
+        // A tenth of Earth's coupling keeps convection well short of saturating.
+        let (slow_rate, slow_field) = field_after_freezing_at(0.000_1);
+        let (fast_rate, fast_field) = field_after_freezing_at(0.000_2);
+        assert!(fast_rate > slow_rate);
+        assert!(fast_field > slow_field, "{} vs {}", fast_field, slow_field);
+    }
+
+    #[test]
+    fn a_saturating_dynamo_levels_off_where_a_linear_one_keeps_rising() {
+        let saturating = DynamoModel::Saturating { max_field: 0.5 };
+        // Weak convection behaves the same either way.
+        let (linear_weak, saturating_weak) = (DynamoModel::Linear.field_strength(0.05, 1.0), saturating.field_strength(0.05, 1.0));
+        assert!((linear_weak - saturating_weak).abs() < 1e-3);
+
+        let (linear_strong, saturating_strong) = (DynamoModel::Linear.field_strength(1.0, 1.0), saturating.field_strength(1.0, 1.0));
+        assert_eq!(linear_strong, 1.0);
+        assert!(saturating_strong < 0.5 && saturating_strong < linear_strong);
+
+        let doubled = DynamoModel::Custom(|convection, contribution| 2.0 * convection * contribution);
+        assert_eq!(doubled.field_strength(0.3, 1.0), 0.6);
+    }
+
+    #[test]
+    fn the_default_dynamo_keeps_the_linear_field() {
+        let mut core = seeded_core(5);
+        assert_eq!(core.dynamo_model, DynamoModel::Linear);
+        let inner_core = InnerCore::new(&PlanetConfig::earth());
+        core.update_dynamics(&inner_core, 100_000.0, 0.1);
+        assert_eq!(core.magnetic_field_strength, core.convection_strength * inner_core.magnetic_contribution_factor);
+    }
+
+    #[test]
+    fn a_dipole_is_twice_as_strong_at_the_pole_as_at_the_equator() {
+        let mut core = seeded_core(1);
+        core.magnetic_field_strength = EARTH_FIELD_STRENGTH;
+        core.core_radius_km = EARTH_CORE_RADIUS_KM;
+        assert_eq!(core.dipole_moment_am2(), EARTH_DIPOLE_MOMENT_AM2);
+
+        let radius_km = PlanetConfig::earth().radius_km;
+        let equator = core.surface_field_ut(radius_km, 0.0);
+        let pole = core.surface_field_ut(radius_km, 90.0);
+        assert!((pole / equator - 2.0).abs() < 1e-4, "{} / {}", pole, equator);
+        assert_eq!(core.surface_field_ut(radius_km, -90.0), pole);
+        // Present-day Earth sits in the observed 25-65 µT range.
+        assert!(equator > 25.0 && pole < 65.0, "{} to {} µT", equator, pole);
+    }
+
+    #[test]
+    fn runaway_crystallization_freezes_the_outer_core_and_kills_the_dynamo() {
+        let config = PlanetConfig::earth();
+        let mut inner_core = InnerCore::new(&config);
+        inner_core.set_cooling_model(1.0);
+        let mut core = OuterCore::new_seeded(&inner_core, &config, 3);
+        let mut thickness_km = core.thickness_km;
+
+        let mut steps = 0;
+        while !core.is_frozen() {
+            steps += 1;
+            assert!(steps < 10_000, "the outer core never froze");
+            inner_core.update_crystallization(1_000_000.0, steps as f32);
+            core.update_dynamics(&inner_core, 1_000_000.0, steps as f32);
+            assert!(core.thickness_km <= thickness_km);
+            thickness_km = core.thickness_km;
+        }
+
+        assert!(!core.dynamo_active);
+        assert_eq!((core.thickness_km, core.convection_strength, core.magnetic_field_strength), (0.0, 0.0, 0.0));
+        let reversals = core.reversal_history.len();
+        for step in 1..=100 {
+            inner_core.update_crystallization(1_000_000.0, (steps + step) as f32);
+            core.update_dynamics(&inner_core, 1_000_000.0, (steps + step) as f32);
+        }
+        assert!(!core.dynamo_active);
+        assert_eq!(core.reversal_history.len(), reversals);
+        // Solid right through, the inner core has no latent heat left to pass on.
+        assert_eq!(inner_core.latent_heat_release_tj_per_year, 0.0);
+    }
+
+    #[test]
+    fn enrichment_shifts_iron_and_nickel_into_light_elements() {
+        let mut core = seeded_core(2);
+        let (iron_before, nickel_before, light_before) = (core.iron_pct, core.nickel_pct, core.light_elements_pct);
+        let total = |core: &OuterCore| core.iron_pct + core.nickel_pct + core.light_elements_pct;
+        assert!((total(&core) - 100.0).abs() < 0.01);
+
+        let mut light_pct = light_before;
+        for _ in 0..10 {
+            core.enrich_light_elements(5.0);
+            assert!((total(&core) - 100.0).abs() < 0.01, "composition sums to {}", total(&core));
+            assert!(core.light_elements_pct > light_pct);
+            light_pct = core.light_elements_pct;
+        }
+        assert!(core.iron_pct < iron_before && core.nickel_pct < nickel_before);
+        // Half enriched, so the light-element share is half again what it started at.
+        assert!((core.light_elements_pct - light_before * 1.5).abs() < 0.01);
     }
 }
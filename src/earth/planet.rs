@@ -0,0 +1,200 @@
+use crate::earth::crust::{DEFAULT_MANTLE_DENSITY, DEFAULT_SOLAR_CONSTANT_W_PER_M2};
+use crate::earth::thermal::{shell_volume_m3, PLANET_AGE_YEARS};
+
+pub const GRAVITATIONAL_CONSTANT: f64 = 6.674e-11;
+// Greenhouse factor that holds a bare continent with Earth's albedo at today's 15 °C.
+pub const EARTH_GREENHOUSE_FACTOR: f32 = 0.39;
+// Gough (1981): the Sun has brightened steadily on the main sequence, and started out at
+// 1 / (1 + FAINT_SUN_DIMMING) of its present output.
+const FAINT_SUN_DIMMING: f64 = 0.4;
+
+// How the star's output changes with the age of the planet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SolarLuminosity {
+    Constant,
+    // Dimmer in the past, about 70% of today when the planet formed.
+    #[default]
+    FaintYoungSun,
+}
+
+impl SolarLuminosity {
+    // Output at the given planetary age as a fraction of the present day's.
+    pub fn fraction_of_present(&self, age_years: f64) -> f32 {
+        match self {
+            SolarLuminosity::Constant => 1.0,
+            SolarLuminosity::FaintYoungSun => {
+                let age_fraction = age_years.max(0.0) / PLANET_AGE_YEARS;
+                (1.0 / (1.0 + FAINT_SUN_DIMMING * (1.0 - age_fraction))) as f32
+            }
+        }
+    }
+}
+
+// Bulk parameters the layers are built from. Mantle layer thicknesses scale with the
+// depth from the surface to the core, and layer densities with `mantle_density`, both
+// relative to the Earth preset.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlanetConfig {
+    pub name: String,
+    pub radius_km: f32,
+    pub core_radius_km: f32,
+    pub inner_core_radius_km: f32,
+    pub inner_core_temperature_c: f32,
+    // Temperature at the inner-core boundary, which the inner core cools toward.
+    pub icb_temperature_c: f32,
+    pub outer_core_temperature_c: f32,
+    pub inner_core_density: f32,
+    pub outer_core_density: f32,
+    // Density at the top of the mantle; deeper layers keep Earth's ratios to it.
+    pub mantle_density: f32,
+    // Present-day sunlight; `luminosity` scales it back for earlier ages.
+    pub solar_constant_w_per_m2: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub luminosity: SolarLuminosity,
+    // Age of the planet when a simulation starts, by default the present.
+    #[cfg_attr(feature = "serde", serde(default = "present_age_years"))]
+    pub start_age_years: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub greenhouse_factor: f32,
+    pub surface_gravity_m_per_s2: f32,
+    // Heat dissipated in the mantle by tidal flexing from a nearby massive body (TW).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tidal_heating_tw: f32,
+}
+
+impl PlanetConfig {
+    pub fn earth() -> Self {
+        Self {
+            name: "Earth".to_string(),
+            radius_km: 6_371.0,
+            core_radius_km: 3_421.0,
+            inner_core_radius_km: 1_221.0,
+            inner_core_temperature_c: 5_400.0,
+            icb_temperature_c: 4_300.0,
+            outer_core_temperature_c: 4_500.0,
+            inner_core_density: 12_800.0,
+            outer_core_density: 11_000.0,
+            mantle_density: DEFAULT_MANTLE_DENSITY,
+            solar_constant_w_per_m2: DEFAULT_SOLAR_CONSTANT_W_PER_M2,
+            luminosity: SolarLuminosity::FaintYoungSun,
+            start_age_years: PLANET_AGE_YEARS,
+            greenhouse_factor: EARTH_GREENHOUSE_FACTOR,
+            surface_gravity_m_per_s2: 9.81,
+            tidal_heating_tw: 0.0,
+        }
+    }
+
+    // A smaller planet that has lost more of its heat: a sulfur-rich, lighter core with
+    // only a small solid centre, and a thinner mantle under weaker sunlight.
+    pub fn mars() -> Self {
+        Self {
+            name: "Mars".to_string(),
+            radius_km: 3_390.0,
+            core_radius_km: 1_830.0,
+            inner_core_radius_km: 500.0,
+            inner_core_temperature_c: 2_300.0,
+            icb_temperature_c: 1_900.0,
+            outer_core_temperature_c: 2_000.0,
+            inner_core_density: 7_500.0,
+            outer_core_density: 6_500.0,
+            mantle_density: 3_500.0,
+            solar_constant_w_per_m2: 586.0,
+            luminosity: SolarLuminosity::FaintYoungSun,
+            start_age_years: PLANET_AGE_YEARS,
+            greenhouse_factor: 0.0,
+            surface_gravity_m_per_s2: 3.72,
+            tidal_heating_tw: 0.0,
+        }
+    }
+
+    // Jupiter's moon Io: an iron-sulfide core under a silicate mantle kept partly molten by
+    // about 100 TW of tidal heating, far more than its own interior produces.
+    pub fn io() -> Self {
+        Self {
+            name: "Io".to_string(),
+            radius_km: 1_821.6,
+            core_radius_km: 950.0,
+            inner_core_radius_km: 100.0,
+            inner_core_temperature_c: 1_700.0,
+            icb_temperature_c: 1_600.0,
+            outer_core_temperature_c: 1_650.0,
+            inner_core_density: 8_000.0,
+            outer_core_density: 5_150.0,
+            mantle_density: DEFAULT_MANTLE_DENSITY,
+            solar_constant_w_per_m2: 50.5,
+            luminosity: SolarLuminosity::FaintYoungSun,
+            start_age_years: PLANET_AGE_YEARS,
+            greenhouse_factor: 0.0,
+            surface_gravity_m_per_s2: 1.796,
+            tidal_heating_tw: 100.0,
+        }
+    }
+
+    // Sunlight reaching the planet at the given age.
+    pub fn solar_constant_at(&self, age_years: f64) -> f32 {
+        self.solar_constant_w_per_m2 * self.luminosity.fraction_of_present(age_years)
+    }
+
+    pub fn mantle_depth_km(&self) -> f32 {
+        self.radius_km - self.core_radius_km
+    }
+
+    pub fn outer_core_thickness_km(&self) -> f32 {
+        self.core_radius_km - self.inner_core_radius_km
+    }
+
+    // Total mass implied by the surface gravity and radius.
+    pub fn mass_kg(&self) -> f64 {
+        let radius_m = self.radius_km as f64 * 1_000.0;
+        self.surface_gravity_m_per_s2 as f64 * radius_m * radius_m / GRAVITATIONAL_CONSTANT
+    }
+
+    // Mean density the mantle needs for the planet to have its surface gravity, given the
+    // core densities. Unlike `mantle_density`, this covers the whole depth of the mantle.
+    pub fn mean_mantle_density(&self) -> f32 {
+        let core_mass_kg = shell_volume_m3(0.0, self.inner_core_radius_km) * self.inner_core_density as f64
+            + shell_volume_m3(self.inner_core_radius_km, self.core_radius_km) * self.outer_core_density as f64;
+        ((self.mass_kg() - core_mass_kg) / shell_volume_m3(self.core_radius_km, self.radius_km)) as f32
+    }
+
+    // Factors applied to Earth's mantle layer thicknesses and densities.
+    pub fn mantle_thickness_scale(&self) -> f32 {
+        self.mantle_depth_km() / PlanetConfig::earth().mantle_depth_km()
+    }
+
+    pub fn mantle_density_scale(&self) -> f32 {
+        self.mantle_density / DEFAULT_MANTLE_DENSITY
+    }
+}
+
+#[cfg(feature = "serde")]
+fn present_age_years() -> f64 {
+    PLANET_AGE_YEARS
+}
+
+impl Default for PlanetConfig {
+    fn default() -> Self {
+        Self::earth()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_young_sun_is_faint_and_brightens_steadily() {
+        let earth = PlanetConfig::earth();
+        let present = earth.solar_constant_at(PLANET_AGE_YEARS);
+        assert!(earth.solar_constant_at(0.0) < present);
+        assert!((SolarLuminosity::FaintYoungSun.fraction_of_present(0.0) - 1.0 / 1.4).abs() < 1e-6);
+        assert!((present - earth.solar_constant_w_per_m2).abs() < 1e-3);
+
+        let ages: Vec<f64> = (0..=45).map(|step| step as f64 * 1e8).collect();
+        let luminosity: Vec<f32> = ages.iter().map(|&age| SolarLuminosity::FaintYoungSun.fraction_of_present(age)).collect();
+        assert!(luminosity.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ages.iter().all(|&age| SolarLuminosity::Constant.fraction_of_present(age) == 1.0));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::earth::units::km_to_m;
+use std::f64::consts::PI;
+
+// Axis the planet spins about before any mass has moved: the geographic north pole.
+pub const REFERENCE_SPIN_AXIS: [f64; 3] = [0.0, 0.0, 1.0];
+// Power iterations used to find the principal axis; each costs one 3x3 product.
+const PRINCIPAL_AXIS_ITERATIONS: usize = 1_000;
+// Differences in moment smaller than this share of the whole are rounding noise; a planet
+// with none larger has no preferred axis.
+const MIN_RESOLVED_MOMENT_FRACTION: f64 = 1e-12;
+
+// Moment of inertia tensor (kg·m²) about the planet's centre, in a frame with x through
+// 0°N 0°E, y through 0°N 90°E and z through the north pole.
+pub type InertiaTensor = [[f64; 3]; 3];
+
+// A uniform spherical shell adds the same moment about every axis through the centre.
+pub fn add_shell(tensor: &mut InertiaTensor, inner_radius_km: f32, outer_radius_km: f32, density: f32) {
+    let (inner_m, outer_m) = (km_to_m(inner_radius_km as f64), km_to_m(outer_radius_km as f64));
+    let moment = 8.0 / 15.0 * PI * density as f64 * (outer_m.powi(5) - inner_m.powi(5));
+    for (i, row) in tensor.iter_mut().enumerate() {
+        row[i] += moment;
+    }
+}
+
+// A concentrated mass adds m (r² δij - ri rj); a negative mass stands for a deficit, such
+// as buoyant rock, against the surrounding shell.
+pub fn add_point_mass(tensor: &mut InertiaTensor, mass_kg: f64, radius_km: f32, lat_deg: f32, lon_deg: f32) {
+    let r = unit_vector(lat_deg, lon_deg).map(|x| x * km_to_m(radius_km as f64));
+    let r2 = dot(r, r);
+    for (i, row) in tensor.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value += mass_kg * (if i == j { r2 } else { 0.0 } - r[i] * r[j]);
+        }
+    }
+}
+
+// The planet settles to spin about its axis of greatest moment. Power iteration from the
+// current axis finds it, after shifting the tensor down by a bound on its smallest moment so
+// the spherical part drops out and the greatest moment dominates. Where several axes tie,
+// as about a symmetric planet, it keeps to the one nearest the current axis.
+pub fn principal_axis(tensor: &InertiaTensor, from: [f64; 3]) -> [f64; 3] {
+    let shift = (0..3)
+        .map(|i| tensor[i][i] - (0..3).filter(|&j| j != i).map(|j| tensor[i][j].abs()).sum::<f64>())
+        .fold(f64::INFINITY, f64::min);
+
+    let mut axis = from;
+    for _ in 0..PRINCIPAL_AXIS_ITERATIONS {
+        let next = [0, 1, 2].map(|i| (0..3).map(|j| (tensor[i][j] - if i == j { shift } else { 0.0 }) * axis[j]).sum::<f64>());
+        let length = dot(next, next).sqrt();
+        if length <= MIN_RESOLVED_MOMENT_FRACTION * tensor[0][0].abs() {
+            break;
+        }
+        axis = next.map(|x| x / length);
+    }
+    axis
+}
+
+// Angle between two axes, either way up.
+pub fn angle_between_deg(a: [f64; 3], b: [f64; 3]) -> f32 {
+    let cos = dot(a, b).abs() / (dot(a, a) * dot(b, b)).sqrt();
+    cos.min(1.0).acos().to_degrees() as f32
+}
+
+fn unit_vector(lat_deg: f32, lon_deg: f32) -> [f64; 3] {
+    let (lat, lon) = ((lat_deg as f64).to_radians(), (lon_deg as f64).to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
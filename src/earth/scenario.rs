@@ -0,0 +1,265 @@
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::earth::crust::CrustType;
+use crate::earth::error::WorldSimError;
+use crate::earth::limits::Limits;
+use crate::earth::mantle::{HotSpot, PlateBuilder, WilsonStage};
+use crate::earth::planet::PlanetConfig;
+use crate::earth::simulation::{Simulation, SimulationReport};
+use crate::earth::Earth;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PlanetPreset {
+    #[default]
+    Earth,
+    Mars,
+    Io,
+}
+
+impl PlanetPreset {
+    pub fn config(&self) -> PlanetConfig {
+        match self {
+            PlanetPreset::Earth => PlanetConfig::earth(),
+            PlanetPreset::Mars => PlanetConfig::mars(),
+            PlanetPreset::Io => PlanetConfig::io(),
+        }
+    }
+}
+
+// A plate to place at the start. Omitted fields take the PlateBuilder defaults; neighbours
+// are indices into the scenario's plate list.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PlateSpec {
+    pub center_lat_deg: f32,
+    pub center_lon_deg: f32,
+    pub crust: CrustType,
+    pub area_km2: f32,
+    pub age_myr: f32,
+    pub velocity_cm_per_year: f32,
+    pub direction_deg: f32,
+    pub transform_boundary: bool,
+    pub back_arc_spreading_cm_per_year: Option<f32>,
+    pub volcanic_activity: f32,
+    pub wilson_stage: WilsonStage,
+    pub neighbors: Vec<usize>,
+}
+
+impl Default for PlateSpec {
+    fn default() -> Self {
+        let plate = PlateBuilder::new().build();
+        Self {
+            center_lat_deg: plate.center_lat_deg,
+            center_lon_deg: plate.center_lon_deg,
+            crust: plate.crust.kind,
+            area_km2: plate.area_km2,
+            age_myr: plate.age_myr,
+            velocity_cm_per_year: plate.velocity_cm_per_year,
+            direction_deg: plate.motion_direction_deg,
+            transform_boundary: plate.is_transform_boundary,
+            back_arc_spreading_cm_per_year: None,
+            volcanic_activity: plate.volcanic_activity_factor,
+            wilson_stage: plate.wilson_stage,
+            neighbors: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotSpotSpec {
+    pub lat_deg: f32,
+    pub lon_deg: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub surface_age_myr: f32,
+}
+
+// Everything needed to reproduce a run without touching code. Only the duration and step
+// are required in a config file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scenario {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub planet: PlanetPreset,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seed: u64,
+    pub total_years: f32,
+    pub step_years: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub plates: Vec<PlateSpec>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hot_spots: Vec<HotSpotSpec>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub limits: Limits,
+}
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    Io(std::io::Error),
+    Parse(String),
+    UnsupportedFormat(String),
+    // A plate lists a neighbour index past the end of the scenario's plate list.
+    UnknownNeighbor { plate: usize, neighbor: usize, plate_count: usize },
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::Io(err) => write!(f, "could not read scenario: {}", err),
+            ScenarioError::Parse(message) => write!(f, "invalid scenario: {}", message),
+            ScenarioError::UnsupportedFormat(path) => {
+                write!(f, "unsupported scenario file '{}', expected a .json or .toml file", path)
+            }
+            ScenarioError::UnknownNeighbor { plate, neighbor, plate_count } => {
+                write!(f, "plate {} lists neighbour {}, but the scenario has only {} plates", plate, neighbor, plate_count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl Scenario {
+    // Reads a scenario, choosing the format from the file extension. JSON needs the `serde`
+    // feature and TOML the `toml` feature.
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScenarioError> {
+        let path = path.as_ref();
+        // The file is only read once its format is known to be supported.
+        match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "serde")]
+            Some("json") => Self::from_json_str(&std::fs::read_to_string(path).map_err(ScenarioError::Io)?),
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml_str(&std::fs::read_to_string(path).map_err(ScenarioError::Io)?),
+            _ => Err(ScenarioError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json_str(text: &str) -> Result<Self, ScenarioError> {
+        serde_json::from_str(text).map_err(|err| ScenarioError::Parse(err.to_string()))
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(text: &str) -> Result<Self, ScenarioError> {
+        toml::from_str(text).map_err(|err| ScenarioError::Parse(err.to_string()))
+    }
+
+    // Fails if a plate names a neighbour index the scenario has no plate for.
+    pub fn build_earth(&self) -> Result<Earth, ScenarioError> {
+        let mut earth = Earth::with_planet_seeded(self.planet.config(), self.seed);
+        earth.limits = self.limits;
+
+        let ids: Vec<u32> = self
+            .plates
+            .iter()
+            .map(|spec| {
+                let mut builder = PlateBuilder::new()
+                    .center(spec.center_lat_deg, spec.center_lon_deg)
+                    .crust(spec.crust)
+                    .area_km2(spec.area_km2)
+                    .age_myr(spec.age_myr)
+                    .velocity(spec.velocity_cm_per_year)
+                    .direction_deg(spec.direction_deg)
+                    .transform_boundary(spec.transform_boundary)
+                    .volcanic_activity(spec.volcanic_activity)
+                    .wilson_stage(spec.wilson_stage);
+                if let Some(rate) = spec.back_arc_spreading_cm_per_year {
+                    builder = builder.back_arc_spreading(rate);
+                }
+                earth.mantle.add_plate(builder.build())
+            })
+            .collect();
+
+        for (plate, (spec, &id)) in self.plates.iter().zip(&ids).enumerate() {
+            for &neighbor in &spec.neighbors {
+                let &neighbor_id = ids.get(neighbor).ok_or(ScenarioError::UnknownNeighbor { plate, neighbor, plate_count: ids.len() })?;
+                earth.mantle.connect_plates(id, neighbor_id).expect("every scenario plate was just added");
+            }
+        }
+
+        for spec in &self.hot_spots {
+            earth.mantle.hot_spots.push(HotSpot {
+                lat_deg: spec.lat_deg,
+                lon_deg: spec.lon_deg,
+                surface_age_myr: spec.surface_age_myr,
+                track: Vec::new(),
+            });
+        }
+        Ok(earth)
+    }
+
+    pub fn run(&self) -> Result<SimulationReport, WorldSimError> {
+        Simulation::new(self.build_earth()?).run(self.total_years, self.step_years, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn a_json_scenario_runs_the_configured_number_of_steps() {
+        let scenario = Scenario::from_json_str(
+            r#"{
+                "planet": "mars",
+                "seed": 3,
+                "total_years": 5000000,
+                "step_years": 1000000,
+                "plates": [{ "center_lon_deg": -40.0 }, { "center_lon_deg": 40.0, "crust": "continental", "neighbors": [0] }],
+                "hot_spots": [{ "lat_deg": 18.0, "lon_deg": -134.0 }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(scenario.planet, PlanetPreset::Mars);
+        assert_eq!(scenario.plates.len(), 2);
+        assert_eq!(scenario.plates[1].crust, CrustType::Continental);
+
+        let report = scenario.run().unwrap();
+        assert_eq!(report.steps, 5);
+        assert_eq!(report.elapsed_years, 5_000_000.0);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn a_toml_scenario_runs_the_configured_number_of_steps() {
+        let scenario = Scenario::from_toml_str(
+            r#"
+            seed = 3
+            total_years = 3000000.0
+            step_years = 1000000.0
+
+            [[plates]]
+            center_lat_deg = 10.0
+            crust = "continental"
+            wilson_stage = "rifting"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(scenario.planet, PlanetPreset::Earth);
+        assert_eq!((scenario.plates[0].crust, scenario.plates[0].wilson_stage), (CrustType::Continental, WilsonStage::Rifting));
+        assert_eq!(scenario.run().unwrap().steps, 3);
+    }
+
+    #[test]
+    fn a_neighbour_past_the_plate_list_is_refused() {
+        let scenario = Scenario {
+            planet: PlanetPreset::Earth,
+            seed: 1,
+            total_years: 1_000_000.0,
+            step_years: 1_000_000.0,
+            plates: vec![PlateSpec::default(), PlateSpec { neighbors: vec![0, 2], ..PlateSpec::default() }],
+            hot_spots: Vec::new(),
+            limits: Limits::default(),
+        };
+        let err = scenario.build_earth().unwrap_err();
+        assert!(matches!(err, ScenarioError::UnknownNeighbor { plate: 1, neighbor: 2, plate_count: 2 }), "{err}");
+        assert!(matches!(scenario.run(), Err(WorldSimError::Serialization(message)) if message.contains("neighbour 2")));
+    }
+}
@@ -0,0 +1,36 @@
+// Birch's law: P-wave velocity rises linearly with density (in g/cm³), along a line set by
+// the mean atomic mass of the material, so rock and core alloy each follow their own.
+const SILICATE_BIRCH_INTERCEPT_KM_PER_S: f32 = -1.87;
+const SILICATE_BIRCH_SLOPE_KM_PER_S_PER_G_CM3: f32 = 3.05;
+const IRON_BIRCH_INTERCEPT_KM_PER_S: f32 = -1.9;
+const IRON_BIRCH_SLOPE_KM_PER_S_PER_G_CM3: f32 = 1.0;
+// Waves slow through rock hotter than its reference state, by this much per degree.
+const VP_TEMPERATURE_SENSITIVITY_KM_PER_S_PER_C: f32 = 5e-4;
+const KG_M3_PER_G_CM3: f32 = 1_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Material {
+    Silicate,
+    Iron,
+}
+
+// Density and P-wave velocity at one radius, as listed in PREM.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadialSample {
+    pub radius_km: f32,
+    pub density: f32,
+    pub p_velocity_km_per_s: f32,
+}
+
+// Velocity for the material at this density, slowed by however much warmer it is than its
+// reference temperature (or sped up if cooler).
+pub fn p_wave_velocity_km_per_s(material: Material, density: f32, temperature_anomaly_c: f32) -> f32 {
+    let (intercept, slope) = match material {
+        Material::Silicate => (SILICATE_BIRCH_INTERCEPT_KM_PER_S, SILICATE_BIRCH_SLOPE_KM_PER_S_PER_G_CM3),
+        Material::Iron => (IRON_BIRCH_INTERCEPT_KM_PER_S, IRON_BIRCH_SLOPE_KM_PER_S_PER_G_CM3),
+    };
+    let velocity = intercept + slope * density / KG_M3_PER_G_CM3 - VP_TEMPERATURE_SENSITIVITY_KM_PER_S_PER_C * temperature_anomaly_c;
+    velocity.max(0.0)
+}
@@ -0,0 +1,459 @@
+use crate::earth::error::WorldSimError;
+use crate::earth::mantle::{MantleEvent, TRANSFORM_SLIP_THRESHOLD_MPA, TRANSFORM_STRESS_RATE_MPA_PER_YEAR};
+use crate::earth::outer_core::REVERSAL_FIELD_THRESHOLD;
+use crate::earth::snapshot::EarthSnapshot;
+use crate::earth::telemetry::Recorder;
+use crate::earth::validation::Validator;
+use crate::earth::Earth;
+#[cfg(all(feature = "serde", feature = "std"))]
+use log::{info, warn};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+// Longest step allowed while the field is weak enough to reverse, so a coarse step
+// can't swallow several reversals in one roll.
+const REVERSAL_STEP_YEARS: f32 = 100_000.0;
+// Step run_until_steady advances by between checks for a steady state.
+const STEADY_STATE_STEP_YEARS: f32 = 1_000_000.0;
+// Steps in a row without a single event after which `events` gives up, so a planet that has
+// gone quiet ends the iterator instead of spinning forever.
+pub const MAX_QUIET_STEPS: u32 = 10_000;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Simulation {
+    pub earth: Earth,
+    pub elapsed_years: f32,
+    pub steps: u32,
+    // Running totals since the simulation was created, kept as it steps.
+    pub reversals: usize,
+    pub subducted_volume_km3: f32,
+    pub volcanic_crust_km: f32,
+    pub event_counts: BTreeMap<String, usize>,
+    // Where and how often to save, if at all. Not part of the saved state itself.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    checkpoint: Option<(u32, PathBuf)>,
+}
+
+// What a run added up to, plus the state it ended in.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationReport {
+    pub planet: String,
+    pub steps: u32,
+    pub elapsed_years: f32,
+    pub inner_core_radius_km: f32,
+    pub reversals: usize,
+    pub subducted_volume_km3: f32,
+    pub volcanic_crust_km: f32,
+    pub plate_count: usize,
+    pub hot_spot_count: usize,
+    pub final_state: EarthSnapshot,
+    // Number of mantle events of each kind over the whole run.
+    pub event_counts: BTreeMap<String, usize>,
+}
+
+impl SimulationReport {
+    #[cfg(feature = "std")]
+    pub fn describe(&self) {
+        println!("Simulation report ({}):", self.planet);
+        println!("  Steps: {}, Elapsed: {:.0} years", self.steps, self.elapsed_years);
+        println!("  Inner core radius: {:.1} km, Reversals: {}", self.inner_core_radius_km, self.reversals);
+        println!("  Subducted: {:.1} km³, Volcanic crust: {:.3} km", self.subducted_volume_km3, self.volcanic_crust_km);
+        println!("  Plates: {}, Hot spots: {}", self.plate_count, self.hot_spot_count);
+        for (field, value) in self.final_state.fields() {
+            println!("  {}: {}", field, value);
+        }
+        for (name, count) in &self.event_counts {
+            println!("  {} event(s): {}", name, count);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(std::io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(err) => write!(f, "could not access checkpoint: {}", err),
+            CheckpointError::Parse(message) => write!(f, "invalid checkpoint: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl Simulation {
+    pub fn new(earth: Earth) -> Self {
+        Self {
+            earth,
+            elapsed_years: 0.0,
+            steps: 0,
+            reversals: 0,
+            subducted_volume_km3: 0.0,
+            volcanic_crust_km: 0.0,
+            event_counts: BTreeMap::new(),
+            checkpoint: None,
+        }
+    }
+
+    // Starts the run over on a freshly reset Earth, clearing the running totals. Checkpointing
+    // carries on as configured.
+    pub fn reset(&mut self) {
+        self.earth.reset();
+        self.elapsed_years = 0.0;
+        self.steps = 0;
+        self.reversals = 0;
+        self.subducted_volume_km3 = 0.0;
+        self.volcanic_crust_km = 0.0;
+        self.event_counts.clear();
+    }
+
+    // Saves the whole simulation to `path` after every `n_steps` steps from here on; zero
+    // turns checkpointing off. Each save replaces the previous one.
+    pub fn checkpoint_every(&mut self, n_steps: u32, path: &Path) {
+        self.checkpoint = (n_steps > 0).then(|| (n_steps, path.to_path_buf()));
+    }
+
+    // Writes to a sibling temporary file first and renames it into place, so a crash
+    // mid-write leaves the previous checkpoint intact.
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub fn save_checkpoint(&self, path: &Path) -> Result<(), CheckpointError> {
+        let json = serde_json::to_string(self).map_err(|err| CheckpointError::Parse(err.to_string()))?;
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        std::fs::write(&partial, json).map_err(CheckpointError::Io)?;
+        std::fs::rename(&partial, path).map_err(CheckpointError::Io)
+    }
+
+    // Picks a run back up exactly where a checkpoint left it, random streams included, so it
+    // continues as if it had never stopped. Checkpointing has to be set up again if wanted.
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub fn resume_from(path: &Path) -> Result<Simulation, CheckpointError> {
+        let text = std::fs::read_to_string(path).map_err(CheckpointError::Io)?;
+        serde_json::from_str(&text).map_err(|err| CheckpointError::Parse(err.to_string()))
+    }
+
+    // A step that isn't positive would never use up `total_years`, so it is refused up front.
+    pub fn run(&mut self, total_years: f32, step_years: f32, mut recorder: Option<&mut dyn Recorder>) -> Result<SimulationReport, WorldSimError> {
+        Validator::new().positive("step_years", step_years).finish()?;
+        let mut remaining = total_years;
+
+        while remaining > 0.0 {
+            let years = step_years.min(remaining);
+            self.advance(years, &mut recorder)?;
+            remaining -= years;
+        }
+        Ok(self.report())
+    }
+
+    // Like `run`, but shortens the step whenever a transform boundary is about to slip or
+    // the field is weak enough to reverse, never going below `min_step_years`. Both bounds
+    // must be positive: a boundary sitting right at its slip threshold would otherwise pin
+    // the step at zero.
+    pub fn run_adaptive(
+        &mut self,
+        total_years: f32,
+        max_step_years: f32,
+        min_step_years: f32,
+        mut recorder: Option<&mut dyn Recorder>,
+    ) -> Result<SimulationReport, WorldSimError> {
+        Validator::new()
+            .positive("max_step_years", max_step_years)
+            .positive("min_step_years", min_step_years)
+            .finish()?;
+        let mut remaining = total_years;
+
+        while remaining > 0.0 {
+            let years = self.adaptive_step_years(max_step_years, min_step_years).min(remaining);
+            self.advance(years, &mut recorder)?;
+            remaining -= years;
+        }
+        Ok(self.report())
+    }
+
+    // Steps until no key observable changes by more than `tol`, relative to its size, over a
+    // single step, or until `max_years` have passed. Returns whether it settled first, leaving
+    // the simulation at the state it settled in.
+    pub fn run_until_steady(&mut self, tol: f32, max_years: f32) -> Result<bool, WorldSimError> {
+        let mut remaining = max_years;
+        let mut before = self.steady_state_observables();
+
+        while remaining > 0.0 {
+            let years = STEADY_STATE_STEP_YEARS.min(remaining);
+            self.advance(years, &mut None)?;
+            remaining -= years;
+
+            // A short final step is held to a proportionally tighter tolerance.
+            let step_tol = tol * years / STEADY_STATE_STEP_YEARS;
+            let after = self.steady_state_observables();
+            let settled = before.iter().zip(&after).all(|(b, a)| (a - b).abs() <= step_tol * b.abs().max(1.0));
+            if settled {
+                return Ok(true);
+            }
+            before = after;
+        }
+        Ok(false)
+    }
+
+    // Steps `step_years` at a time, only as the iterator is polled, yielding each event with
+    // the elapsed time at the end of its step. A step always runs to completion, so dropping
+    // the iterator early leaves the simulation consistent and ready to carry on; events of the
+    // last step not yet pulled are dropped with it. It ends once MAX_QUIET_STEPS steps in a
+    // row have produced nothing, otherwise stop it with `take_while`, `find` or the like. A
+    // failed step is yielded as its error and ends it.
+    pub fn events(&mut self, step_years: f32) -> impl Iterator<Item = Result<(f32, MantleEvent), WorldSimError>> + '_ {
+        let mut pending = VecDeque::new();
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let mut quiet_steps = 0;
+            while pending.is_empty() {
+                if quiet_steps >= MAX_QUIET_STEPS {
+                    return None;
+                }
+                match self.advance(step_years, &mut None) {
+                    Ok(events) => pending.extend(events),
+                    Err(err) => {
+                        failed = true;
+                        return Some(Err(err));
+                    }
+                }
+                quiet_steps += 1;
+            }
+            pending.pop_front().map(|event| Ok((self.elapsed_years, event)))
+        })
+    }
+
+    // The running totals so far together with the current state.
+    pub fn report(&self) -> SimulationReport {
+        let earth = &self.earth;
+        SimulationReport {
+            planet: earth.planet.name.clone(),
+            steps: self.steps,
+            elapsed_years: self.elapsed_years,
+            inner_core_radius_km: earth.inner_core.radius_km,
+            reversals: self.reversals,
+            subducted_volume_km3: self.subducted_volume_km3,
+            volcanic_crust_km: self.volcanic_crust_km,
+            plate_count: earth.mantle.plates.len(),
+            hot_spot_count: earth.mantle.hot_spots.len(),
+            final_state: earth.snapshot(),
+            event_counts: self.event_counts.clone(),
+        }
+    }
+
+    fn advance(&mut self, years: f32, recorder: &mut Option<&mut dyn Recorder>) -> Result<Vec<MantleEvent>, WorldSimError> {
+        let reversals_before = self.earth.outer_core.reversal_history.len();
+        let step_events = self.earth.step(years)?;
+        self.elapsed_years += years;
+        self.steps += 1;
+        self.reversals += self.earth.outer_core.reversal_history.len() - reversals_before;
+        for event in &step_events {
+            match *event {
+                MantleEvent::Subduction { volume_km3, .. } => self.subducted_volume_km3 += volume_km3,
+                MantleEvent::VolcanicGrowth { km, .. } => self.volcanic_crust_km += km,
+                _ => {}
+            }
+            *self.event_counts.entry(event.name().to_string()).or_insert(0) += 1;
+        }
+        if let Some(recorder) = recorder.as_mut() {
+            recorder.on_step(self.elapsed_years, &self.earth);
+        }
+        self.checkpoint_if_due();
+        Ok(step_events)
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    fn checkpoint_if_due(&self) {
+        if let Some((n_steps, path)) = &self.checkpoint {
+            if self.steps.is_multiple_of(*n_steps) {
+                match self.save_checkpoint(path) {
+                    Ok(()) => info!("Checkpoint at step {} saved to {}.", self.steps, path.display()),
+                    Err(err) => warn!("Checkpoint at step {} failed: {}", self.steps, err),
+                }
+            }
+        }
+    }
+
+    // Without serde there is nothing to write a checkpoint with, and without std nowhere to
+    // write it.
+    #[cfg(not(all(feature = "serde", feature = "std")))]
+    fn checkpoint_if_due(&self) {}
+
+    // Inner-core radius, every layer's temperature, and the plates' total area and mean crust
+    // thickness.
+    pub fn steady_state_observables(&self) -> [f32; 10] {
+        let earth = &self.earth;
+        let mantle = &earth.mantle;
+        [
+            earth.inner_core.radius_km,
+            earth.inner_core.temperature_c,
+            earth.outer_core.temperature_c,
+            mantle.lithosphere.temperature_c,
+            mantle.asthenosphere.temperature_c,
+            mantle.transition_zone.temperature_c,
+            mantle.lower_mantle.temperature_c,
+            mantle.d_prime_prime.temperature_c,
+            mantle.total_plate_area_km2(),
+            mantle.mean_crust_thickness_km(),
+        ]
+    }
+
+    fn adaptive_step_years(&self, max_step_years: f32, min_step_years: f32) -> f32 {
+        let mut step_years = max_step_years;
+
+        for plate in &self.earth.mantle.plates {
+            if plate.is_transform_boundary {
+                let headroom_mpa = (TRANSFORM_SLIP_THRESHOLD_MPA - plate.shear_stress_mpa).max(0.0);
+                step_years = step_years.min(headroom_mpa / TRANSFORM_STRESS_RATE_MPA_PER_YEAR);
+            }
+        }
+
+        if self.earth.outer_core.dynamo_active && self.earth.outer_core.magnetic_field_strength < REVERSAL_FIELD_THRESHOLD {
+            step_years = step_years.min(REVERSAL_STEP_YEARS);
+        }
+
+        step_years.max(min_step_years)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::mantle::{PlateBuilder, WilsonStage};
+
+    fn faulted_simulation() -> Simulation {
+        let mut earth = Earth::new_seeded(5);
+        earth.mantle.add_plate(PlateBuilder::new().transform_boundary(true).build());
+        Simulation::new(earth)
+    }
+
+    fn slips(report: &SimulationReport) -> usize {
+        report.event_counts.get("transform_slip").copied().unwrap_or(0)
+    }
+
+    #[test]
+    fn adaptive_steps_catch_slips_a_coarse_step_swallows() {
+        let coarse = faulted_simulation().run(30_000_000.0, 30_000_000.0, None).unwrap();
+        let adaptive = faulted_simulation().run_adaptive(30_000_000.0, 30_000_000.0, 10_000.0, None).unwrap();
+        assert_eq!(slips(&coarse), 1);
+        assert!(slips(&adaptive) > slips(&coarse), "{} adaptive slips", slips(&adaptive));
+    }
+
+    // An old spreading plate that starts to subduct within a few steps.
+    fn ageing_simulation() -> Simulation {
+        let mut earth = Earth::new_seeded(8);
+        earth.mantle.add_plate(PlateBuilder::new().wilson_stage(WilsonStage::Spreading).age_myr(149.5).build());
+        Simulation::new(earth)
+    }
+
+    fn is_subduction(event: &MantleEvent) -> bool {
+        matches!(event, MantleEvent::SubductionStarted { .. } | MantleEvent::Subduction { .. })
+    }
+
+    #[test]
+    fn events_stop_stepping_once_the_caller_stops_pulling() {
+        let mut reference = ageing_simulation();
+        while !reference.advance(100_000.0, &mut None).unwrap().iter().any(is_subduction) {
+            assert!(reference.steps < 100, "the plate never subducted");
+        }
+
+        let mut simulation = ageing_simulation();
+        let before: Vec<_> = simulation.events(100_000.0).map(Result::unwrap).take_while(|(_, event)| !is_subduction(event)).collect();
+        assert!(before.iter().all(|(time, _)| *time < reference.elapsed_years));
+        assert_eq!(simulation.steps, reference.steps);
+        assert_eq!(simulation.elapsed_years, reference.elapsed_years);
+
+        // Picking up again carries on from the step that subducted, not from a later one.
+        let (time, _) = simulation.events(100_000.0).next().unwrap().unwrap();
+        assert_eq!(simulation.steps, reference.steps + 1);
+        assert!(time > reference.elapsed_years);
+    }
+
+    #[test]
+    fn a_damped_planet_settles_before_the_cap() {
+        // No plates to collide and an inner core that has stopped freezing: everything left
+        // relaxes toward equilibrium.
+        let mut earth = Earth::new_seeded(3);
+        earth.inner_core.set_cooling_model(0.0);
+        let mut simulation = Simulation::new(earth);
+
+        let max_years = 1_000_000_000.0;
+        assert!(simulation.run_until_steady(1e-3, max_years).unwrap());
+        assert!(simulation.elapsed_years < max_years, "settled only at the cap");
+        assert_eq!(simulation.elapsed_years, simulation.steps as f32 * STEADY_STATE_STEP_YEARS);
+
+        // Held to no change at all, the same planet runs out the cap instead.
+        let mut strict = faulted_simulation();
+        assert!(!strict.run_until_steady(0.0, 5_000_000.0).unwrap());
+        assert_eq!(strict.elapsed_years, 5_000_000.0);
+    }
+
+    #[test]
+    fn non_positive_steps_are_refused() {
+        let mut simulation = faulted_simulation();
+        assert!(matches!(simulation.run(1_000_000.0, 0.0, None), Err(WorldSimError::OutOfRange(_))));
+        assert!(matches!(simulation.run(1_000_000.0, -5.0, None), Err(WorldSimError::OutOfRange(_))));
+        assert!(matches!(simulation.run_adaptive(1_000_000.0, 100_000.0, 0.0, None), Err(WorldSimError::OutOfRange(_))));
+        assert_eq!(simulation.steps, 0);
+    }
+
+    #[test]
+    fn a_reset_simulation_repeats_its_first_run() {
+        let mut simulation = Simulation::new(Earth::new_seeded(5));
+        let first = simulation.run(5_000_000.0, 1_000_000.0, None).unwrap();
+
+        simulation.reset();
+        assert_eq!((simulation.steps, simulation.elapsed_years, simulation.reversals), (0, 0.0, 0));
+        assert!(simulation.event_counts.is_empty());
+        let second = simulation.run(5_000_000.0, 1_000_000.0, None).unwrap();
+        assert_eq!(second, first);
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn a_resumed_checkpoint_finishes_like_an_uninterrupted_run() {
+        let path = std::env::temp_dir().join(format!("world-sim-checkpoint-{}.json", std::process::id()));
+        let mut uninterrupted = faulted_simulation();
+        uninterrupted.run(10_000_000.0, 1_000_000.0, None).unwrap();
+
+        let mut first_half = faulted_simulation();
+        first_half.checkpoint_every(5, &path);
+        first_half.run(5_000_000.0, 1_000_000.0, None).unwrap();
+        drop(first_half);
+
+        let mut resumed = Simulation::resume_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(resumed.steps, 5);
+        resumed.run(5_000_000.0, 1_000_000.0, None).unwrap();
+
+        assert_eq!(resumed.steps, uninterrupted.steps);
+        assert_eq!(resumed.earth, uninterrupted.earth);
+        assert_eq!(resumed.event_counts, uninterrupted.event_counts);
+    }
+
+    #[test]
+    fn the_report_counts_every_forced_reversal() {
+        // A weak inner-core contribution keeps the field below the reversal threshold, so
+        // every step draws for a reversal.
+        let weak_field = || {
+            let mut earth = Earth::new_seeded(17);
+            earth.inner_core.magnetic_contribution_factor = 0.1;
+            Simulation::new(earth)
+        };
+        let mut simulation = weak_field();
+        let report = simulation.run(20_000_000.0, 100_000.0, None).unwrap();
+
+        assert_eq!(report.steps, 200);
+        assert!(report.reversals > 0);
+        assert_eq!(report.reversals, simulation.earth.outer_core.reversal_history.len());
+        assert_eq!(weak_field().run(20_000_000.0, 100_000.0, None).unwrap().reversals, report.reversals);
+    }
+}
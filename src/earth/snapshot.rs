@@ -0,0 +1,113 @@
+use crate::earth::Earth;
+
+// Field order is part of the snapshot format; append new fields at the end.
+pub const SNAPSHOT_FIELDS: [&str; 22] = [
+    "inner_core.radius_km",
+    "inner_core.temperature_c",
+    "inner_core.mass_kg",
+    "inner_core.cumulative_rotation_deg",
+    "outer_core.temperature_c",
+    "outer_core.convection_strength",
+    "outer_core.magnetic_field_strength",
+    "outer_core.magnetic_polarity_normal",
+    "outer_core.heat_flux_to_mantle_w_per_m2",
+    "mantle.lithosphere.temperature_c",
+    "mantle.asthenosphere.temperature_c",
+    "mantle.transition_zone.temperature_c",
+    "mantle.lower_mantle.temperature_c",
+    "mantle.lower_mantle.stored_slab_volume_km3",
+    "mantle.d_prime_prime.temperature_c",
+    "mantle.d_prime_prime.plume_generation_potential",
+    "mantle.plate_count",
+    "mantle.total_plate_area_km2",
+    "mantle.hot_spot_count",
+    "ocean.volume_km3",
+    "ocean.sea_level_m",
+    "thermal_conductivity_w_per_m_k",
+];
+
+// Key scalars of an Earth at one moment, in SNAPSHOT_FIELDS order. Flags and counts are
+// stored as numbers so every field can be compared the same way.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EarthSnapshot {
+    values: [f64; SNAPSHOT_FIELDS.len()],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldDelta {
+    pub field: &'static str,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl EarthSnapshot {
+    pub fn capture(earth: &Earth) -> Self {
+        let mantle = &earth.mantle;
+        Self {
+            values: [
+                earth.inner_core.radius_km as f64,
+                earth.inner_core.temperature_c as f64,
+                earth.inner_core.mass_kg,
+                earth.inner_core.cumulative_rotation_deg as f64,
+                earth.outer_core.temperature_c as f64,
+                earth.outer_core.convection_strength as f64,
+                earth.outer_core.magnetic_field_strength as f64,
+                if earth.outer_core.magnetic_polarity_normal { 1.0 } else { 0.0 },
+                earth.outer_core.heat_flux_to_mantle_w_per_m2 as f64,
+                mantle.lithosphere.temperature_c as f64,
+                mantle.asthenosphere.temperature_c as f64,
+                mantle.transition_zone.temperature_c as f64,
+                mantle.lower_mantle.temperature_c as f64,
+                mantle.lower_mantle.stored_slab_volume_km3 as f64,
+                mantle.d_prime_prime.temperature_c as f64,
+                mantle.d_prime_prime.plume_generation_potential as f64,
+                mantle.plates.len() as f64,
+                mantle.plates.iter().map(|plate| plate.area_km2 as f64).sum(),
+                mantle.hot_spots.len() as f64,
+                earth.ocean.volume_km3 as f64,
+                earth.ocean.sea_level_m as f64,
+                earth.thermal_conductivity_w_per_m_k as f64,
+            ],
+        }
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = (&'static str, f64)> + '_ {
+        SNAPSHOT_FIELDS.iter().copied().zip(self.values.iter().copied())
+    }
+
+    pub fn get(&self, field: &str) -> Option<f64> {
+        let index = SNAPSHOT_FIELDS.iter().position(|&name| name == field)?;
+        Some(self.values[index])
+    }
+
+    // Fields whose value differs in `other`, treating this snapshot as the earlier state.
+    pub fn diff(&self, other: &EarthSnapshot) -> Vec<FieldDelta> {
+        SNAPSHOT_FIELDS
+            .iter()
+            .zip(self.values.iter().zip(other.values.iter()))
+            .filter(|(_, (before, after))| before != after)
+            .map(|(&field, (&before, &after))| FieldDelta { field, before, after })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_step_shows_up_only_in_the_fields_it_touched() {
+        let mut earth = Earth::new_seeded(11);
+        let before = earth.snapshot();
+        earth.step(1_000_000.0).unwrap();
+        let delta = before.diff(&earth.snapshot());
+
+        let radius = delta.iter().find(|delta| delta.field == "inner_core.radius_km").expect("the inner core grew");
+        assert!(radius.after > radius.before);
+        assert!(delta.iter().all(|delta| delta.field != "thermal_conductivity_w_per_m_k"));
+        assert!(delta.iter().all(|delta| delta.before != delta.after));
+        assert!(before.diff(&before).is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::earth::Earth;
+
+// Column order is part of the output format; append new columns at the end.
+pub const TELEMETRY_COLUMNS: [&str; 7] = [
+    "t_years",
+    "inner_core_radius_km",
+    "outer_core_magnetic_field_strength",
+    "outer_core_convection_strength",
+    "lithosphere_temperature_c",
+    "hot_spot_count",
+    "sea_level_m",
+];
+
+pub trait Recorder {
+    fn on_step(&mut self, t_years: f32, earth: &Earth);
+}
+
+#[cfg(feature = "std")]
+fn telemetry_row(t_years: f32, earth: &Earth) -> [f32; TELEMETRY_COLUMNS.len()] {
+    [
+        t_years,
+        earth.inner_core.radius_km,
+        earth.outer_core.magnetic_field_strength,
+        earth.outer_core.convection_strength,
+        earth.mantle.lithosphere.temperature_c,
+        earth.mantle.hot_spots.len() as f32,
+        earth.ocean.sea_level_m,
+    ]
+}
+
+// Writes a header line followed by one comma-separated row per step. Write errors are
+// held back and reported by `finish`, so the simulation loop isn't interrupted.
+#[cfg(feature = "std")]
+pub struct CsvRecorder<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> CsvRecorder<W> {
+    pub fn new(writer: W) -> Self {
+        let mut recorder = Self { writer, error: None };
+        let header = TELEMETRY_COLUMNS.join(",");
+        recorder.write(|w| writeln!(w, "{}", header));
+        recorder
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write(&mut self, f: impl FnOnce(&mut W) -> io::Result<()>) {
+        if self.error.is_none() {
+            if let Err(error) = f(&mut self.writer) {
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> Recorder for CsvRecorder<W> {
+    fn on_step(&mut self, t_years: f32, earth: &Earth) {
+        let row = telemetry_row(t_years, earth)
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.write(|w| writeln!(w, "{}", row));
+    }
+}
+
+// Emits a JSON array with one object per step, keyed by the telemetry column names.
+// The closing bracket is written by `finish`.
+#[cfg(feature = "std")]
+pub struct JsonRecorder<W: Write> {
+    writer: W,
+    steps: usize,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> JsonRecorder<W> {
+    pub fn new(writer: W) -> Self {
+        let mut recorder = Self { writer, steps: 0, error: None };
+        recorder.write(|w| write!(w, "["));
+        recorder
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write(|w| writeln!(w, "]"));
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write(&mut self, f: impl FnOnce(&mut W) -> io::Result<()>) {
+        if self.error.is_none() {
+            if let Err(error) = f(&mut self.writer) {
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> Recorder for JsonRecorder<W> {
+    fn on_step(&mut self, t_years: f32, earth: &Earth) {
+        let fields = TELEMETRY_COLUMNS
+            .iter()
+            .zip(telemetry_row(t_years, earth))
+            .map(|(column, value)| format!("\"{}\":{}", column, value))
+            .collect::<Vec<_>>()
+            .join(",");
+        let separator = if self.steps == 0 { "" } else { "," };
+        self.steps += 1;
+        self.write(|w| write!(w, "{}{{{}}}", separator, fields));
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::earth::simulation::Simulation;
+
+    #[test]
+    fn five_steps_write_a_header_and_five_rows() {
+        let mut simulation = Simulation::new(Earth::new_seeded(9));
+        let mut recorder = CsvRecorder::new(Vec::new());
+        simulation.run(500_000.0, 100_000.0, Some(&mut recorder)).unwrap();
+
+        let csv = String::from_utf8(recorder.finish().unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "t_years,inner_core_radius_km,outer_core_magnetic_field_strength,outer_core_convection_strength,lithosphere_temperature_c,hot_spot_count,sea_level_m");
+        assert!(lines[1..].iter().all(|row| row.split(',').count() == TELEMETRY_COLUMNS.len()));
+    }
+
+    #[test]
+    fn json_rows_are_keyed_by_column() {
+        let mut simulation = Simulation::new(Earth::new_seeded(9));
+        let mut recorder = JsonRecorder::new(Vec::new());
+        simulation.run(500_000.0, 100_000.0, Some(&mut recorder)).unwrap();
+
+        let json = String::from_utf8(recorder.finish().unwrap()).unwrap();
+        assert!(json.starts_with("[{\"t_years\":") && json.trim_end().ends_with("}]"));
+        assert_eq!(json.matches("\"sea_level_m\"").count(), 5);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::earth::units::km_to_m;
+pub use crate::earth::units::SECONDS_PER_YEAR;
+use std::f64::consts::PI;
+
+pub const CORE_HEAT_CAPACITY_J_PER_KG_K: f64 = 800.0;
+pub const MANTLE_HEAT_CAPACITY_J_PER_KG_K: f64 = 1200.0;
+
+pub const DEFAULT_THERMAL_CONDUCTIVITY_W_PER_M_K: f32 = 4.0;
+// Surface temperature the column cools against when no crust is present.
+pub const DEFAULT_SURFACE_TEMPERATURE_C: f32 = 15.0;
+// Share of the explicit scheme's stability limit a single substep may use.
+const DIFFUSION_STABILITY_FACTOR: f64 = 0.5;
+
+// Molar gas constant (J/mol/K).
+pub const GAS_CONSTANT_J_PER_MOL_K: f64 = 8.314;
+
+// Half-lives (years) of the isotopes that heat the silicate Earth.
+pub const URANIUM_238_HALF_LIFE_YEARS: f64 = 4.468e9;
+pub const URANIUM_235_HALF_LIFE_YEARS: f64 = 7.04e8;
+pub const THORIUM_232_HALF_LIFE_YEARS: f64 = 1.405e10;
+pub const POTASSIUM_40_HALF_LIFE_YEARS: f64 = 1.248e9;
+// Share of present-day radiogenic heat each isotope supplies, in the order above.
+const PRESENT_ISOTOPE_HEAT_SHARES: [f64; 4] = [0.39, 0.02, 0.42, 0.17];
+// How long ago the planet formed; a new planet starts with the heat production of that time.
+pub const PLANET_AGE_YEARS: f64 = 4.5e9;
+
+pub fn sphere_area_m2(radius_km: f32) -> f64 {
+    let radius_m = km_to_m(radius_km as f64);
+    4.0 * PI * radius_m * radius_m
+}
+
+pub fn shell_volume_m3(inner_radius_km: f32, outer_radius_km: f32) -> f64 {
+    let inner_m = km_to_m(inner_radius_km as f64);
+    let outer_m = km_to_m(outer_radius_km as f64);
+    (4.0 / 3.0) * PI * (outer_m.powi(3) - inner_m.powi(3))
+}
+
+// Heat carried in one year by a flux (W/m²) through the given area.
+pub fn flux_to_tj_per_year(flux_w_per_m2: f32, area_m2: f64) -> f64 {
+    (flux_w_per_m2 as f64 * area_m2 * SECONDS_PER_YEAR) / 1e12
+}
+
+pub fn heat_content_tj(mass_kg: f64, heat_capacity_j_per_kg_k: f64, temperature_c: f32) -> f64 {
+    mass_kg * heat_capacity_j_per_kg_k * (temperature_c as f64 + 273.15) / 1e12
+}
+
+// Solid-state creep follows an Arrhenius law: viscosity falls exponentially as the rock
+// warms above its reference temperature and rises as it cools.
+pub fn arrhenius_viscosity(reference_viscosity: f32, reference_temperature_c: f32, temperature_c: f32, activation_energy_j_per_mol: f32) -> f32 {
+    let (t_k, reference_k) = (temperature_c as f64 + 273.15, reference_temperature_c as f64 + 273.15);
+    let exponent = activation_energy_j_per_mol as f64 / GAS_CONSTANT_J_PER_MOL_K * (1.0 / t_k.max(1.0) - 1.0 / reference_k);
+    (reference_viscosity as f64 * exponent.exp()).min(f32::MAX as f64) as f32
+}
+
+// Heat released per kilogram of rock by each long-lived radioactive isotope (W/kg).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadiogenicHeat {
+    pub uranium_238: f64,
+    pub uranium_235: f64,
+    pub thorium_232: f64,
+    pub potassium_40: f64,
+}
+
+impl RadiogenicHeat {
+    // Splits a present-day heat production across the isotopes and winds each back to when
+    // the planet formed. The short-lived U-235 and K-40 make early heating about five times
+    // today's.
+    pub fn primordial(present_w_per_kg: f64) -> Self {
+        let [uranium_238, uranium_235, thorium_232, potassium_40] = PRESENT_ISOTOPE_HEAT_SHARES;
+        let mut heat = Self {
+            uranium_238: present_w_per_kg * uranium_238,
+            uranium_235: present_w_per_kg * uranium_235,
+            thorium_232: present_w_per_kg * thorium_232,
+            potassium_40: present_w_per_kg * potassium_40,
+        };
+        heat.decay(-PLANET_AGE_YEARS as f32);
+        heat
+    }
+
+    pub fn total(&self) -> f64 {
+        self.uranium_238 + self.uranium_235 + self.thorium_232 + self.potassium_40
+    }
+
+    pub fn decay(&mut self, years: f32) {
+        let remaining = |half_life_years: f64| (-std::f64::consts::LN_2 * years as f64 / half_life_years).exp();
+        self.uranium_238 *= remaining(URANIUM_238_HALF_LIFE_YEARS);
+        self.uranium_235 *= remaining(URANIUM_235_HALF_LIFE_YEARS);
+        self.thorium_232 *= remaining(THORIUM_232_HALF_LIFE_YEARS);
+        self.potassium_40 *= remaining(POTASSIUM_40_HALF_LIFE_YEARS);
+    }
+}
+
+// One cell of the vertical conduction column.
+pub struct ConductiveLayer {
+    pub thickness_km: f32,
+    pub temperature_c: f32,
+    pub density: f32,
+    pub heat_capacity_j_per_kg_k: f64,
+    // Heat generated inside the cell, warming it as it conducts.
+    pub heat_production_w_per_kg: f64,
+}
+
+impl ConductiveLayer {
+    // Heat needed to warm one square metre of the layer by one kelvin (J/m²/K).
+    fn heat_capacity_per_m2(&self) -> f64 {
+        self.density as f64 * self.heat_capacity_j_per_kg_k * km_to_m(self.thickness_km as f64)
+    }
+}
+
+// Explicit finite-volume conduction through layers ordered from the bottom up. The bottom
+// is insulated (it is the centre of the planet); the top loses heat to a fixed surface
+// temperature through a conducting lid of `lid_km`. Each layer also warms by its own heat
+// production. The step is split into substeps short
+// enough to stay stable, and the number used is returned.
+pub fn diffuse_layers(
+    layers: &mut [ConductiveLayer],
+    conductivity_w_per_m_k: f32,
+    surface_temperature_c: f32,
+    lid_km: f32,
+    years: f32,
+) -> u32 {
+    // Layers with no thickness left (a frozen outer core) hold no heat and drop out.
+    let mut layers: Vec<&mut ConductiveLayer> = layers.iter_mut().filter(|layer| layer.thickness_km > 0.0).collect();
+    if layers.is_empty() || years <= 0.0 {
+        return 0;
+    }
+    let k = conductivity_w_per_m_k as f64;
+
+    // Conductance (W/m²/K) across each interface: conductances[i] sits above layer i.
+    let conductances: Vec<f64> = (0..layers.len())
+        .map(|i| {
+            let below_m = layers[i].thickness_km as f64 * 500.0;
+            let above_m = match layers.get(i + 1) {
+                Some(next) => next.thickness_km as f64 * 500.0,
+                None => km_to_m(lid_km as f64),
+            };
+            k / (below_m + above_m)
+        })
+        .collect();
+    let capacities: Vec<f64> = layers.iter().map(|layer| layer.heat_capacity_per_m2()).collect();
+
+    let max_substep_s = (0..layers.len())
+        .map(|i| {
+            let below = if i == 0 { 0.0 } else { conductances[i - 1] };
+            capacities[i] / (below + conductances[i])
+        })
+        .fold(f64::INFINITY, f64::min)
+        * DIFFUSION_STABILITY_FACTOR;
+
+    let total_s = years as f64 * SECONDS_PER_YEAR;
+    let substeps = (total_s / max_substep_s).ceil().max(1.0);
+    let dt_s = total_s / substeps;
+
+    let mut temperatures: Vec<f64> = layers.iter().map(|layer| layer.temperature_c as f64).collect();
+    for _ in 0..substeps as u32 {
+        let upward_flux: Vec<f64> = (0..temperatures.len())
+            .map(|i| {
+                let above = temperatures.get(i + 1).copied().unwrap_or(surface_temperature_c as f64);
+                conductances[i] * (temperatures[i] - above)
+            })
+            .collect();
+        for i in 0..temperatures.len() {
+            let from_below = if i == 0 { 0.0 } else { upward_flux[i - 1] };
+            temperatures[i] += (from_below - upward_flux[i]) * dt_s / capacities[i];
+            temperatures[i] += layers[i].heat_production_w_per_kg * dt_s / layers[i].heat_capacity_j_per_kg_k;
+        }
+    }
+
+    for (layer, temperature) in layers.iter_mut().zip(temperatures) {
+        layer.temperature_c = temperature as f32;
+    }
+    substeps as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rock_layer(temperature_c: f32) -> ConductiveLayer {
+        ConductiveLayer {
+            thickness_km: 100.0,
+            temperature_c,
+            density: 4000.0,
+            heat_capacity_j_per_kg_k: MANTLE_HEAT_CAPACITY_J_PER_KG_K,
+            heat_production_w_per_kg: 0.0,
+        }
+    }
+
+    #[test]
+    fn a_hot_bottom_and_cold_top_settle_into_a_monotonic_geotherm() {
+        // Start with a jagged profile: hot at the base, cold at the top, and uneven in between.
+        let mut layers: Vec<ConductiveLayer> =
+            [4000.0, 1200.0, 3000.0, 800.0, 2500.0, 100.0].into_iter().map(rock_layer).collect();
+
+        for _ in 0..50 {
+            let substeps = diffuse_layers(&mut layers, DEFAULT_THERMAL_CONDUCTIVITY_W_PER_M_K, 0.0, 50.0, 2.0e8);
+            // 200 Myr is longer than the stable substep for 100 km of rock, so it must be split.
+            assert!(substeps > 1, "the step was not split: {substeps}");
+        }
+
+        for pair in layers.windows(2) {
+            assert!(
+                pair[0].temperature_c > pair[1].temperature_c,
+                "geotherm not decreasing upward: {} then {}",
+                pair[0].temperature_c,
+                pair[1].temperature_c
+            );
+        }
+        assert!(layers.iter().all(|layer| layer.temperature_c.is_finite() && layer.temperature_c >= 0.0));
+    }
+
+    #[test]
+    fn a_flux_converts_to_heat_in_watts_per_square_metre() {
+        // 1 W/m² over a square kilometre is a megawatt, or about 31.6 TJ a year.
+        let tj = flux_to_tj_per_year(1.0, 1e6);
+        assert!((tj - SECONDS_PER_YEAR * 1e6 / 1e12).abs() < 1e-12, "{tj}");
+
+        // Earth's present core-mantle boundary flux of about 0.08 W/m² carries roughly
+        // 12 TW, or 3.8e8 TJ a year.
+        let cmb_tj = flux_to_tj_per_year(0.08, sphere_area_m2(3_480.0));
+        let back_w_per_m2 = cmb_tj * 1e12 / (sphere_area_m2(3_480.0) * SECONDS_PER_YEAR);
+        assert!((cmb_tj / 3.8e8 - 1.0).abs() < 0.05, "{cmb_tj}");
+        assert!((back_w_per_m2 - 0.08).abs() < 1e-6, "{back_w_per_m2}");
+    }
+
+    #[test]
+    fn radiogenic_heat_decays_by_each_half_life_over_the_planet_age() {
+        let initial = RadiogenicHeat::primordial(7e-12);
+        let mut heat = initial.clone();
+        heat.decay(PLANET_AGE_YEARS as f32);
+
+        let expected = |half_life_years: f64| 0.5f64.powf(PLANET_AGE_YEARS / half_life_years);
+        let ratios = [
+            (heat.uranium_238 / initial.uranium_238, URANIUM_238_HALF_LIFE_YEARS),
+            (heat.uranium_235 / initial.uranium_235, URANIUM_235_HALF_LIFE_YEARS),
+            (heat.thorium_232 / initial.thorium_232, THORIUM_232_HALF_LIFE_YEARS),
+            (heat.potassium_40 / initial.potassium_40, POTASSIUM_40_HALF_LIFE_YEARS),
+        ];
+        for (ratio, half_life_years) in ratios {
+            assert!((ratio - expected(half_life_years)).abs() < 1e-6, "{} vs {}", ratio, expected(half_life_years));
+        }
+
+        // Back to the present-day rate, a fraction of what the young planet produced.
+        assert!((heat.total() - 7e-12).abs() < 7e-18);
+        assert!(heat.total() / initial.total() < 0.5);
+    }
+}
@@ -0,0 +1,140 @@
+// The simulation stores plate speeds in cm/yr, erosion and growth in mm/yr, distances in km
+// and time in years or Myr. These helpers keep the conversions between them in one place.
+
+pub const SECONDS_PER_YEAR: f64 = 3.1536e7;
+pub const YEARS_PER_MYR: f32 = 1_000_000.0;
+pub const M_PER_KM: f32 = 1_000.0;
+pub const CM_PER_KM: f32 = 100_000.0;
+pub const MM_PER_KM: f32 = 1_000_000.0;
+
+pub fn cm_per_year_to_m_per_s(cm_per_year: f32) -> f64 {
+    cm_per_year as f64 / 100.0 / SECONDS_PER_YEAR
+}
+
+pub fn cm_per_year_to_mm_per_year(cm_per_year: f32) -> f32 {
+    cm_per_year * 10.0
+}
+
+pub fn cm_per_year_to_km_per_year(cm_per_year: f32) -> f32 {
+    cm_per_year / CM_PER_KM
+}
+
+pub fn mm_per_year_to_km_per_year(mm_per_year: f32) -> f32 {
+    mm_per_year / MM_PER_KM
+}
+
+pub fn years_to_myr(years: f32) -> f32 {
+    years / YEARS_PER_MYR
+}
+
+pub fn myr_to_years(myr: f32) -> f32 {
+    myr * YEARS_PER_MYR
+}
+
+pub fn km_to_m(km: f64) -> f64 {
+    km * M_PER_KM as f64
+}
+
+pub fn m_to_km(m: f64) -> f64 {
+    m / M_PER_KM as f64
+}
+
+// A speed stored in cm/yr, the unit plate motions are quoted in.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Velocity(pub f32);
+
+impl Velocity {
+    pub fn from_mm_per_year(mm_per_year: f32) -> Self {
+        Self(mm_per_year / 10.0)
+    }
+
+    pub fn as_cm_per_year(self) -> f32 {
+        self.0
+    }
+
+    pub fn as_mm_per_year(self) -> f32 {
+        cm_per_year_to_mm_per_year(self.0)
+    }
+
+    pub fn as_km_per_year(self) -> f32 {
+        cm_per_year_to_km_per_year(self.0)
+    }
+
+    pub fn as_m_per_s(self) -> f64 {
+        cm_per_year_to_m_per_s(self.0)
+    }
+
+    // Distance covered at this speed over the given number of years.
+    pub fn distance_over(self, years: f32) -> Length {
+        Length(self.as_km_per_year() * years)
+    }
+}
+
+// A length stored in km.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Length(pub f32);
+
+impl Length {
+    pub fn from_m(m: f32) -> Self {
+        Self(m / M_PER_KM)
+    }
+
+    pub fn as_km(self) -> f32 {
+        self.0
+    }
+
+    pub fn as_m(self) -> f32 {
+        self.0 * M_PER_KM
+    }
+
+    pub fn as_cm(self) -> f32 {
+        self.0 * CM_PER_KM
+    }
+
+    pub fn as_mm(self) -> f32 {
+        self.0 * MM_PER_KM
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() <= expected.abs() * 1e-6, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn free_functions_convert_known_values() {
+        // 1 cm/yr is 0.01 m over 3.1536e7 s.
+        assert_close(cm_per_year_to_m_per_s(1.0), 3.170_979e-10);
+        assert_eq!(cm_per_year_to_mm_per_year(5.0), 50.0);
+        assert_eq!(cm_per_year_to_km_per_year(100_000.0), 1.0);
+        assert_eq!(mm_per_year_to_km_per_year(1_000_000.0), 1.0);
+        assert_eq!(years_to_myr(2_500_000.0), 2.5);
+        assert_eq!(myr_to_years(2.5), 2_500_000.0);
+        assert_eq!(km_to_m(6_371.0), 6_371_000.0);
+        assert_eq!(m_to_km(6_371_000.0), 6_371.0);
+    }
+
+    #[test]
+    fn a_velocity_reads_back_in_every_unit() {
+        let velocity = Velocity::from_mm_per_year(50.0);
+        assert_eq!(velocity.as_cm_per_year(), 5.0);
+        assert_eq!(velocity.as_mm_per_year(), 50.0);
+        assert_eq!(velocity.as_km_per_year(), 5e-5);
+        assert_close(velocity.as_m_per_s(), 5.0 * 3.170_979e-10);
+        assert_eq!(velocity.distance_over(1_000_000.0), Length(50.0));
+    }
+
+    #[test]
+    fn a_length_reads_back_in_every_unit() {
+        let length = Length::from_m(2_500.0);
+        assert_eq!(length.as_km(), 2.5);
+        assert_eq!(length.as_m(), 2_500.0);
+        assert_eq!(length.as_cm(), 250_000.0);
+        assert_eq!(length.as_mm(), 2_500_000.0);
+    }
+}
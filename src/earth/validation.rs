@@ -0,0 +1,95 @@
+use std::fmt;
+
+pub const ABSOLUTE_ZERO_C: f32 = -273.15;
+// How far a composition may drift from 100% before it counts as impossible.
+const COMPOSITION_TOLERANCE_PCT: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constraint {
+    Positive,
+    NonNegative,
+    AboveAbsoluteZero,
+    SumsToHundred,
+    WithinRange { min: f32, max: f32 },
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::Positive => f.write_str("must be positive"),
+            Constraint::NonNegative => f.write_str("must not be negative"),
+            Constraint::AboveAbsoluteZero => write!(f, "must be above absolute zero ({} °C)", ABSOLUTE_ZERO_C),
+            Constraint::SumsToHundred => write!(f, "must sum to 100% (±{}%)", COMPOSITION_TOLERANCE_PCT),
+            Constraint::WithinRange { min, max } => write!(f, "must be between {} and {}", min, max),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub field: &'static str,
+    pub value: f32,
+    pub constraint: Constraint,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {} {}", self.field, self.value, self.constraint)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+// Collects every violated constraint instead of stopping at the first, so one call reports
+// everything wrong with a state. NaN fails every check.
+pub struct Validator {
+    errors: Vec<ValidationError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    pub fn positive(&mut self, field: &'static str, value: f32) -> &mut Self {
+        self.check(field, value, value > 0.0, Constraint::Positive)
+    }
+
+    pub fn non_negative(&mut self, field: &'static str, value: f32) -> &mut Self {
+        self.check(field, value, value >= 0.0, Constraint::NonNegative)
+    }
+
+    pub fn above_absolute_zero(&mut self, field: &'static str, value: f32) -> &mut Self {
+        self.check(field, value, value > ABSOLUTE_ZERO_C, Constraint::AboveAbsoluteZero)
+    }
+
+    pub fn composition(&mut self, field: &'static str, parts_pct: &[f32]) -> &mut Self {
+        let total: f32 = parts_pct.iter().sum();
+        self.check(field, total, (total - 100.0).abs() <= COMPOSITION_TOLERANCE_PCT, Constraint::SumsToHundred)
+    }
+
+    pub fn within(&mut self, field: &'static str, value: f32, min: f32, max: f32) -> &mut Self {
+        self.check(field, value, value >= min && value <= max, Constraint::WithinRange { min, max })
+    }
+
+    pub fn finish(&mut self) -> Result<(), Vec<ValidationError>> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(std::mem::take(&mut self.errors))
+        }
+    }
+
+    fn check(&mut self, field: &'static str, value: f32, holds: bool, constraint: Constraint) -> &mut Self {
+        if !holds {
+            self.errors.push(ValidationError { field, value, constraint });
+        }
+        self
+    }
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,140 @@
+// Mass of water in one present-day ocean (Gt); transition-zone water capacity is counted in these.
+pub const OCEAN_MASS_WATER_GT: f64 = 1.4e9;
+
+// Volatiles bound into new ocean crust by hydrothermal alteration (Gt per km² of crust),
+// carried down per km³ of slab, and released per km³ of erupted magma.
+const HYDRATION_WATER_GT_PER_KM2: f64 = 0.2;
+const HYDRATION_CARBON_GT_PER_KM2: f64 = 0.02;
+const SLAB_WATER_GT_PER_KM3: f64 = 0.06;
+const SLAB_CARBON_GT_PER_KM3: f64 = 0.003;
+const MAGMA_WATER_GT_PER_KM3: f64 = 0.014;
+const MAGMA_CARBON_GT_PER_KM3: f64 = 0.002;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolatileReservoirs {
+    pub surface_gt: f64,
+    pub crustal_gt: f64,
+    pub deep_gt: f64,
+}
+
+impl VolatileReservoirs {
+    pub fn total_gt(&self) -> f64 {
+        self.surface_gt + self.crustal_gt + self.deep_gt
+    }
+}
+
+// Water and carbon moved between the surface, the crust and the deep mantle. Every flux
+// takes from one reservoir and adds to another, so each species' total never changes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolatileBudget {
+    pub water: VolatileReservoirs,
+    pub carbon: VolatileReservoirs,
+}
+
+impl VolatileBudget {
+    pub fn new() -> Self {
+        Self {
+            water: VolatileReservoirs {
+                surface_gt: OCEAN_MASS_WATER_GT,
+                crustal_gt: 0.3 * OCEAN_MASS_WATER_GT,
+                deep_gt: 0.5 * OCEAN_MASS_WATER_GT,
+            },
+            carbon: VolatileReservoirs {
+                surface_gt: 4.0e4,
+                crustal_gt: 9.0e7,
+                deep_gt: 3.0e8,
+            },
+        }
+    }
+
+    // Newly formed crust takes up surface water and carbon.
+    pub fn hydrate(&mut self, new_crust_area_km2: f32) {
+        let area = new_crust_area_km2 as f64;
+        transfer(&mut self.water.surface_gt, &mut self.water.crustal_gt, area * HYDRATION_WATER_GT_PER_KM2);
+        transfer(&mut self.carbon.surface_gt, &mut self.carbon.crustal_gt, area * HYDRATION_CARBON_GT_PER_KM2);
+    }
+
+    // A sinking slab carries crustal volatiles down. Water the deep mantle has no room for
+    // is driven off and returned to the surface through the arc.
+    pub fn subduct(&mut self, slab_volume_km3: f32, deep_water_capacity_gt: f64) {
+        let volume = slab_volume_km3 as f64;
+        transfer(&mut self.water.crustal_gt, &mut self.water.deep_gt, volume * SLAB_WATER_GT_PER_KM3);
+        transfer(&mut self.carbon.crustal_gt, &mut self.carbon.deep_gt, volume * SLAB_CARBON_GT_PER_KM3);
+
+        let overflow = self.water.deep_gt - deep_water_capacity_gt;
+        transfer(&mut self.water.deep_gt, &mut self.water.surface_gt, overflow);
+    }
+
+    // Magma rising from the mantle degasses part of what it holds at the surface.
+    pub fn degas(&mut self, magma_km3: f32) {
+        let volume = magma_km3 as f64;
+        transfer(&mut self.water.deep_gt, &mut self.water.surface_gt, volume * MAGMA_WATER_GT_PER_KM3);
+        transfer(&mut self.carbon.deep_gt, &mut self.carbon.surface_gt, volume * MAGMA_CARBON_GT_PER_KM3);
+    }
+
+    #[cfg(feature = "std")]
+    pub fn describe(&self) {
+        println!("{}", self.summary());
+    }
+
+    pub fn summary(&self) -> String {
+        [
+            format!(
+                "  Water: surface {:.3e} Gt, crust {:.3e} Gt, deep {:.3e} Gt",
+                self.water.surface_gt, self.water.crustal_gt, self.water.deep_gt
+            ),
+            format!(
+                "  Carbon: surface {:.3e} Gt, crust {:.3e} Gt, deep {:.3e} Gt",
+                self.carbon.surface_gt, self.carbon.crustal_gt, self.carbon.deep_gt
+            ),
+        ]
+        .join("\n")
+    }
+}
+
+// Moves up to `amount` from one reservoir to another, never leaving the source negative.
+fn transfer(from: &mut f64, to: &mut f64, amount: f64) -> f64 {
+    let moved = amount.min(*from).max(0.0);
+    *from -= moved;
+    *to += moved;
+    moved
+}
+
+impl Default for VolatileBudget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shallow_gt(reservoirs: &VolatileReservoirs) -> f64 {
+        reservoirs.surface_gt + reservoirs.crustal_gt
+    }
+
+    #[test]
+    fn cycling_conserves_volatiles_up_to_what_is_stored_deep() {
+        let mut budget = VolatileBudget::new();
+        let before = budget.clone();
+        let deep_water_capacity_gt = 0.6 * OCEAN_MASS_WATER_GT;
+
+        for _ in 0..100 {
+            budget.hydrate(2e6);
+            budget.subduct(1e6, deep_water_capacity_gt);
+            budget.degas(5e5);
+            assert!(budget.water.deep_gt <= deep_water_capacity_gt);
+        }
+
+        for (after, before) in [(&budget.water, &before.water), (&budget.carbon, &before.carbon)] {
+            let stored_deep_gt = after.deep_gt - before.deep_gt;
+            let lost_shallow_gt = shallow_gt(before) - shallow_gt(after);
+            assert!((lost_shallow_gt - stored_deep_gt).abs() <= 1e-9 * before.total_gt());
+            assert!((after.total_gt() - before.total_gt()).abs() <= 1e-9 * before.total_gt());
+        }
+        assert!(budget.carbon.deep_gt > before.carbon.deep_gt);
+    }
+}
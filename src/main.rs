@@ -1,85 +1,107 @@
-mod earth {
-    pub mod inner_core;
-    pub mod outer_core;
-    pub mod mantle;
-    pub mod crust;
-}
+mod earth;
 
-use earth::inner_core::InnerCore;
-use earth::outer_core::OuterCore;
-use earth::mantle::{Mantle, Plate, HotSpot};
+use earth::Earth;
+#[cfg(feature = "std")]
+use earth::crust::CrustType;
+#[cfg(feature = "std")]
+use earth::mantle::{ConsumptionCause, ConvectionCell, HotSpot, MantleEvent, PlateBuilder, WilsonStage};
 
+#[cfg(feature = "std")]
 fn main() {
-    let mut core = InnerCore::new();
-    let mut outer = OuterCore::new(&core);
-
-    let mut mantle = Mantle::new();
-
-    mantle.plates.push(Plate {
-        area_km2: 100_000_000.0,
-        age_myr: 50.0,
-        velocity_cm_per_year: 5.0,
-        motion_direction_deg: 90.0,
-        is_subducting: false,
-        is_transform_boundary: true,
-        shear_stress_mpa: 0.0,
-        has_back_arc_spreading: true,
-        back_arc_spreading_rate_cm_per_year: 2.0,
-        volcanic_activity_factor: 0.7,
-    });
+    env_logger::init();
+
+    if let Some(path) = std::env::args().nth(1) {
+        run_scenario(&path);
+        return;
+    }
+
+    let mut earth = Earth::new();
+
+    let plate_a = earth.mantle.add_plate(
+        PlateBuilder::new()
+            .center(0.0, -30.0)
+            .crust(CrustType::Oceanic)
+            .area_km2(100_000_000.0)
+            .age_myr(50.0)
+            .velocity(5.0)
+            .direction_deg(90.0)
+            .transform_boundary(true)
+            .back_arc_spreading(2.0)
+            .volcanic_activity(0.7)
+            .build(),
+    );
 
-    mantle.plates.push(Plate {
-        area_km2: 60_000_000.0,
-        age_myr: 10.0,
-        velocity_cm_per_year: 3.0,
-        motion_direction_deg: 45.0,
-        is_subducting: false,
-        is_transform_boundary: false,
-        shear_stress_mpa: 0.0,
-        has_back_arc_spreading: false,
-        back_arc_spreading_rate_cm_per_year: 0.0,
-        volcanic_activity_factor: 0.3,
+    let plate_b = earth.mantle.add_plate(
+        PlateBuilder::new()
+            .center(10.0, 20.0)
+            .crust(CrustType::Continental)
+            .area_km2(60_000_000.0)
+            .age_myr(10.0)
+            .velocity(3.0)
+            .direction_deg(45.0)
+            .volcanic_activity(0.3)
+            .wilson_stage(WilsonStage::Rifting)
+            .build(),
+    );
+
+    earth.mantle.connect_plates(plate_a, plate_b).expect("both plates were just added");
+
+    // An upwelling between the two plates pushes them apart.
+    earth.mantle.convection_cells.push(ConvectionCell {
+        center_lat_deg: 5.0,
+        center_lon_deg: -5.0,
+        upwelling_strength: 3.0,
+        radius_km: 2_500.0,
     });
 
-    mantle.hot_spots.push(HotSpot {
+    earth.mantle.hot_spots.push(HotSpot {
         lat_deg: 0.0,
         lon_deg: -155.0,
         surface_age_myr: 1.0,
+        track: Vec::new(),
     });
 
-    mantle.hot_spots.push(HotSpot {
+    earth.mantle.hot_spots.push(HotSpot {
         lat_deg: 20.0,
         lon_deg: 120.0,
         surface_age_myr: 0.5,
+        track: Vec::new(),
     });
 
     println!("=== Initial States ===");
-    core.describe();
-    outer.describe();
-    mantle.describe();
+    earth.describe();
 
     let sim_years = 10_000_000.0;
     println!("\n=== Simulating {:.0} years ===", sim_years);
 
-    core.update_crystallization(sim_years);
-    outer.update_dynamics(&core, sim_years);
-    mantle.update_advanced_dynamics(sim_years);
-    mantle.d_prime_prime.transfer_heat(outer.heat_flux_to_mantle_mw_per_m2, 0.02);
+    let events = match earth.step(sim_years) {
+        Ok(events) => events,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+    report_events(&events, sim_years);
 
     println!("\n=== States After Simulation ===");
-    core.describe();
-    outer.describe();
-    mantle.describe();
+    earth.describe();
 
     println!("\n=== Plate Motions and Dynamics ===");
-    for (i, plate) in mantle.plates.iter().enumerate() {
+    for plate in &earth.mantle.plates {
         println!(
-            "Plate {}: Area {:.1} km², Age {:.1} Myr, Velocity {:.2} cm/yr, Direction {:.1}°, Subducting: {}, Transform: {}, Back-arc: {}, Volcanic factor: {:.2}",
-            i + 1,
+            "Plate {} ({}): Area {:.1} km², Age {:.1} Myr, Stage {:?}, Center ({:.1}°, {:.1}°), Velocity {:.2} cm/yr, Direction {:.1}°, Euler pole ({:.1}°, {:.1}°) at {:.3}°/Myr, Subducting: {}, Transform: {}, Back-arc: {}, Volcanic factor: {:.2}",
+            plate.id,
+            plate.crust.kind,
             plate.area_km2,
             plate.age_myr,
+            plate.wilson_stage,
+            plate.center_lat_deg,
+            plate.center_lon_deg,
             plate.velocity_cm_per_year,
             plate.motion_direction_deg,
+            plate.euler_pole.lat_deg,
+            plate.euler_pole.lon_deg,
+            plate.euler_pole.rate_deg_per_myr,
             plate.is_subducting,
             plate.is_transform_boundary,
             plate.has_back_arc_spreading,
@@ -87,14 +109,180 @@ fn main() {
         );
     }
 
+    println!("\n=== Plate Boundaries ===");
+    for boundary in earth.mantle.classify_boundaries() {
+        println!(
+            "Plates {} and {}: {:?}, {:.0} km",
+            boundary.plate_id, boundary.other_plate_id, boundary.kind, boundary.length_km
+        );
+    }
+
     println!("\n=== Hot Spot Positions ===");
-    for (i, hs) in mantle.hot_spots.iter().enumerate() {
+    for (i, hs) in earth.mantle.hot_spots.iter().enumerate() {
         println!(
-            "Hot Spot {}: Lat {:.1}°, Lon {:.1}°, Surface Age {:.1} Myr",
+            "Hot Spot {}: Lat {:.1}°, Lon {:.1}°, Surface Age {:.1} Myr, Track {:.0} km",
             i + 1,
             hs.lat_deg,
             hs.lon_deg,
-            hs.surface_age_myr
+            hs.surface_age_myr,
+            hs.track_length_km()
+        );
+    }
+
+    println!("\n=== Named Features ===");
+    for feature in &earth.mantle.features {
+        println!(
+            "{}: {:?} at Lat {:.1}°, Lon {:.1}°, formed at {:.1} Myr",
+            feature.name, feature.kind, feature.lat_deg, feature.lon_deg, feature.birth_myr
         );
     }
 }
+
+// Without `std` there is no console, file system or entropy, so this only builds a seeded
+// planet and steps it: a check that the physics kernel stands on its own.
+#[cfg(not(feature = "std"))]
+fn main() {
+    let mut earth = Earth::new_seeded(0);
+    earth.step(10_000_000.0).expect("a freshly built planet steps in order");
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+fn run_scenario(path: &str) {
+    let report = earth::scenario::Scenario::load(path)
+        .map_err(earth::error::WorldSimError::from)
+        .and_then(|scenario| scenario.run());
+    match report {
+        Ok(report) => report.describe(),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(all(not(feature = "serde"), feature = "std"))]
+fn run_scenario(path: &str) {
+    eprintln!("Cannot load {}: scenario files need the `serde` feature.", path);
+    std::process::exit(1);
+}
+
+#[cfg(feature = "std")]
+fn report_events(events: &[MantleEvent], years: f32) {
+    let mut total_volcanic_growth = 0.0;
+    let mut total_tectonic_deformation = 0.0;
+    let mut total_subduction_volume = 0.0;
+    let mut total_spread_area = 0.0;
+    let mut total_back_arc_area = 0.0;
+    let mut total_erosion = 0.0;
+    let mut subduction_events = 0;
+    let mut earthquake_events = 0;
+    let mut largest_magnitude: f32 = 0.0;
+    let mut lithosphere_quakes = 0;
+    let mut largest_lithosphere_magnitude: f32 = 0.0;
+    let mut plume_events = 0;
+    let mut flood_basalts = 0;
+    let mut total_flood_basalt_km3 = 0.0;
+    let mut subducted_plates = 0;
+    let mut overridden_plates = 0;
+    let mut collisions = 0;
+    let mut total_collision_area = 0.0;
+    let mut total_orogeny = 0.0;
+    let mut eruptions = 0;
+    let mut total_sediment_routed = 0.0;
+    let mut largest_vei = 0;
+    let mut breakups = 0;
+
+    for event in events {
+        match *event {
+            MantleEvent::SubductionStarted { .. } => subduction_events += 1,
+            MantleEvent::TransformSlip { magnitude, .. } => {
+                earthquake_events += 1;
+                largest_magnitude = largest_magnitude.max(magnitude);
+            }
+            MantleEvent::Spreading { area_km2, .. } => total_spread_area += area_km2,
+            MantleEvent::BackArcSpreading { area_km2, .. } => total_back_arc_area += area_km2,
+            MantleEvent::Subduction { volume_km3, .. } => total_subduction_volume += volume_km3,
+            MantleEvent::VolcanicGrowth { km, .. } => total_volcanic_growth += km,
+            MantleEvent::TectonicDeformation { km, .. } => total_tectonic_deformation += km,
+            MantleEvent::CrustErosion { km, .. } => total_erosion += km,
+            MantleEvent::PlumeSpawned { .. } => plume_events += 1,
+            MantleEvent::LargeIgneousProvince { volume_km3, .. } => {
+                flood_basalts += 1;
+                total_flood_basalt_km3 += volume_km3;
+            }
+            MantleEvent::PlateConsumed { cause: ConsumptionCause::Subduction, .. } => subducted_plates += 1,
+            MantleEvent::PlateConsumed { cause: ConsumptionCause::Collision, .. } => overridden_plates += 1,
+            MantleEvent::Collision { area_km2, .. } => {
+                collisions += 1;
+                total_collision_area += area_km2;
+            }
+            MantleEvent::Orogeny { km, .. } => total_orogeny += km,
+            MantleEvent::VolcanicEruption { vei, .. } => {
+                eruptions += 1;
+                largest_vei = largest_vei.max(vei);
+            }
+            MantleEvent::SedimentRouted { km, .. } => total_sediment_routed += km,
+            MantleEvent::LithosphereQuake { magnitude } => {
+                lithosphere_quakes += 1;
+                largest_lithosphere_magnitude = largest_lithosphere_magnitude.max(magnitude);
+            }
+            MantleEvent::ContinentalBreakup { .. } => breakups += 1,
+        }
+    }
+
+    // Only show significant changes
+    if subduction_events > 0 {
+        println!("⚠️ {} plate(s) started subduction.", subduction_events);
+    }
+    if earthquake_events > 0 {
+        println!("⚡ {} transform fault slip event(s) occurred, largest Mw {:.1}!", earthquake_events, largest_magnitude);
+    }
+    if lithosphere_quakes > 0 {
+        println!("🌐 {} lithospheric stress release(s) from plate convergence, largest Mw {:.1}.", lithosphere_quakes, largest_lithosphere_magnitude);
+    }
+    if total_spread_area > 1000.0 {
+        println!("🌋 Plate spreading: gained {:.1} km² new area.", total_spread_area);
+    }
+    if total_back_arc_area > 100.0 {
+        println!("🌊 Back-arc spreading: plate area increased by {:.1} km².", total_back_arc_area);
+    }
+    if total_subduction_volume > 1000.0 {
+        println!("🌊 Plate subduction: recycling {:.1} km³ into mantle.", total_subduction_volume);
+    }
+    if total_volcanic_growth > 0.001 {
+        println!("🌋 Crust growth: added {:.3} km from volcanism.", total_volcanic_growth);
+    }
+    if total_tectonic_deformation.abs() > 0.01 {
+        println!("⛰️ Crust tectonic adjustment: thickness changed by {:.3} km.", total_tectonic_deformation);
+    }
+    if total_erosion > 0.1 {
+        println!("🌊 Crust erosion: reduced thickness by {:.3} km over {:.0} years.", total_erosion, years);
+    }
+    if total_sediment_routed > 0.001 {
+        println!("🏞️ Sediment transport: {:.3} km carried off highlands into neighbouring basins.", total_sediment_routed);
+    }
+    if collisions > 0 {
+        println!("💥 {} plate collision(s) transferred {:.1} km² to overriding plates.", collisions, total_collision_area);
+    }
+    if total_orogeny > 0.01 {
+        println!("🏔️ Mountain building: convergent margins thickened crust by {:.3} km.", total_orogeny);
+    }
+    if eruptions > 0 {
+        println!("🌋 {} eruption(s) occurred, largest VEI {}.", eruptions, largest_vei);
+    }
+    if breakups > 0 {
+        println!("🌊 {} hyperextended continental margin(s) broke up into new ocean floor.", breakups);
+    }
+    if subducted_plates > 0 {
+        println!("🕳️ {} plate(s) fully consumed by subduction.", subducted_plates);
+    }
+    if overridden_plates > 0 {
+        println!("🕳️ {} plate(s) fully consumed in collisions.", overridden_plates);
+    }
+    if flood_basalts > 0 {
+        println!("🌋 {} plume head(s) erupted as large igneous provinces, {:.0} km³ of flood basalt.", flood_basalts, total_flood_basalt_km3);
+    }
+    if plume_events > 0 {
+        println!("🔥 {} new mantle plume(s) reached the surface as hot spots.", plume_events);
+    }
+}